@@ -1,8 +1,12 @@
-use gsnake_core::models::{LevelDefinition, Position};
-use std::collections::HashSet;
+use gsnake_core::{
+    engine::GameEngine,
+    models::{Direction, GameStatus, LevelDefinition, Position},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
 
 /// Represents special mechanics present in a level
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct LevelMechanics {
     pub has_floating_food: bool,
@@ -12,7 +16,7 @@ pub struct LevelMechanics {
 }
 
 /// Represents detected obstacle patterns in a level
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum ObstaclePattern {
     VerticalWall,
@@ -22,21 +26,45 @@ pub enum ObstaclePattern {
 }
 
 /// Represents complexity metrics for a level
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct ComplexityMetrics {
     pub obstacle_density: f32,
     pub food_count: usize,
     pub grid_area: i32,
+    /// Open cells with exactly one open neighbor, see [`count_dead_ends`]
+    #[serde(default)]
+    pub dead_ends: usize,
+    /// Fraction of open cells with exactly two open neighbors
+    #[serde(default)]
+    pub corridor_ratio: f32,
+}
+
+/// Which symmetry transforms map the level layout onto itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymmetrySet {
+    /// Unchanged when flipped across the vertical axis (x mirrored)
+    pub mirror_left_right: bool,
+    /// Unchanged when flipped across the horizontal axis (y mirrored)
+    pub mirror_top_bottom: bool,
+    /// Unchanged when rotated by 180 degrees
+    pub rotation_180: bool,
+}
+
+impl SymmetrySet {
+    pub fn any(&self) -> bool {
+        self.mirror_left_right || self.mirror_top_bottom || self.rotation_180
+    }
 }
 
 /// Complete analysis result for a level
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct LevelAnalysis {
     pub mechanics: LevelMechanics,
     pub pattern: ObstaclePattern,
     pub complexity: ComplexityMetrics,
+    pub symmetry: SymmetrySet,
 }
 
 /// Analyzes a level definition and returns structured analysis
@@ -45,12 +73,178 @@ pub fn analyze_level(level: &LevelDefinition) -> LevelAnalysis {
     let mechanics = detect_mechanics(level);
     let pattern = detect_obstacle_pattern(&level.obstacles);
     let complexity = calculate_complexity(level);
+    let symmetry = symmetries(level);
 
     LevelAnalysis {
         mechanics,
         pattern,
         complexity,
+        symmetry,
+    }
+}
+
+/// Difficulty score at or below which a level is suggested as "easy"
+const EASY_MAX_SCORE: u32 = 1;
+/// Difficulty score at or below which a level is suggested as "medium"
+const MEDIUM_MAX_SCORE: u32 = 3;
+
+/// Suggests "easy", "medium" or "hard" from analysis metrics and, when known, the
+/// length of the solver's solution.
+///
+/// Points are added for obstacle density, for each special mechanic (spikes and
+/// stones weigh the most), for lots of food and for long solutions.
+pub fn classify_difficulty(analysis: &LevelAnalysis, solution_len: Option<usize>) -> &'static str {
+    let mut score = 0;
+
+    let density = analysis.complexity.obstacle_density;
+    if density > 0.15 {
+        score += 2;
+    } else if density > 0.05 {
+        score += 1;
+    }
+
+    let mechanics = &analysis.mechanics;
+    if mechanics.has_spikes {
+        score += 2;
+    }
+    if mechanics.has_stones {
+        score += 2;
+    }
+    if mechanics.has_falling_food {
+        score += 1;
+    }
+    if mechanics.has_floating_food {
+        score += 1;
+    }
+    if analysis.complexity.food_count > 5 {
+        score += 1;
+    }
+
+    match solution_len {
+        Some(moves) if moves > 40 => score += 2,
+        Some(moves) if moves > 15 => score += 1,
+        _ => {},
+    }
+
+    if score <= EASY_MAX_SCORE {
+        "easy"
+    } else if score <= MEDIUM_MAX_SCORE {
+        "medium"
+    } else {
+        "hard"
+    }
+}
+
+/// Checks which transforms map every cell (obstacles, all food kinds, stones,
+/// spikes, exit and snake) onto a cell of the same kind.
+///
+/// The snake is compared as a set of occupied cells, ignoring which end is the head.
+pub fn symmetries(level: &LevelDefinition) -> SymmetrySet {
+    let width = level.grid_size.width;
+    let height = level.grid_size.height;
+    let layers: [(&str, &[Position]); 8] = [
+        ("obstacle", &level.obstacles),
+        ("food", &level.food),
+        ("floating_food", &level.floating_food),
+        ("falling_food", &level.falling_food),
+        ("stone", &level.stones),
+        ("spike", &level.spikes),
+        ("exit", std::slice::from_ref(&level.exit)),
+        ("snake", &level.snake),
+    ];
+    let cells: HashSet<(&str, i32, i32)> = layers
+        .iter()
+        .flat_map(|(kind, positions)| {
+            positions
+                .iter()
+                .map(move |position| (*kind, position.x, position.y))
+        })
+        .collect();
+
+    // Each transform is a bijection on the grid, so mapping every cell into the
+    // set is enough for the layout to map onto itself.
+    let maps_onto_itself = |transform: &dyn Fn(i32, i32) -> (i32, i32)| {
+        cells.iter().all(|(kind, x, y)| {
+            let (tx, ty) = transform(*x, *y);
+            cells.contains(&(*kind, tx, ty))
+        })
+    };
+
+    SymmetrySet {
+        mirror_left_right: maps_onto_itself(&|x, y| (width - 1 - x, y)),
+        mirror_top_bottom: maps_onto_itself(&|x, y| (x, height - 1 - y)),
+        rotation_180: maps_onto_itself(&|x, y| (width - 1 - x, height - 1 - y)),
+    }
+}
+
+/// A grid transform mapping `(x, y)` to its image
+type CellTransform<'a> = Box<dyn Fn(i32, i32) -> (i32, i32) + 'a>;
+
+/// Stable hash of the layout (obstacles, all food kinds, stones, spikes, exit and snake) that
+/// is the same for every rotation and mirror image of the level.
+///
+/// Square grids are compared under all 8 symmetries of the square. Other grids only under
+/// the 4 that keep their width and height: identity, both mirrors and the 180° rotation.
+/// The snake is compared segment by segment, so which end is the head matters.
+pub fn canonical_fingerprint(level: &LevelDefinition) -> String {
+    let width = level.grid_size.width;
+    let height = level.grid_size.height;
+    let mut transforms: Vec<CellTransform> = vec![
+        Box::new(|x, y| (x, y)),
+        Box::new(move |x, y| (width - 1 - x, y)),
+        Box::new(move |x, y| (x, height - 1 - y)),
+        Box::new(move |x, y| (width - 1 - x, height - 1 - y)),
+    ];
+    if width == height {
+        transforms.extend::<[CellTransform; 4]>([
+            Box::new(move |x, y| (width - 1 - y, x)),
+            Box::new(move |x, y| (y, width - 1 - x)),
+            Box::new(|x, y| (y, x)),
+            Box::new(move |x, y| (width - 1 - y, width - 1 - x)),
+        ]);
+    }
+
+    let canonical = transforms
+        .iter()
+        .map(|transform| layout_key(level, transform))
+        .min()
+        .unwrap_or_default();
+    format!("{:016x}", fnv1a(canonical.as_bytes()))
+}
+
+/// Text form of the layout after `transform`, with every layer but the snake sorted
+fn layout_key(level: &LevelDefinition, transform: &CellTransform) -> String {
+    let mapped = |positions: &[Position]| -> Vec<(i32, i32)> {
+        positions
+            .iter()
+            .map(|position| transform(position.x, position.y))
+            .collect()
+    };
+    let layers: [(&str, &[Position]); 7] = [
+        ("obstacle", &level.obstacles),
+        ("food", &level.food),
+        ("floating_food", &level.floating_food),
+        ("falling_food", &level.falling_food),
+        ("stone", &level.stones),
+        ("spike", &level.spikes),
+        ("exit", std::slice::from_ref(&level.exit)),
+    ];
+
+    let mut key = format!("{}x{}", level.grid_size.width, level.grid_size.height);
+    for (kind, positions) in layers {
+        let mut cells = mapped(positions);
+        cells.sort_unstable();
+        key.push_str(&format!("|{kind}:{cells:?}"));
     }
+    key.push_str(&format!("|snake:{:?}", mapped(&level.snake)));
+    key
+}
+
+/// FNV-1a hash; unlike `DefaultHasher` it is stable across Rust versions and platforms
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
 }
 
 /// Detects which special mechanics are present in the level
@@ -118,17 +312,224 @@ fn calculate_complexity(level: &LevelDefinition) -> ComplexityMetrics {
 
     let food_count = level.food.len() + level.floating_food.len() + level.falling_food.len();
 
+    let neighbor_counts = open_neighbor_counts(level);
+    let corridors = neighbor_counts.iter().filter(|count| **count == 2).count();
+    let corridor_ratio = if neighbor_counts.is_empty() {
+        0.0
+    } else {
+        corridors as f32 / neighbor_counts.len() as f32
+    };
+
     ComplexityMetrics {
         obstacle_density,
         food_count,
         grid_area,
+        dead_ends: neighbor_counts.iter().filter(|count| **count == 1).count(),
+        corridor_ratio,
+    }
+}
+
+/// Counts open cells with exactly one open neighbor; obstacles, stones and the grid
+/// edge close a cell off
+#[allow(dead_code)]
+pub fn count_dead_ends(level: &LevelDefinition) -> usize {
+    open_neighbor_counts(level)
+        .into_iter()
+        .filter(|count| *count == 1)
+        .count()
+}
+
+/// Number of open orthogonal neighbors of every open cell, in row-major order
+fn open_neighbor_counts(level: &LevelDefinition) -> Vec<usize> {
+    let blocked: HashSet<(i32, i32)> = level
+        .obstacles
+        .iter()
+        .chain(&level.stones)
+        .map(|position| (position.x, position.y))
+        .collect();
+    let is_open = |x: i32, y: i32| in_bounds(level, x, y) && !blocked.contains(&(x, y));
+
+    let mut counts = Vec::new();
+    for y in 0..level.grid_size.height {
+        for x in 0..level.grid_size.width {
+            if !is_open(x, y) {
+                continue;
+            }
+            counts.push(
+                [(0, -1), (0, 1), (1, 0), (-1, 0)]
+                    .into_iter()
+                    .filter(|(dx, dy)| is_open(x + dx, y + dy))
+                    .count(),
+            );
+        }
+    }
+    counts
+}
+
+/// Food the level can be won with: regular, floating and falling food together
+pub fn derive_total_food(level: &LevelDefinition) -> u32 {
+    let total = level.food.len() + level.floating_food.len() + level.falling_food.len();
+    // Level arrays cannot practically exceed u32::MAX in real-world usage.
+    total as u32
+}
+
+/// Checks that every position lies on the grid and that the snake is laid out sanely
+pub fn geometry_issues(level: &LevelDefinition) -> Vec<String> {
+    let mut issues = Vec::new();
+    let width = level.grid_size.width;
+    let height = level.grid_size.height;
+
+    let fields: [(&str, &[Position]); 8] = [
+        ("snake", &level.snake),
+        ("obstacles", &level.obstacles),
+        ("food", &level.food),
+        ("floatingFood", &level.floating_food),
+        ("fallingFood", &level.falling_food),
+        ("stones", &level.stones),
+        ("spikes", &level.spikes),
+        ("exit", std::slice::from_ref(&level.exit)),
+    ];
+    for (field, positions) in fields {
+        for position in positions {
+            if !in_bounds(level, position.x, position.y) {
+                issues.push(format!(
+                    "{field} position ({}, {}) is outside the {width}x{height} grid",
+                    position.x, position.y
+                ));
+            }
+        }
+    }
+
+    let mut seen_segments = HashSet::new();
+    for segment in &level.snake {
+        if !seen_segments.insert((segment.x, segment.y)) {
+            issues.push(format!(
+                "snake segments overlap at ({}, {})",
+                segment.x, segment.y
+            ));
+        }
+    }
+
+    // Overlapping segments (distance 0) are already reported above.
+    for pair in level.snake.windows(2) {
+        let (segment, next) = (&pair[0], &pair[1]);
+        if (segment.x - next.x).abs() + (segment.y - next.y).abs() > 1 {
+            issues.push(format!(
+                "snake segments ({}, {}) and ({}, {}) are not adjacent",
+                segment.x, segment.y, next.x, next.y
+            ));
+        }
+    }
+
+    if seen_segments.contains(&(level.exit.x, level.exit.y)) {
+        issues.push(format!(
+            "exit ({}, {}) is placed on a snake segment",
+            level.exit.x, level.exit.y
+        ));
+    }
+
+    let obstacles: HashSet<(i32, i32)> = level
+        .obstacles
+        .iter()
+        .map(|position| (position.x, position.y))
+        .collect();
+    for (field, positions) in fields
+        .into_iter()
+        .filter(|(field, _)| *field != "obstacles")
+    {
+        for position in positions {
+            if obstacles.contains(&(position.x, position.y)) {
+                issues.push(format!(
+                    "obstacle at ({}, {}) overlaps {field}",
+                    position.x, position.y
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Flood-fills from the snake head across cells not blocked by obstacles, stones or spikes.
+///
+/// Gravity is ignored, so the result over-approximates where the snake can go: a
+/// target outside this set can never be reached.
+pub fn reachable_cells(level: &LevelDefinition) -> HashSet<Position> {
+    let blocked: HashSet<(i32, i32)> = level
+        .obstacles
+        .iter()
+        .chain(&level.stones)
+        .chain(&level.spikes)
+        .map(|position| (position.x, position.y))
+        .collect();
+
+    let Some(head) = level.snake.first() else {
+        return HashSet::new();
+    };
+
+    let mut seen = HashSet::from([(head.x, head.y)]);
+    let mut queue = VecDeque::from([(head.x, head.y)]);
+    while let Some((x, y)) = queue.pop_front() {
+        for (dx, dy) in [(0, -1), (0, 1), (1, 0), (-1, 0)] {
+            let next = (x + dx, y + dy);
+            if !in_bounds(level, next.0, next.1) || blocked.contains(&next) {
+                continue;
+            }
+            if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
     }
+
+    seen.into_iter().map(|(x, y)| Position::new(x, y)).collect()
+}
+
+/// Lists food and exit positions that `reachable_cells` cannot reach.
+///
+/// Falling food is skipped because its starting cell is not where it comes to rest.
+pub fn unreachable_targets(level: &LevelDefinition) -> Vec<Position> {
+    let reachable = reachable_cells(level);
+    level
+        .food
+        .iter()
+        .chain(&level.floating_food)
+        .chain(std::iter::once(&level.exit))
+        .filter(|position| !reachable.contains(*position))
+        .cloned()
+        .collect()
+}
+
+/// Checks that at least one opening move is accepted and does not end the game.
+///
+/// Each direction is tried on a fresh clone of the start state, so a spawn flush
+/// against a wall with its only free side behind the body is reported as stuck.
+pub fn snake_can_move(level: &LevelDefinition) -> bool {
+    let Ok(engine) = GameEngine::new(level.clone()) else {
+        return false;
+    };
+
+    [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ]
+    .into_iter()
+    .any(|direction| {
+        let mut trial = engine.clone();
+        matches!(trial.process_move(direction), Ok(true))
+            && trial.game_state().status != GameStatus::GameOver
+    })
+}
+
+fn in_bounds(level: &LevelDefinition, x: i32, y: i32) -> bool {
+    x >= 0 && y >= 0 && x < level.grid_size.width && y < level.grid_size.height
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use gsnake_core::models::{Direction, GridSize};
+    use gsnake_core::models::GridSize;
 
     fn create_test_level(
         obstacles: Vec<Position>,
@@ -157,6 +558,205 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_canonical_fingerprint_ignores_mirroring() {
+        let grid_size = GridSize {
+            width: 6,
+            height: 4,
+        };
+        let mut level = create_test_level(
+            vec![Position::new(1, 1), Position::new(2, 3)],
+            vec![Position::new(4, 0)],
+            vec![],
+            vec![],
+            vec![],
+            grid_size,
+        );
+        level.exit = Position::new(5, 2);
+        level.snake = vec![Position::new(1, 0), Position::new(0, 0)];
+
+        let mirror = |positions: &[Position]| -> Vec<Position> {
+            positions
+                .iter()
+                .map(|position| Position::new(5 - position.x, position.y))
+                .collect()
+        };
+        let mut mirrored = level.clone();
+        mirrored.obstacles = mirror(&level.obstacles);
+        mirrored.floating_food = mirror(&level.floating_food);
+        mirrored.snake = mirror(&level.snake);
+        mirrored.exit = Position::new(0, 2);
+        assert_eq!(
+            canonical_fingerprint(&mirrored),
+            canonical_fingerprint(&level)
+        );
+
+        let mut unrelated = level.clone();
+        unrelated.obstacles = vec![Position::new(3, 2)];
+        assert_ne!(
+            canonical_fingerprint(&unrelated),
+            canonical_fingerprint(&level)
+        );
+    }
+
+    #[test]
+    fn test_canonical_fingerprint_rotates_only_square_grids() {
+        let square = GridSize {
+            width: 4,
+            height: 4,
+        };
+        let level = create_test_level(
+            vec![Position::new(1, 0)],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            square,
+        );
+        // A quarter turn maps (x, y) to (3 - y, x).
+        let mut rotated = level.clone();
+        rotated.obstacles = vec![Position::new(3, 1)];
+        rotated.snake = vec![Position::new(3, 0)];
+        rotated.exit = Position::new(-2, 5);
+        assert_eq!(
+            canonical_fingerprint(&rotated),
+            canonical_fingerprint(&level)
+        );
+
+        let wide = GridSize {
+            width: 4,
+            height: 3,
+        };
+        let level = create_test_level(
+            vec![Position::new(1, 0)],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            wide,
+        );
+        let mut rotated = level.clone();
+        rotated.obstacles = vec![Position::new(3, 1)];
+        rotated.snake = vec![Position::new(3, 0)];
+        rotated.exit = Position::new(-2, 5);
+        assert_ne!(
+            canonical_fingerprint(&rotated),
+            canonical_fingerprint(&level)
+        );
+    }
+
+    #[test]
+    fn test_count_dead_ends_open_field_has_none() {
+        let level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize {
+                width: 5,
+                height: 5,
+            },
+        );
+        assert_eq!(count_dead_ends(&level), 0);
+    }
+
+    #[test]
+    fn test_count_dead_ends_comb_layout() {
+        // Teeth hang from the open top row at x = 1, 3, 5, with the last one made of stones,
+        // leaving four dead-end gaps at the bottom.
+        let obstacles = (1..5)
+            .flat_map(|y| [Position::new(1, y), Position::new(3, y)])
+            .collect();
+        let stones = (1..5).map(|y| Position::new(5, y)).collect();
+        let level = create_test_level(
+            obstacles,
+            vec![],
+            vec![],
+            stones,
+            vec![],
+            GridSize {
+                width: 7,
+                height: 5,
+            },
+        );
+
+        assert_eq!(count_dead_ends(&level), 4);
+        let complexity = analyze_level(&level).complexity;
+        assert_eq!(complexity.dead_ends, 4);
+        // The gaps above each dead end plus five cells of the top row
+        assert_eq!(complexity.corridor_ratio, 17.0 / 23.0);
+    }
+
+    #[test]
+    fn test_symmetries_detects_left_right_mirror() {
+        // 5x5 grid: snake and exit centred on the x axis, obstacles mirrored.
+        let mut level = create_test_level(
+            vec![Position::new(0, 1), Position::new(4, 1)],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(5, 5),
+        );
+        level.snake = vec![Position::new(2, 4), Position::new(2, 3)];
+        level.exit = Position::new(2, 0);
+
+        let symmetry = symmetries(&level);
+        assert!(symmetry.mirror_left_right);
+        assert!(!symmetry.mirror_top_bottom);
+        assert!(!symmetry.rotation_180);
+        assert!(symmetry.any());
+    }
+
+    #[test]
+    fn test_symmetries_detects_rotation_only() {
+        // 5x5 grid: exit in the centre, snake coiled around it, and diagonal
+        // obstacles that survive a half turn but not either mirror.
+        let mut level = create_test_level(
+            vec![Position::new(0, 0), Position::new(4, 4)],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(5, 5),
+        );
+        level.snake = vec![
+            Position::new(1, 1),
+            Position::new(2, 1),
+            Position::new(3, 1),
+            Position::new(3, 2),
+            Position::new(3, 3),
+            Position::new(2, 3),
+            Position::new(1, 3),
+            Position::new(1, 2),
+        ];
+        level.exit = Position::new(2, 2);
+
+        let symmetry = symmetries(&level);
+        assert!(symmetry.rotation_180);
+        assert!(!symmetry.mirror_left_right);
+        assert!(!symmetry.mirror_top_bottom);
+    }
+
+    #[test]
+    fn test_symmetries_distinguishes_cell_kinds() {
+        // Food and a stone swap places under every transform, which is not symmetric.
+        let mut level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            vec![Position::new(4, 4)],
+            vec![],
+            GridSize::new(5, 5),
+        );
+        level.snake = vec![Position::new(2, 2)];
+        level.exit = Position::new(2, 2);
+        level.food = vec![Position::new(0, 0)];
+
+        assert!(!symmetries(&level).any());
+    }
+
     #[test]
     fn test_detect_mechanics_all_present() {
         let level = create_test_level(
@@ -349,4 +949,164 @@ mod tests {
         assert_eq!(analysis.complexity.food_count, 1);
         assert_eq!(analysis.complexity.obstacle_density, 0.05);
     }
+
+    #[test]
+    fn test_geometry_issues_accepts_valid_level() {
+        let level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(10, 10),
+        );
+        assert!(geometry_issues(&level).is_empty());
+    }
+
+    #[test]
+    fn test_geometry_issues_reports_out_of_bounds_and_overlaps() {
+        let mut level = create_test_level(
+            vec![Position::new(10, 0)],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(10, 10),
+        );
+        level.snake = vec![
+            Position::new(1, 0),
+            Position::new(0, 0),
+            Position::new(1, 0),
+        ];
+        level.exit = Position::new(0, 0);
+
+        let issues = geometry_issues(&level);
+        assert_eq!(issues.len(), 3);
+        assert!(issues[0].contains("obstacles position (10, 0) is outside the 10x10 grid"));
+        assert!(issues[1].contains("snake segments overlap at (1, 0)"));
+        assert!(issues[2].contains("exit (0, 0) is placed on a snake segment"));
+    }
+
+    #[test]
+    fn test_geometry_issues_reports_gap_in_snake() {
+        let mut level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(10, 10),
+        );
+        level.snake = vec![
+            Position::new(3, 3),
+            Position::new(3, 4),
+            Position::new(5, 4),
+        ];
+
+        assert_eq!(
+            geometry_issues(&level),
+            vec!["snake segments (3, 4) and (5, 4) are not adjacent".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_classify_difficulty_empty_level_is_easy() {
+        let level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(10, 10),
+        );
+        let analysis = analyze_level(&level);
+
+        assert_eq!(classify_difficulty(&analysis, None), "easy");
+        assert_eq!(classify_difficulty(&analysis, Some(8)), "easy");
+    }
+
+    #[test]
+    fn test_classify_difficulty_dense_spiked_level_is_hard() {
+        let obstacles = (0..5)
+            .flat_map(|y| [Position::new(2, y), Position::new(7, y)])
+            .collect();
+        let level = create_test_level(
+            obstacles,
+            vec![],
+            vec![],
+            vec![],
+            vec![Position::new(4, 4), Position::new(5, 4)],
+            GridSize::new(10, 5),
+        );
+        let analysis = analyze_level(&level);
+
+        assert!(analysis.complexity.obstacle_density > 0.15);
+        assert_eq!(classify_difficulty(&analysis, None), "hard");
+    }
+
+    #[test]
+    fn test_reachable_cells_covers_open_grid() {
+        let mut level =
+            create_test_level(vec![], vec![], vec![], vec![], vec![], GridSize::new(4, 3));
+        level.exit = Position::new(3, 2);
+        assert_eq!(reachable_cells(&level).len(), 12);
+        assert!(unreachable_targets(&level).is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_targets_reports_sealed_exit() {
+        // Exit at (5, 5) sealed behind a full vertical wall at x = 3.
+        let wall: Vec<Position> = (0..10).map(|y| Position::new(3, y)).collect();
+        let level = create_test_level(wall, vec![], vec![], vec![], vec![], GridSize::new(10, 10));
+
+        assert_eq!(unreachable_targets(&level), vec![Position::new(5, 5)]);
+    }
+
+    #[test]
+    fn test_reachable_cells_treats_stones_as_blocking() {
+        let stones: Vec<Position> = (0..10).map(|y| Position::new(3, y)).collect();
+        let level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            stones,
+            vec![],
+            GridSize::new(10, 10),
+        );
+
+        let reachable = reachable_cells(&level);
+        assert_eq!(reachable.len(), 30);
+        assert!(!reachable.contains(&Position::new(5, 5)));
+    }
+
+    #[test]
+    fn test_snake_can_move_from_open_spawn() {
+        let level = create_test_level(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(10, 10),
+        );
+        assert!(snake_can_move(&level));
+    }
+
+    #[test]
+    fn test_snake_can_move_rejects_cornered_spawn() {
+        // Head in the top-left corner, body to the east, obstacle below: every
+        // opening move either leaves the grid, reverses, or hits the obstacle.
+        let mut level = create_test_level(
+            vec![Position::new(0, 1)],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            GridSize::new(10, 10),
+        );
+        level.snake = vec![Position::new(0, 0), Position::new(1, 0)];
+        level.snake_direction = Direction::West;
+
+        assert!(!snake_can_move(&level));
+    }
 }
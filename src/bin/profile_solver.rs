@@ -1,6 +1,8 @@
 use anyhow::{bail, Context, Result};
-use clap::Parser;
-use gsnake_levels::solver::{load_level, solve_level};
+use clap::{Parser, ValueEnum};
+use gsnake_core::LevelDefinition;
+use gsnake_levels::solver::{load_level, solve_level_with_timeout};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
     fs,
@@ -24,9 +26,33 @@ struct Args {
     #[arg(short = 'd', long = "max-depth", default_value = "500")]
     max_depth: usize,
 
+    /// Give up on a single solve after this many milliseconds
+    #[arg(long = "timeout-ms")]
+    timeout_ms: Option<u64>,
+
     /// Comma-delimited difficulty list, e.g. easy,medium
     #[arg(long, value_delimiter = ',', default_value = "easy,medium,hard")]
     difficulties: Vec<String>,
+
+    /// Output format: a human summary, or structured results for tracking over time
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Earlier `--format json` output to compare per-level average solve times against
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Fail when a level's average solve time exceeds its baseline by more than this percentage
+    #[arg(long = "max-regression", default_value = "10", requires = "baseline")]
+    max_regression: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
 }
 
 #[derive(Debug, Clone)]
@@ -35,13 +61,15 @@ struct LevelTarget {
     path: PathBuf,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 struct LevelStats {
     total: Duration,
     min: Option<Duration>,
     max: Option<Duration>,
     solves: usize,
     total_moves: usize,
+    /// Every recorded duration, in recording order, so percentiles are exact
+    samples: Vec<Duration>,
 }
 
 impl LevelStats {
@@ -51,16 +79,28 @@ impl LevelStats {
         self.total_moves += moves;
         self.min = Some(self.min.map_or(elapsed, |current| current.min(elapsed)));
         self.max = Some(self.max.map_or(elapsed, |current| current.max(elapsed)));
+        self.samples.push(elapsed);
     }
 
-    fn avg_ms(self) -> f64 {
+    fn avg_ms(&self) -> f64 {
         if self.solves == 0 {
             return 0.0;
         }
         duration_ms(self.total) / self.solves as f64
     }
 
-    fn avg_moves(self) -> f64 {
+    /// Nearest-rank percentile of the recorded durations, e.g. `percentile_ms(95.0)`
+    fn percentile_ms(&self, percentile: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+        duration_ms(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    fn avg_moves(&self) -> f64 {
         if self.solves == 0 {
             return 0.0;
         }
@@ -68,6 +108,54 @@ impl LevelStats {
     }
 }
 
+/// Timings gathered over every iteration of every benchmarked level
+#[derive(Clone)]
+struct BenchmarkRun {
+    targets: Vec<LevelTarget>,
+    level_stats: BTreeMap<PathBuf, LevelStats>,
+    difficulty_totals: BTreeMap<String, Duration>,
+    /// Time spent in the solve loop; levels are loaded beforehand and not included
+    wall_time: Duration,
+    total_solves: usize,
+}
+
+/// Outcome of comparing a run against a baseline report
+#[derive(Debug, Default)]
+struct BaselineCheck {
+    /// One line per level slower than the threshold allows
+    regressions: Vec<String>,
+    /// Levels that the baseline has no timings for
+    new_levels: Vec<String>,
+    /// Levels whose baseline average is 0 ms, so no percentage can be computed
+    zero_baseline: Vec<String>,
+}
+
+/// Per-level results in `--format json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LevelReport {
+    /// Relative to `--levels-root`, so reports from different checkouts can be compared
+    path: String,
+    difficulty: String,
+    avg_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+    avg_moves: f64,
+    solves: usize,
+}
+
+/// Whole-run results in `--format json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BenchmarkReport {
+    iterations: usize,
+    max_depth: usize,
+    levels_benchmarked: usize,
+    total_solves: usize,
+    wall_time_s: f64,
+    mean_solve_ms: f64,
+    difficulty_totals_s: BTreeMap<String, f64>,
+    levels: Vec<LevelReport>,
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
     if args.iterations == 0 {
@@ -87,22 +175,75 @@ fn main() -> Result<()> {
         );
     }
 
-    let total_solves = targets.len() * args.iterations;
+    let run = run_benchmark(&args, targets, load_level)?;
+    let report = build_report(&args, &run);
+    match args.format {
+        OutputFormat::Text => print_text_summary(&args, &normalized_difficulties, &run),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .context("Failed to serialize benchmark report")?
+        ),
+        OutputFormat::Csv => print!("{}", format_csv(&report)),
+    }
+
+    if let Some(baseline_path) = &args.baseline {
+        let baseline = load_baseline(baseline_path)?;
+        let check = compare_to_baseline(&report, &baseline, args.max_regression)
+            .with_context(|| format!("Failed to compare against {}", baseline_path.display()))?;
+        for path in &check.new_levels {
+            eprintln!("new (not in baseline): {path}");
+        }
+        for path in &check.zero_baseline {
+            eprintln!("skipped (0 ms baseline): {path}");
+        }
+        for regression in &check.regressions {
+            eprintln!("regressed: {regression}");
+        }
+        if !check.regressions.is_empty() {
+            bail!(
+                "{} level(s) regressed by more than {}% against {}",
+                check.regressions.len(),
+                args.max_regression,
+                baseline_path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Solves every target `args.iterations` times, timing only the solver.
+///
+/// Each level is parsed once up front through `load` and cloned per solve, so file
+/// I/O and JSON parsing stay out of both per-level and wall times.
+fn run_benchmark(
+    args: &Args,
+    targets: Vec<LevelTarget>,
+    mut load: impl FnMut(&Path) -> Result<LevelDefinition>,
+) -> Result<BenchmarkRun> {
+    let levels = targets
+        .iter()
+        .map(|target| load(&target.path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let timeout = args.timeout_ms.map(Duration::from_millis);
     let mut level_stats: BTreeMap<PathBuf, LevelStats> = BTreeMap::new();
     let mut difficulty_totals: BTreeMap<String, Duration> = BTreeMap::new();
     let total_start = Instant::now();
 
     for _ in 0..args.iterations {
-        for target in &targets {
+        for (target, level) in targets.iter().zip(&levels) {
+            let level = level.clone();
             let level_start = Instant::now();
-            let level = load_level(&target.path)?;
-            let solution = solve_level(level, args.max_depth).with_context(|| {
-                format!(
-                    "Failed to solve {} (difficulty {})",
-                    target.path.display(),
-                    target.difficulty
-                )
-            })?;
+            let solution =
+                solve_level_with_timeout(level, args.max_depth, timeout).with_context(|| {
+                    format!(
+                        "Failed to solve {} (difficulty {})",
+                        target.path.display(),
+                        target.difficulty
+                    )
+                })?;
             let elapsed = level_start.elapsed();
             level_stats
                 .entry(target.path.clone())
@@ -114,30 +255,93 @@ fn main() -> Result<()> {
         }
     }
 
-    let wall_time = total_start.elapsed();
+    Ok(BenchmarkRun {
+        total_solves: targets.len() * args.iterations,
+        targets,
+        level_stats,
+        difficulty_totals,
+        wall_time: total_start.elapsed(),
+    })
+}
+
+fn load_baseline(path: &Path) -> Result<BenchmarkReport> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse baseline {}", path.display()))
+}
+
+/// Flags levels whose average solve time grew by more than `max_regression_pct` percent.
+///
+/// Fails when no level of `current` appears in `baseline`, since nothing would be compared.
+fn compare_to_baseline(
+    current: &BenchmarkReport,
+    baseline: &BenchmarkReport,
+    max_regression_pct: f64,
+) -> Result<BaselineCheck> {
+    let baseline_ms: BTreeMap<&str, f64> = baseline
+        .levels
+        .iter()
+        .map(|level| (level.path.as_str(), level.avg_ms))
+        .collect();
+
+    let mut check = BaselineCheck::default();
+    for level in &current.levels {
+        let Some(&before) = baseline_ms.get(level.path.as_str()) else {
+            check.new_levels.push(level.path.clone());
+            continue;
+        };
+        if before <= 0.0 {
+            check.zero_baseline.push(level.path.clone());
+            continue;
+        }
+        if level.avg_ms > before * (1.0 + max_regression_pct / 100.0) {
+            check.regressions.push(format!(
+                "{}: avg {:.3} ms vs {:.3} ms baseline ({:+.1}%)",
+                level.path,
+                level.avg_ms,
+                before,
+                (level.avg_ms / before - 1.0) * 100.0
+            ));
+        }
+    }
+    if !current.levels.is_empty() && check.new_levels.len() == current.levels.len() {
+        bail!(
+            "None of the {} benchmarked level(s) appear in the baseline",
+            current.levels.len()
+        );
+    }
+    Ok(check)
+}
+
+fn print_text_summary(args: &Args, normalized_difficulties: &[String], run: &BenchmarkRun) {
     println!("Solver benchmark");
     println!("levels root: {}", args.levels_root.display());
     println!("difficulties: {}", normalized_difficulties.join(","));
     println!("iterations per level: {}", args.iterations);
     println!("max depth: {}", args.max_depth);
-    println!("levels benchmarked: {}", targets.len());
-    println!("total solves: {}", total_solves);
-    println!("wall time: {:.3} s", duration_s(wall_time));
+    if let Some(timeout_ms) = args.timeout_ms {
+        println!("per-solve timeout: {} ms", timeout_ms);
+    }
+    println!("levels benchmarked: {}", run.targets.len());
+    println!("total solves: {}", run.total_solves);
+    println!("wall time: {:.3} s", duration_s(run.wall_time));
     println!(
         "mean solve time: {:.3} ms",
-        duration_ms(wall_time) / total_solves as f64
+        duration_ms(run.wall_time) / run.total_solves as f64
     );
 
     println!("\nPer-difficulty cumulative time:");
-    for difficulty in &normalized_difficulties {
-        let total = difficulty_totals
+    for difficulty in normalized_difficulties {
+        let total = run
+            .difficulty_totals
             .get(difficulty)
             .copied()
             .unwrap_or(Duration::ZERO);
         println!("  - {}: {:.3} s", difficulty, duration_s(total));
     }
 
-    let mut hotspots: Vec<(&PathBuf, &LevelStats)> = level_stats.iter().collect();
+    let mut hotspots: Vec<(&PathBuf, &LevelStats)> = run.level_stats.iter().collect();
     hotspots.sort_by(|a, b| {
         b.1.total
             .cmp(&a.1.total)
@@ -147,18 +351,82 @@ fn main() -> Result<()> {
     println!("\nHotspot summary (top 3 by cumulative time):");
     for (index, (path, stats)) in hotspots.into_iter().take(3).enumerate() {
         println!(
-            "  {}. {} | total {:.3} s | avg {:.3} ms | min {:.3} ms | max {:.3} ms | avg moves {:.1}",
+            "  {}. {} | total {:.3} s | avg {:.3} ms | p50 {:.3} ms | p95 {:.3} ms | min {:.3} ms | max {:.3} ms | avg moves {:.1}",
             index + 1,
             path.display(),
             duration_s(stats.total),
             stats.avg_ms(),
+            stats.percentile_ms(50.0),
+            stats.percentile_ms(95.0),
             duration_ms(stats.min.unwrap_or_default()),
             duration_ms(stats.max.unwrap_or_default()),
             stats.avg_moves()
         );
     }
+}
 
-    Ok(())
+fn build_report(args: &Args, run: &BenchmarkRun) -> BenchmarkReport {
+    let levels = run
+        .targets
+        .iter()
+        .filter_map(|target| {
+            let stats = run.level_stats.get(&target.path)?;
+            let path = target
+                .path
+                .strip_prefix(&args.levels_root)
+                .unwrap_or(&target.path);
+            Some(LevelReport {
+                path: path.display().to_string(),
+                difficulty: target.difficulty.clone(),
+                avg_ms: stats.avg_ms(),
+                min_ms: duration_ms(stats.min.unwrap_or_default()),
+                max_ms: duration_ms(stats.max.unwrap_or_default()),
+                avg_moves: stats.avg_moves(),
+                solves: stats.solves,
+            })
+        })
+        .collect();
+
+    BenchmarkReport {
+        iterations: args.iterations,
+        max_depth: args.max_depth,
+        levels_benchmarked: run.targets.len(),
+        total_solves: run.total_solves,
+        wall_time_s: duration_s(run.wall_time),
+        mean_solve_ms: duration_ms(run.wall_time) / run.total_solves.max(1) as f64,
+        difficulty_totals_s: run
+            .difficulty_totals
+            .iter()
+            .map(|(difficulty, total)| (difficulty.clone(), duration_s(*total)))
+            .collect(),
+        levels,
+    }
+}
+
+/// One header line, then one row per level
+fn format_csv(report: &BenchmarkReport) -> String {
+    let mut output = String::from("path,difficulty,avg_ms,min_ms,max_ms,avg_moves,solves\n");
+    for level in &report.levels {
+        output.push_str(&format!(
+            "{},{},{:.3},{:.3},{:.3},{:.1},{}\n",
+            csv_field(&level.path),
+            csv_field(&level.difficulty),
+            level.avg_ms,
+            level.min_ms,
+            level.max_ms,
+            level.avg_moves,
+            level.solves
+        ));
+    }
+    output
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 fn discover_levels(levels_root: &Path, difficulties: &[String]) -> Result<Vec<LevelTarget>> {
@@ -166,11 +434,13 @@ fn discover_levels(levels_root: &Path, difficulties: &[String]) -> Result<Vec<Le
 
     for difficulty in difficulties {
         let difficulty_dir = levels_root.join(difficulty);
+        // Like generate_all_playbacks, skip absent difficulties; main bails if nothing is left.
         if !difficulty_dir.exists() {
-            bail!(
-                "Difficulty directory not found: {}",
+            eprintln!(
+                "skipping missing difficulty directory: {}",
                 difficulty_dir.display()
             );
+            continue;
         }
 
         let mut files = Vec::new();
@@ -238,6 +508,205 @@ mod tests {
         assert_eq!(normalized, vec!["easy".to_string(), "medium".to_string()]);
     }
 
+    fn sample_run() -> (Args, BenchmarkRun) {
+        let args = Args::parse_from(["profile_solver", "--iterations", "2"]);
+        let mut level_stats = BTreeMap::new();
+        let mut targets = Vec::new();
+        for (difficulty, name, millis) in [("easy", "a.json", 4), ("hard", "b,c.json", 10)] {
+            let path = PathBuf::from(format!("levels/{difficulty}/{name}"));
+            let mut stats = LevelStats::default();
+            stats.record(Duration::from_millis(millis), 3);
+            stats.record(Duration::from_millis(millis * 2), 3);
+            level_stats.insert(path.clone(), stats);
+            targets.push(LevelTarget {
+                difficulty: difficulty.to_string(),
+                path,
+            });
+        }
+        let run = BenchmarkRun {
+            targets,
+            level_stats,
+            difficulty_totals: BTreeMap::from([("easy".to_string(), Duration::from_millis(12))]),
+            wall_time: Duration::from_millis(40),
+            total_solves: 4,
+        };
+        (args, run)
+    }
+
+    #[test]
+    fn level_stats_percentiles_use_nearest_rank() {
+        let mut stats = LevelStats::default();
+        assert_eq!(stats.percentile_ms(50.0), 0.0);
+        // Recorded out of order; one outlier run
+        for millis in [5, 1, 4, 2, 3, 6, 7, 8, 9, 100] {
+            stats.record(Duration::from_millis(millis), 1);
+        }
+        assert_eq!(stats.percentile_ms(50.0), 5.0);
+        assert_eq!(stats.percentile_ms(95.0), 100.0);
+        assert_eq!(stats.percentile_ms(90.0), 9.0);
+
+        let mut single = LevelStats::default();
+        single.record(Duration::from_millis(7), 1);
+        assert_eq!(single.percentile_ms(50.0), 7.0);
+        assert_eq!(single.percentile_ms(95.0), 7.0);
+    }
+
+    #[test]
+    fn json_report_parses_with_an_entry_per_level() -> Result<()> {
+        let (args, run) = sample_run();
+        let json = serde_json::to_string_pretty(&build_report(&args, &run))?;
+
+        let parsed: serde_json::Value = serde_json::from_str(&json)?;
+        let levels = parsed["levels"].as_array().unwrap();
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0]["path"], "easy/a.json");
+        assert_eq!(levels[0]["difficulty"], "easy");
+        assert_eq!(levels[0]["avg_ms"], 6.0);
+        assert_eq!(levels[0]["min_ms"], 4.0);
+        assert_eq!(levels[0]["max_ms"], 8.0);
+        assert_eq!(levels[0]["solves"], 2);
+        assert_eq!(parsed["total_solves"], 4);
+        assert_eq!(parsed["mean_solve_ms"], 10.0);
+        Ok(())
+    }
+
+    #[test]
+    fn baseline_comparison_flags_regressions_and_new_levels() {
+        let (args, run) = sample_run();
+        let current = build_report(&args, &run);
+        let mut baseline = current.clone();
+        // b,c.json averaged 15 ms; pretend it used to take 10 ms.
+        baseline.levels[1].avg_ms = 10.0;
+
+        let check = compare_to_baseline(&current, &baseline, 10.0).unwrap();
+        assert_eq!(
+            check.regressions,
+            vec!["hard/b,c.json: avg 15.000 ms vs 10.000 ms baseline (+50.0%)"]
+        );
+        assert!(check.new_levels.is_empty());
+        assert!(compare_to_baseline(&current, &baseline, 60.0)
+            .unwrap()
+            .regressions
+            .is_empty());
+
+        baseline.levels.remove(0);
+        let check = compare_to_baseline(&current, &baseline, 60.0).unwrap();
+        assert_eq!(check.new_levels, vec!["easy/a.json"]);
+        assert!(check.regressions.is_empty());
+    }
+
+    #[test]
+    fn baseline_comparison_matches_reports_from_another_levels_root() {
+        let (args, run) = sample_run();
+        let current = build_report(&args, &run);
+        let other_args =
+            Args::parse_from(["profile_solver", "--levels-root", "/ci/checkout/levels"]);
+        let mut other_run = run.clone();
+        for target in &mut other_run.targets {
+            target.path = Path::new("/ci/checkout").join(&target.path);
+        }
+        other_run.level_stats = run
+            .level_stats
+            .iter()
+            .map(|(path, stats)| (Path::new("/ci/checkout").join(path), stats.clone()))
+            .collect();
+        let baseline = build_report(&other_args, &other_run);
+
+        let check = compare_to_baseline(&current, &baseline, 10.0).unwrap();
+        assert!(check.new_levels.is_empty());
+        assert!(check.regressions.is_empty());
+    }
+
+    #[test]
+    fn baseline_comparison_fails_without_matches_and_skips_zero_baselines() {
+        let (args, run) = sample_run();
+        let current = build_report(&args, &run);
+
+        let mut baseline = current.clone();
+        baseline.levels[0].avg_ms = 0.0;
+        let check = compare_to_baseline(&current, &baseline, 10.0).unwrap();
+        assert_eq!(check.zero_baseline, vec!["easy/a.json"]);
+        assert!(check.regressions.is_empty());
+
+        for level in &mut baseline.levels {
+            level.path = format!("elsewhere/{}", level.path);
+        }
+        let error = compare_to_baseline(&current, &baseline, 10.0).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("None of the 2 benchmarked level(s) appear in the baseline"));
+    }
+
+    #[test]
+    fn csv_report_has_header_and_quotes_fields() {
+        let (args, run) = sample_run();
+        let csv = format_csv(&build_report(&args, &run));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "path,difficulty,avg_ms,min_ms,max_ms,avg_moves,solves",
+                "easy/a.json,easy,6.000,4.000,8.000,3.0,2",
+                "\"hard/b,c.json\",hard,15.000,10.000,20.000,3.0,2",
+            ]
+        );
+    }
+
+    #[test]
+    fn discover_levels_skips_missing_difficulty_directories() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path();
+        fs::create_dir_all(levels_root.join("easy"))?;
+        fs::write(levels_root.join("easy").join("a.json"), "{}")?;
+
+        let difficulties = vec!["easy".to_string(), "expert".to_string()];
+        let discovered = discover_levels(levels_root, &difficulties)?;
+
+        assert_eq!(discovered.len(), 1);
+        assert_eq!(discovered[0].difficulty, "easy");
+        assert_eq!(discovered[0].path, levels_root.join("easy").join("a.json"));
+        Ok(())
+    }
+
+    #[test]
+    fn run_benchmark_loads_each_level_once() -> Result<()> {
+        let args = Args::parse_from(["profile_solver", "--iterations", "4"]);
+        let targets: Vec<LevelTarget> = ["a.json", "b.json"]
+            .into_iter()
+            .map(|name| LevelTarget {
+                difficulty: "easy".to_string(),
+                path: PathBuf::from(name),
+            })
+            .collect();
+        let level: LevelDefinition = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "Corridor",
+            "difficulty": "easy",
+            "gridSize": { "width": 4, "height": 1 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 3, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        }))?;
+
+        let mut loads: BTreeMap<PathBuf, usize> = BTreeMap::new();
+        let run = run_benchmark(&args, targets, |path| {
+            *loads.entry(path.to_path_buf()).or_default() += 1;
+            Ok(level.clone())
+        })?;
+
+        assert_eq!(loads.values().copied().collect::<Vec<_>>(), vec![1, 1]);
+        assert_eq!(run.total_solves, 8);
+        assert_eq!(run.level_stats[Path::new("a.json")].solves, 4);
+        Ok(())
+    }
+
     #[test]
     fn discover_levels_returns_sorted_json_files() -> Result<()> {
         let temp_dir = TempDir::new()?;
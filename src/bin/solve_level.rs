@@ -1,7 +1,13 @@
 use anyhow::{Context, Result};
 use clap::Parser;
-use gsnake_levels::solver::solve_level_to_playback;
-use std::path::PathBuf;
+use gsnake_levels::{
+    playback::{
+        compact_directions, direction_key, write_replay_script, DelayStrategy,
+        DEFAULT_STEP_DELAY_MS,
+    },
+    solver::{load_level, solve_level_to_playback_with_options, SolveStrategy},
+};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "solve_level")]
@@ -11,22 +17,119 @@ struct Args {
     level_path: PathBuf,
 
     /// Path to save the playback solution JSON
-    output_path: PathBuf,
+    #[arg(required_unless_present = "print")]
+    output_path: Option<PathBuf>,
+
+    /// Print the solution to stdout instead of writing a playback file
+    #[arg(long, conflicts_with_all = ["output_path", "with_meta", "emit_script"])]
+    print: bool,
+
+    /// Also write an executable shell script that replays the level with the new playback
+    #[arg(long = "emit-script", value_name = "OUT_SH")]
+    emit_script: Option<PathBuf>,
 
     /// Maximum search depth for solver (default: 500)
     #[arg(short = 'd', long = "max-depth", default_value = "500")]
     max_depth: usize,
+
+    /// Use best-effort beam search keeping this many states per depth (not guaranteed optimal)
+    #[arg(long = "beam-width")]
+    beam_width: Option<usize>,
+
+    /// Use a right-hand wall follower (levels without food only; not optimal)
+    #[arg(long = "wall-follower", conflicts_with = "beam_width")]
+    wall_follower: bool,
+
+    /// Use A* search guided by head-to-food-to-exit distances (optimal unless gravity moves the head)
+    #[arg(long, conflicts_with_all = ["beam_width", "wall_follower"])]
+    astar: bool,
+
+    /// Wrap the steps in a header with level id, generator, optimality and move count
+    #[arg(long = "with-meta")]
+    with_meta: bool,
+
+    /// Delay before each move in the written playback
+    #[arg(long = "delay-ms", default_value_t = DEFAULT_STEP_DELAY_MS)]
+    delay_ms: u64,
+
+    /// Start at --delay-ms and speed up by STEP_MS per move, down to MIN_MS
+    #[arg(long, num_args = 2, value_names = ["MIN_MS", "STEP_MS"])]
+    accelerate: Option<Vec<u64>>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let move_count = solve_level_to_playback(&args.level_path, &args.output_path, args.max_depth)
-        .with_context(|| "Failed to generate playback")?;
+    let strategy = match (args.beam_width, args.wall_follower, args.astar) {
+        (Some(beam_width), _, _) => SolveStrategy::Beam(beam_width),
+        (None, true, _) => SolveStrategy::WallFollower,
+        (None, false, true) => SolveStrategy::AStar,
+        (None, false, false) => SolveStrategy::Bfs,
+    };
+
+    let delays = match args.accelerate.as_deref() {
+        Some(&[min, step]) => DelayStrategy::Accelerate {
+            start: args.delay_ms,
+            min,
+            step,
+        },
+        _ => DelayStrategy::Constant(args.delay_ms),
+    };
+
+    let Some(output_path) = args.output_path else {
+        return print_solution(&args.level_path, args.max_depth, strategy);
+    };
+    let move_count = solve_level_to_playback_with_options(
+        &args.level_path,
+        &output_path,
+        args.max_depth,
+        strategy,
+        &delays,
+        args.with_meta,
+    )
+    .with_context(|| "Failed to generate playback")?;
 
     println!(
         "Solved {} in {} moves",
         args.level_path.display(),
         move_count
     );
+
+    if let Some(script_path) = args.emit_script {
+        emit_replay_script(&script_path, &args.level_path, &output_path)?;
+        println!("Wrote replay script {}", script_path.display());
+    }
+    Ok(())
+}
+
+fn emit_replay_script(script_path: &Path, level_path: &Path, playback_path: &Path) -> Result<()> {
+    let manifest_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let level_path = level_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", level_path.display()))?;
+    let playback_path = playback_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", playback_path.display()))?;
+    write_replay_script(script_path, &manifest_path, &level_path, &playback_path)
+}
+
+fn print_solution(level_path: &Path, max_depth: usize, strategy: SolveStrategy) -> Result<()> {
+    let level = load_level(level_path)?;
+    let solution = strategy
+        .solve(level, max_depth)
+        .with_context(|| format!("No solution found within depth {max_depth}"))?;
+    let words: Vec<&str> = solution.iter().copied().map(direction_key).collect();
+
+    println!(
+        "Solved {} in {} moves ({})",
+        level_path.display(),
+        solution.len(),
+        if strategy.is_optimal() {
+            "optimal"
+        } else {
+            "not guaranteed optimal"
+        }
+    );
+    println!("Moves:   {}", words.join(" "));
+    println!("Compact: {}", compact_directions(&solution));
     Ok(())
 }
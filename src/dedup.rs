@@ -0,0 +1,484 @@
+use crate::{
+    analysis::canonical_fingerprint,
+    levels,
+    playback::{direction_key, load_playback_directions, load_playback_steps, PlaybackStep},
+    solver::load_level,
+};
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// Playbacks whose decoded move sequences are identical
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackGroup {
+    pub hash: u64,
+    pub moves: usize,
+    /// Sorted paths; the first regular file is the one duplicates link to
+    pub paths: Vec<PathBuf>,
+}
+
+/// What `link_duplicates` replaced and which duplicates it had to leave in place
+#[derive(Debug, Default)]
+pub struct LinkReport {
+    pub linked: usize,
+    /// Duplicates left in place because their step delays differ from every earlier file
+    pub unlinked: Vec<PathBuf>,
+    pub failures: Vec<(PathBuf, anyhow::Error)>,
+}
+
+/// Levels whose layouts are rotations or mirror images of each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelCluster {
+    pub fingerprint: String,
+    /// Sorted level paths
+    pub paths: Vec<PathBuf>,
+}
+
+pub fn run_duplicate_levels(levels_root: &Path) -> Result<()> {
+    let clusters = find_duplicate_levels(levels_root)?;
+
+    if clusters.is_empty() {
+        println!("No duplicate levels found");
+        return Ok(());
+    }
+
+    for cluster in &clusters {
+        println!(
+            "Cluster {} ({} levels):",
+            cluster.fingerprint,
+            cluster.paths.len()
+        );
+        for path in &cluster.paths {
+            println!("  {}", path.display());
+        }
+    }
+    let duplicates: usize = clusters.iter().map(|cluster| cluster.paths.len() - 1).sum();
+    println!(
+        "{duplicates} duplicate level(s) across {} cluster(s)",
+        clusters.len()
+    );
+    Ok(())
+}
+
+/// Groups the level JSON files of every difficulty by [`canonical_fingerprint`]
+pub fn find_duplicate_levels(levels_root: &Path) -> Result<Vec<LevelCluster>> {
+    let mut by_fingerprint: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+
+    for difficulty in levels::catalog_difficulties(levels_root) {
+        let dir = levels_root.join(difficulty);
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let level = load_level(&path)
+                .with_context(|| format!("Failed to load level: {}", path.display()))?;
+            by_fingerprint
+                .entry(canonical_fingerprint(&level))
+                .or_default()
+                .push(path);
+        }
+    }
+
+    Ok(by_fingerprint
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(fingerprint, paths)| LevelCluster { fingerprint, paths })
+        .collect())
+}
+
+pub fn run_dedup_playbacks(levels_root: &Path, link: bool) -> Result<()> {
+    let playbacks_root = levels::playbacks_root(levels_root);
+    let groups =
+        find_duplicate_playbacks(&playbacks_root, &levels::catalog_difficulties(levels_root))?;
+
+    if groups.is_empty() {
+        println!("No duplicate playbacks found");
+        return Ok(());
+    }
+
+    for group in &groups {
+        println!(
+            "Group {:016x} ({} playbacks, {} moves):",
+            group.hash,
+            group.paths.len(),
+            group.moves
+        );
+        for path in &group.paths {
+            println!("  {}", path.display());
+        }
+    }
+    let duplicates: usize = groups.iter().map(|group| group.paths.len() - 1).sum();
+    println!(
+        "{duplicates} duplicate playback(s) across {} group(s)",
+        groups.len()
+    );
+
+    if link {
+        let report = link_duplicates(&groups);
+        println!("Replaced {} duplicate(s) with symlinks", report.linked);
+        if !report.unlinked.is_empty() {
+            println!(
+                "Left {} duplicate(s) unlinked because their step delays differ:",
+                report.unlinked.len()
+            );
+            for path in &report.unlinked {
+                println!("  {}", path.display());
+            }
+        }
+        for (path, error) in &report.failures {
+            eprintln!("  {}: {error:#}", path.display());
+        }
+        if !report.failures.is_empty() {
+            bail!("Failed to link {} duplicate(s)", report.failures.len());
+        }
+    }
+    Ok(())
+}
+
+/// Groups playbacks under `playbacks_root/<difficulty>/` for each of `difficulties` by their
+/// decoded directions.
+///
+/// Comparing decoded directions rather than file bytes means playbacks that only
+/// differ in key spelling (`R` vs `Right`) or delays still count as duplicates; only
+/// `link_duplicates` also requires equal delays.
+pub fn find_duplicate_playbacks(
+    playbacks_root: &Path,
+    difficulties: &[String],
+) -> Result<Vec<PlaybackGroup>> {
+    let mut by_sequence: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+
+    for difficulty in difficulties {
+        let dir = playbacks_root.join(difficulty);
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let sequence: String = load_playback_directions(&path)?
+                .into_iter()
+                .map(direction_key)
+                .collect::<Vec<_>>()
+                .join(",");
+            by_sequence.entry(sequence).or_default().push(path);
+        }
+    }
+
+    Ok(by_sequence
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(sequence, paths)| {
+            let mut hasher = DefaultHasher::new();
+            sequence.hash(&mut hasher);
+            PlaybackGroup {
+                hash: hasher.finish(),
+                moves: sequence.split(',').count(),
+                paths,
+            }
+        })
+        .collect())
+}
+
+/// Replaces every duplicate in each group with a relative symlink to the group's first
+/// regular file with the same steps, delays included.
+///
+/// A duplicate whose delays differ from every earlier regular file would play back
+/// differently through a link, so it is left in place and listed in `unlinked`.
+///
+/// Symlinks are never followed or written through: existing links are skipped, and a
+/// duplicate that is not a regular file, or whose difficulty directory is itself a link, is
+/// reported as a failure and left untouched. One failure doesn't stop the remaining
+/// duplicates from being linked.
+pub fn link_duplicates(groups: &[PlaybackGroup]) -> LinkReport {
+    let mut report = LinkReport::default();
+    for group in groups {
+        let mut canonicals: Vec<(PathBuf, Vec<PlaybackStep>)> = Vec::new();
+        for path in &group.paths {
+            if is_symlink(path) {
+                continue;
+            }
+            let steps = match load_playback_steps(path) {
+                Ok(steps) => steps,
+                Err(error) => {
+                    report.failures.push((path.clone(), error));
+                    continue;
+                },
+            };
+            let Some((canonical, _)) = canonicals.iter().find(|(_, known)| *known == steps) else {
+                match ensure_plain_file(path) {
+                    Ok(()) if canonicals.is_empty() => canonicals.push((path.clone(), steps)),
+                    Ok(()) => {
+                        report.unlinked.push(path.clone());
+                        canonicals.push((path.clone(), steps));
+                    },
+                    Err(error) => report.failures.push((path.clone(), error)),
+                }
+                continue;
+            };
+            let result =
+                link_target(canonical).and_then(|target| replace_with_symlink(&target, path));
+            match result {
+                Ok(()) => report.linked += 1,
+                Err(error) => report.failures.push((path.clone(), error)),
+            }
+        }
+    }
+    report
+}
+
+/// Playbacks live in playbacks/<difficulty>/, so "../<difficulty>/<file>" resolves from
+/// any sibling difficulty directory
+fn link_target(canonical: &Path) -> Result<PathBuf> {
+    let (Some(difficulty), Some(file)) = (
+        canonical.parent().and_then(Path::file_name),
+        canonical.file_name(),
+    ) else {
+        bail!("Invalid playback path: {}", canonical.display());
+    };
+    Ok(Path::new("..").join(difficulty).join(file))
+}
+
+/// Swaps `duplicate` for a link to `target` by renaming a freshly created link over it, so
+/// the playback is never missing if linking fails
+fn replace_with_symlink(target: &Path, duplicate: &Path) -> Result<()> {
+    ensure_plain_file(duplicate)?;
+    let mut staged = duplicate.as_os_str().to_owned();
+    staged.push(".link");
+    let staged = PathBuf::from(staged);
+    symlink(target, &staged)?;
+    if let Err(error) = fs::rename(&staged, duplicate) {
+        let _ = fs::remove_file(&staged);
+        return Err(error).with_context(|| format!("Failed to replace {}", duplicate.display()));
+    }
+    Ok(())
+}
+
+/// Fails unless `path` is a regular file reached without passing through a symlinked
+/// directory, checked with `symlink_metadata` so links are never followed
+fn ensure_plain_file(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)
+        .with_context(|| format!("Failed to read metadata of {}", path.display()))?;
+    if !metadata.file_type().is_file() {
+        bail!("Not a regular file: {}", path.display());
+    }
+    if let Some(parent) = path.parent().filter(|parent| is_symlink(parent)) {
+        bail!(
+            "Refusing to write through symlinked directory {}",
+            parent.display()
+        );
+    }
+    Ok(())
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link)
+        .with_context(|| format!("Failed to link {} -> {}", link.display(), target.display()))
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, link: &Path) -> Result<()> {
+    bail!(
+        "--link requires symlink support, which is only available on Unix: {}",
+        link.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_playback(path: &Path, keys: &[&str]) {
+        write_playback_with_delay(path, keys, 200);
+    }
+
+    fn write_playback_with_delay(path: &Path, keys: &[&str], delay_ms: u64) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let steps: Vec<_> = keys
+            .iter()
+            .map(|key| serde_json::json!({ "key": key, "delay_ms": delay_ms }))
+            .collect();
+        fs::write(path, serde_json::to_string_pretty(&steps).unwrap()).unwrap();
+    }
+
+    fn create_playbacks(root: &Path) {
+        write_playback(&root.join("easy/a.json"), &["Right", "Right", "Up"]);
+        write_playback(&root.join("easy/b.json"), &["Left"]);
+        write_playback(&root.join("hard/c.json"), &["R", "R", "U"]);
+    }
+
+    #[test]
+    fn test_find_duplicate_playbacks_groups_by_decoded_directions() {
+        let temp_dir = TempDir::new().unwrap();
+        create_playbacks(temp_dir.path());
+
+        let groups = find_duplicate_playbacks(
+            temp_dir.path(),
+            &levels::catalog_difficulties(temp_dir.path()),
+        )
+        .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].moves, 3);
+        assert_eq!(
+            groups[0].paths,
+            vec![
+                temp_dir.path().join("easy/a.json"),
+                temp_dir.path().join("hard/c.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_duplicate_levels_clusters_mirrored_layouts() {
+        let temp_dir = TempDir::new().unwrap();
+        let write_level = |path: &str, snake_x: i32, exit_x: i32| {
+            let path = temp_dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let level = serde_json::json!({
+                "id": 1,
+                "name": "Corridor",
+                "difficulty": "easy",
+                "gridSize": { "width": 5, "height": 1 },
+                "snake": [{ "x": snake_x, "y": 0 }],
+                "snakeDirection": "East",
+                "obstacles": [],
+                "food": [],
+                "exit": { "x": exit_x, "y": 0 },
+                "floatingFood": [],
+                "fallingFood": [],
+                "stones": [],
+                "spikes": [],
+                "totalFood": 0
+            });
+            fs::write(path, level.to_string()).unwrap();
+        };
+        write_level("easy/right.json", 0, 4);
+        write_level("easy/short.json", 0, 2);
+        write_level("hard/left.json", 4, 0);
+
+        let clusters = find_duplicate_levels(temp_dir.path()).unwrap();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(
+            clusters[0].paths,
+            vec![
+                temp_dir.path().join("easy/right.json"),
+                temp_dir.path().join("hard/left.json")
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_duplicates_replaces_with_relative_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        create_playbacks(temp_dir.path());
+
+        let groups = find_duplicate_playbacks(
+            temp_dir.path(),
+            &levels::catalog_difficulties(temp_dir.path()),
+        )
+        .unwrap();
+        let report = link_duplicates(&groups);
+        assert_eq!(report.linked, 1);
+        assert!(report.failures.is_empty());
+
+        let duplicate = temp_dir.path().join("hard/c.json");
+        assert!(is_symlink(&duplicate));
+        assert_eq!(
+            fs::read_link(&duplicate).unwrap(),
+            Path::new("../easy/a.json")
+        );
+        assert_eq!(load_playback_directions(&duplicate).unwrap().len(), 3);
+
+        // Linking again is a no-op and the groups are still reported.
+        let groups = find_duplicate_playbacks(
+            temp_dir.path(),
+            &levels::catalog_difficulties(temp_dir.path()),
+        )
+        .unwrap();
+        assert_eq!(link_duplicates(&groups).linked, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_duplicates_leaves_playbacks_with_other_delays_in_place() {
+        let temp_dir = TempDir::new().unwrap();
+        create_playbacks(temp_dir.path());
+        let slow = temp_dir.path().join("medium/slow.json");
+        let slow_copy = temp_dir.path().join("medium/slow_copy.json");
+        write_playback_with_delay(&slow, &["Right", "Right", "Up"], 500);
+        write_playback_with_delay(&slow_copy, &["R", "R", "U"], 500);
+
+        let groups = find_duplicate_playbacks(
+            temp_dir.path(),
+            &levels::catalog_difficulties(temp_dir.path()),
+        )
+        .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths.len(), 4);
+
+        let report = link_duplicates(&groups);
+        assert!(report.failures.is_empty());
+        assert_eq!(report.linked, 2);
+        assert_eq!(report.unlinked, vec![slow.clone()]);
+        assert!(!is_symlink(&slow));
+        assert_eq!(
+            fs::read_link(&slow_copy).unwrap(),
+            Path::new("../medium/slow.json")
+        );
+        assert_eq!(
+            fs::read_link(temp_dir.path().join("hard/c.json")).unwrap(),
+            Path::new("../easy/a.json")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_duplicates_refuses_symlinked_directory_and_keeps_going() {
+        let temp_dir = TempDir::new().unwrap();
+        create_playbacks(temp_dir.path());
+        let outside = temp_dir.path().join("outside/d.json");
+        write_playback(&outside, &["Right", "Right", "Up"]);
+        std::os::unix::fs::symlink("outside", temp_dir.path().join("medium")).unwrap();
+
+        let groups = find_duplicate_playbacks(
+            temp_dir.path(),
+            &levels::catalog_difficulties(temp_dir.path()),
+        )
+        .unwrap();
+        assert_eq!(groups[0].paths.len(), 3);
+
+        let report = link_duplicates(&groups);
+        assert_eq!(report.linked, 1);
+        assert!(is_symlink(&temp_dir.path().join("hard/c.json")));
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, temp_dir.path().join("medium/d.json"));
+        assert!(report.failures[0]
+            .1
+            .to_string()
+            .contains("Refusing to write through symlinked directory"));
+        assert!(!is_symlink(&outside));
+        assert_eq!(load_playback_directions(&outside).unwrap().len(), 3);
+    }
+}
@@ -1,116 +1,654 @@
+use crate::analysis::derive_total_food;
 use crate::levels;
+use crate::normalize;
 use crate::sync_metadata;
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::{write::GzEncoder, Compression};
 use gsnake_core::LevelDefinition;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
-pub fn run_generate_levels_json(filter: Option<&str>, dry_run: bool, sync: bool) -> Result<()> {
-    let levels_root = levels::find_levels_root()?;
-    let playbacks_root = levels_root
-        .parent()
-        .map(|parent| parent.join("playbacks"))
-        .unwrap_or_else(|| PathBuf::from("playbacks"));
-    let difficulties = parse_filter(filter)?;
+/// Caps how many levels are aggregated, counted in levels.toml order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregateLimit {
+    pub count: usize,
+    pub per_difficulty: bool,
+}
+
+/// Which levels to aggregate: comma-separated difficulties and levels.toml tags
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AggregateFilter<'a> {
+    pub difficulties: Option<&'a str>,
+    /// Include only levels carrying at least one of these tags
+    pub tags: Option<&'a str>,
+}
+
+/// Release gates: how aggregation treats unsolved and unloadable levels
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AggregateChecks {
+    /// Fail when an included level is not marked solved in levels.toml
+    pub require_solved: bool,
+    /// Keep aggregating past missing or invalid level files and report them all at the end
+    /// instead of stopping at the first one
+    pub collect_errors: bool,
+}
+
+/// How aggregated levels are serialized and where they are written
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AggregateOutput<'a> {
+    /// Aggregate and validate without emitting anything
+    pub dry_run: bool,
+    pub minify: bool,
+    /// Write to this file instead of stdout
+    pub path: Option<&'a Path>,
+    /// Gzip-compress the file written to `path`, appending `.gz` when its name lacks it
+    pub gzip: bool,
+    /// Also write an [`AggregateManifest`] describing the emitted bytes to this file
+    pub manifest: Option<&'a Path>,
+}
+
+/// Sidecar for a shipped levels.json: lets clients cache-bust on `sha256` and list levels
+/// without downloading the full file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateManifest {
+    /// File name the aggregated levels were written to; `None` for stdout
+    pub file: Option<String>,
+    pub level_count: usize,
+    /// Hex SHA-256 of the exact bytes written, after minify and gzip
+    pub sha256: String,
+    pub generated_levels: Vec<ManifestLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestLevel {
+    pub id: u32,
+    pub difficulty: Option<String>,
+    pub name: String,
+}
+
+impl AggregateManifest {
+    fn new(aggregated: &[LevelDefinition], file: Option<&Path>, bytes: &[u8]) -> Self {
+        Self {
+            file: file
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().into_owned()),
+            level_count: aggregated.len(),
+            sha256: format!("{:x}", Sha256::digest(bytes)),
+            generated_levels: aggregated
+                .iter()
+                .map(|level| ManifestLevel {
+                    id: level.id,
+                    difficulty: level.difficulty.clone(),
+                    name: level.name.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Prints the aggregated levels of `levels_roots`, or writes them to `output.path`; with more
+/// than one root, later roots override or collide with earlier ones depending on
+/// `allow_override`
+///
+/// With `checks.collect_errors`, every level that fails to load is printed to stderr and
+/// nothing is written.
+pub fn run_generate_levels_json(
+    levels_roots: &[PathBuf],
+    filter: AggregateFilter,
+    sync: bool,
+    limit: Option<AggregateLimit>,
+    allow_override: bool,
+    checks: AggregateChecks,
+    output: AggregateOutput,
+) -> Result<()> {
+    if output.gzip && output.path.is_none() {
+        bail!("--gzip requires --output; refusing to write compressed data to stdout");
+    }
+
+    let mut known = Vec::new();
+    for levels_root in levels_roots {
+        for difficulty in levels::catalog_difficulties(levels_root) {
+            if !known.contains(&difficulty) {
+                known.push(difficulty);
+            }
+        }
+    }
+    let difficulties = parse_filter(filter.difficulties, &known)?;
+    let difficulties: Vec<&str> = difficulties.iter().map(String::as_str).collect();
+    let tags = parse_tags(filter.tags)?;
 
     // Run metadata sync if enabled (default behavior)
     if sync {
-        eprintln!("Running metadata sync...");
-        let difficulty_filter = if difficulties.len() == levels::DEFAULT_DIFFICULTIES.len() {
-            None
-        } else {
-            Some(difficulties.join(","))
-        };
-        let summary = sync_metadata::sync_metadata_with_roots(
-            &levels_root,
-            &playbacks_root,
-            difficulty_filter.as_deref(),
+        for levels_root in levels_roots {
+            sync_levels_root(levels_root, &difficulties, known.len())?;
+        }
+    }
+
+    let mut load_errors = Vec::new();
+    let errors = checks.collect_errors.then_some(&mut load_errors);
+    let collected = if let [levels_root] = levels_roots {
+        collect_levels(
+            levels_root,
+            &difficulties,
+            &tags,
+            limit,
+            !output.dry_run,
+            errors,
+        )?
+    } else {
+        aggregate_levels_from_roots(
+            levels_roots,
+            &difficulties,
+            &tags,
+            limit,
+            allow_override,
+            !output.dry_run,
+            errors,
+        )?
+    };
+    if !load_errors.is_empty() {
+        for error in &load_errors {
+            eprintln!("  {error:#}");
+        }
+        bail!("{} level(s) failed to load", load_errors.len());
+    }
+    if checks.require_solved {
+        ensure_all_solved(&collected)?;
+    }
+    let mut aggregated: Vec<LevelDefinition> =
+        collected.into_iter().map(|entry| entry.level).collect();
+    sort_aggregated(&mut aggregated, &known);
+
+    if output.dry_run {
+        return Ok(());
+    }
+
+    let contents = format_aggregated(&aggregated, output.minify)? + "\n";
+    let bytes = encode_aggregated(&contents, output.gzip)?;
+    let output_path = output
+        .path
+        .map(|path| aggregated_output_path(path, output.gzip));
+    match &output_path {
+        Some(path) => std::fs::write(path, &bytes)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => std::io::stdout().write_all(&bytes)?,
+    }
+
+    if let Some(manifest_path) = output.manifest {
+        let manifest = AggregateManifest::new(&aggregated, output_path.as_deref(), &bytes);
+        std::fs::write(
+            manifest_path,
+            serde_json::to_string_pretty(&manifest)? + "\n",
         )
-        .with_context(|| "Metadata sync failed, aborting generate-levels-json")?;
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    }
+    Ok(())
+}
+
+/// Release gate for `--require-solved`: fails listing every collected level whose levels.toml
+/// entry is not `solved = true`
+fn ensure_all_solved(collected: &[CollectedLevel]) -> Result<()> {
+    let unsolved: Vec<String> = collected
+        .iter()
+        .filter(|entry| entry.solved != Some(true))
+        .map(|entry| format!("  {}", entry.path.display()))
+        .collect();
+    if !unsolved.is_empty() {
+        bail!(
+            "{} level(s) are not marked solved in levels.toml:\n{}",
+            unsolved.len(),
+            unsolved.join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// `path` with `.gz` appended when gzip output is requested and the name doesn't end in it
+fn aggregated_output_path(path: &Path, gzip: bool) -> PathBuf {
+    if !gzip || path.extension().is_some_and(|ext| ext == "gz") {
+        return path.to_path_buf();
+    }
+    let mut file_name = path.as_os_str().to_os_string();
+    file_name.push(".gz");
+    PathBuf::from(file_name)
+}
+
+fn encode_aggregated(contents: &str, gzip: bool) -> Result<Vec<u8>> {
+    if !gzip {
+        return Ok(contents.as_bytes().to_vec());
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(contents.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+/// Orders levels by their difficulty's position in `difficulty_order` (the catalog order,
+/// easy < medium < hard < custom tiers), then by id, so the output does not depend on
+/// levels.toml layout
+///
+/// The sort is stable: levels sharing a difficulty and id keep their toml order. Unknown
+/// difficulties sort last.
+fn sort_aggregated(aggregated: &mut [LevelDefinition], difficulty_order: &[String]) {
+    aggregated.sort_by_key(|level| {
+        let difficulty_rank = level
+            .difficulty
+            .as_deref()
+            .and_then(|difficulty| {
+                difficulty_order
+                    .iter()
+                    .position(|known| known == difficulty)
+            })
+            .unwrap_or(difficulty_order.len());
+        (difficulty_rank, level.id)
+    });
+}
+
+/// Serializes aggregated levels, compactly when `minify` is set since the web client
+/// downloads the whole file
+fn format_aggregated(aggregated: &[LevelDefinition], minify: bool) -> Result<String> {
+    let output = if minify {
+        serde_json::to_string(aggregated)
+    } else {
+        serde_json::to_string_pretty(aggregated)
+    };
+    output.with_context(|| "Failed to serialize aggregated levels JSON")
+}
+
+fn sync_levels_root(levels_root: &Path, difficulties: &[&str], known_count: usize) -> Result<()> {
+    let playbacks_root = levels::playbacks_root(levels_root);
+
+    eprintln!("Running metadata sync...");
+    let difficulty_filter = if difficulties.len() == known_count {
+        None
+    } else {
+        Some(difficulties.join(","))
+    };
+    let summary = sync_metadata::sync_metadata_with_roots(
+        levels_root,
+        &playbacks_root,
+        difficulty_filter.as_deref(),
+    )
+    .with_context(|| "Metadata sync failed, aborting generate-levels-json")?;
+
+    eprintln!("Sync completed:");
+    eprintln!("  - Generated {} names", summary.names_generated);
+    eprintln!(
+        "  - Updated {} levels.toml files",
+        summary.toml_files_updated
+    );
+    eprintln!("  - Created {} playbacks", summary.playbacks_created);
+    eprintln!();
+    Ok(())
+}
+
+/// Aggregates several levels roots in order, layering later roots over earlier ones.
+///
+/// A level whose id already came from an earlier root replaces it in place when
+/// `allow_override` is set and is an error otherwise. The limit applies to the
+/// merged result, in the same order.
+fn aggregate_levels_from_roots(
+    levels_roots: &[PathBuf],
+    difficulties: &[&str],
+    tags: &[String],
+    limit: Option<AggregateLimit>,
+    allow_override: bool,
+    write_back: bool,
+    mut errors: Option<&mut Vec<anyhow::Error>>,
+) -> Result<Vec<CollectedLevel>> {
+    let mut merged: Vec<CollectedLevel> = Vec::new();
+    let mut origin_by_id: HashMap<u32, (usize, &Path)> = HashMap::new();
+
+    for levels_root in levels_roots {
+        let levels = collect_levels(
+            levels_root,
+            difficulties,
+            tags,
+            None,
+            write_back,
+            errors.as_deref_mut(),
+        )?;
+        let contributed = levels.len();
+        let mut overridden = 0;
+
+        for entry in levels {
+            let level = &entry.level;
+            match origin_by_id.get(&level.id).copied() {
+                Some((_, earlier_root)) if !allow_override => bail!(
+                    "Level id {} from {} collides with the same id from {}",
+                    level.id,
+                    levels_root.display(),
+                    earlier_root.display()
+                ),
+                Some((index, _)) => {
+                    origin_by_id.insert(level.id, (index, levels_root));
+                    merged[index] = entry;
+                    overridden += 1;
+                },
+                None => {
+                    origin_by_id.insert(level.id, (merged.len(), levels_root));
+                    merged.push(entry);
+                },
+            }
+        }
 
-        eprintln!("Sync completed:");
-        eprintln!("  - Generated {} names", summary.names_generated);
         eprintln!(
-            "  - Updated {} levels.toml files",
-            summary.toml_files_updated
+            "Root {}: {} levels ({} overriding earlier roots)",
+            levels_root.display(),
+            contributed,
+            overridden
         );
-        eprintln!("  - Created {} playbacks", summary.playbacks_created);
-        eprintln!();
     }
 
-    let mut aggregated: Vec<LevelDefinition> = Vec::new();
+    if let Some(limit) = limit {
+        let total_available = merged.len();
+        let mut included_per_difficulty: HashMap<Option<String>, usize> = HashMap::new();
+        merged.retain(|entry| {
+            let key = if limit.per_difficulty {
+                entry.level.difficulty.clone()
+            } else {
+                None
+            };
+            let included = included_per_difficulty.entry(key).or_default();
+            *included += 1;
+            *included <= limit.count
+        });
+        eprintln!(
+            "Included {} of {} levels (limit applied)",
+            merged.len(),
+            total_available
+        );
+    }
+
+    Ok(merged)
+}
+
+/// Loads the levels referenced by each difficulty's levels.toml, in toml order
+///
+/// With a non-empty `tags`, only entries tagged with at least one of them are included. Level
+/// files are only read, never rewritten.
+pub fn aggregate_levels(
+    levels_root: &Path,
+    difficulties: &[&str],
+    tags: &[String],
+    limit: Option<AggregateLimit>,
+) -> Result<Vec<LevelDefinition>> {
+    let collected = collect_levels(levels_root, difficulties, tags, limit, false, None)?;
+    Ok(collected.into_iter().map(|entry| entry.level).collect())
+}
 
-    for difficulty in difficulties {
+/// An aggregated level with the levels.toml details that don't survive into levels.json
+#[derive(Debug)]
+struct CollectedLevel {
+    level: LevelDefinition,
+    path: PathBuf,
+    solved: Option<bool>,
+}
+
+/// Like [`aggregate_levels`], but keeps the levels.toml details of each level. When `errors`
+/// is given, levels that fail to load are pushed there and skipped instead of failing. With
+/// `write_back`, fixes made while loading are saved to the level files.
+fn collect_levels(
+    levels_root: &Path,
+    difficulties: &[&str],
+    tags: &[String],
+    limit: Option<AggregateLimit>,
+    write_back: bool,
+    mut errors: Option<&mut Vec<anyhow::Error>>,
+) -> Result<Vec<CollectedLevel>> {
+    let mut aggregated: Vec<CollectedLevel> = Vec::new();
+    let mut total_available = 0;
+
+    for &difficulty in difficulties {
         let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
         if !levels_toml_path.exists() {
             continue;
         }
 
-        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+        let mut levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+        if !tags.is_empty() {
+            levels_toml.level.retain(|entry| {
+                entry
+                    .tags
+                    .as_ref()
+                    .is_some_and(|entry_tags| entry_tags.iter().any(|tag| tags.contains(tag)))
+            });
+        }
+        total_available += levels_toml
+            .level
+            .iter()
+            .filter(|entry| entry.file.is_some())
+            .count();
+        let mut included_for_difficulty = 0;
+
         for entry in levels_toml.level {
+            if let Some(limit) = limit {
+                let included = if limit.per_difficulty {
+                    included_for_difficulty
+                } else {
+                    aggregated.len()
+                };
+                if included >= limit.count {
+                    break;
+                }
+            }
+
             let file = match entry.file.as_deref() {
                 Some(file) => file,
                 None => continue,
             };
             let level_path = levels_root.join(difficulty).join(file);
-            if !level_path.exists() {
-                bail!("Level file not found: {}", level_path.display());
-            }
-
-            let mut level = load_level(&level_path)?;
+            let loaded = if level_path.exists() {
+                load_level(&level_path, write_back)
+            } else {
+                Err(anyhow!("Level file not found: {}", level_path.display()))
+            };
+            let mut level = match (loaded, errors.as_deref_mut()) {
+                (Ok(level), _) => level,
+                (Err(error), Some(errors)) => {
+                    errors.push(error);
+                    continue;
+                },
+                (Err(error), None) => return Err(error),
+            };
             let difficulty_value = entry
                 .difficulty
                 .as_deref()
                 .unwrap_or(difficulty)
                 .to_string();
             level.difficulty = Some(difficulty_value);
-            aggregated.push(level);
+            aggregated.push(CollectedLevel {
+                level,
+                path: level_path,
+                solved: entry.solved,
+            });
+            included_for_difficulty += 1;
         }
     }
 
-    if dry_run {
-        return Ok(());
+    if limit.is_some() {
+        eprintln!(
+            "Included {} of {} levels (limit applied)",
+            aggregated.len(),
+            total_available
+        );
     }
 
-    let output = serde_json::to_string_pretty(&aggregated)
-        .with_context(|| "Failed to serialize aggregated levels JSON")?;
-    println!("{output}");
+    Ok(aggregated)
+}
+
+pub fn run_verify_aggregate(levels_root: &Path, levels_json_path: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(levels_json_path)
+        .with_context(|| format!("Failed to read {}", levels_json_path.display()))?;
+    let committed: Vec<LevelDefinition> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", levels_json_path.display()))?;
+
+    let difficulties = levels::catalog_difficulties(levels_root);
+    let difficulties: Vec<&str> = difficulties.iter().map(String::as_str).collect();
+    let source = aggregate_levels(levels_root, &difficulties, &[], None)?;
+
+    let discrepancies = aggregate_discrepancies(&source, &committed)?;
+    if !discrepancies.is_empty() {
+        eprintln!(
+            "{} is out of date ({} discrepancy(ies)):",
+            levels_json_path.display(),
+            discrepancies.len()
+        );
+        for discrepancy in &discrepancies {
+            eprintln!("  - {discrepancy}");
+        }
+        bail!("Aggregate does not match the source catalog");
+    }
+
+    println!(
+        "✓ {} matches the source catalog ({} levels)",
+        levels_json_path.display(),
+        source.len()
+    );
     Ok(())
 }
 
-fn parse_filter(filter: Option<&str>) -> Result<Vec<&'static str>> {
+/// Compares a committed aggregate against the source catalog by level id.
+///
+/// Reports levels missing from either side, duplicated ids, and every JSON
+/// field whose value differs between the two definitions.
+pub fn aggregate_discrepancies(
+    source: &[LevelDefinition],
+    aggregate: &[LevelDefinition],
+) -> Result<Vec<String>> {
+    let mut discrepancies = Vec::new();
+    let source_by_id = index_by_id(source, "source catalog", &mut discrepancies)?;
+    let aggregate_by_id = index_by_id(aggregate, "levels.json", &mut discrepancies)?;
+
+    for (id, expected) in &source_by_id {
+        let Some(actual) = aggregate_by_id.get(id) else {
+            discrepancies.push(format!("level {id}: missing from levels.json"));
+            continue;
+        };
+        let (serde_json::Value::Object(expected), serde_json::Value::Object(actual)) =
+            (expected, actual)
+        else {
+            continue;
+        };
+        let fields: BTreeSet<&String> = expected.keys().chain(actual.keys()).collect();
+        for field in fields {
+            if expected.get(field) != actual.get(field) {
+                discrepancies.push(format!("level {id}: {field} differs from source"));
+            }
+        }
+    }
+
+    for id in aggregate_by_id.keys() {
+        if !source_by_id.contains_key(id) {
+            discrepancies.push(format!("level {id}: not present in the source catalog"));
+        }
+    }
+
+    Ok(discrepancies)
+}
+
+fn index_by_id(
+    levels: &[LevelDefinition],
+    label: &str,
+    discrepancies: &mut Vec<String>,
+) -> Result<BTreeMap<u32, serde_json::Value>> {
+    let mut by_id = BTreeMap::new();
+    for level in levels {
+        let value = serde_json::to_value(level)
+            .with_context(|| format!("Failed to serialize level {}", level.id))?;
+        if by_id.insert(level.id, value).is_some() {
+            discrepancies.push(format!("level {}: duplicate id in {label}", level.id));
+        }
+    }
+    Ok(by_id)
+}
+
+fn parse_tags(tags: Option<&str>) -> Result<Vec<String>> {
+    let Some(raw) = tags else {
+        return Ok(Vec::new());
+    };
+    let parsed: Vec<String> = raw
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    if parsed.is_empty() {
+        bail!("Tag filter cannot be empty");
+    }
+    Ok(parsed)
+}
+
+/// Selects the `known` difficulties named in a comma-separated filter, in `known` order
+fn parse_filter(filter: Option<&str>, known: &[String]) -> Result<Vec<String>> {
     if let Some(raw) = filter {
-        let mut selected = Vec::new();
         let requested: HashSet<String> = raw
             .split(',')
             .map(|item| item.trim().to_lowercase())
             .filter(|item| !item.is_empty())
             .collect();
-
-        for difficulty in levels::DEFAULT_DIFFICULTIES {
-            if requested.contains(difficulty) {
-                selected.push(difficulty);
-            }
-        }
+        let selected: Vec<String> = known
+            .iter()
+            .filter(|difficulty| requested.contains(*difficulty))
+            .cloned()
+            .collect();
 
         if selected.is_empty() {
-            bail!("Filter did not match any known difficulty (easy, medium, hard)");
+            bail!(
+                "Filter did not match any known difficulty ({})",
+                known.join(", ")
+            );
         }
         return Ok(selected);
     }
 
-    Ok(levels::DEFAULT_DIFFICULTIES.to_vec())
+    Ok(known.to_vec())
 }
 
-fn load_level(level_path: &Path) -> Result<LevelDefinition> {
+/// Loads a level with duplicate positions removed and a missing `totalFood` derived
+///
+/// Only with `write_back` are those fixes saved to the level file; dry runs and
+/// `verify-aggregate` leave the source untouched.
+fn load_level(level_path: &Path, write_back: bool) -> Result<LevelDefinition> {
     let contents = std::fs::read_to_string(level_path)
         .with_context(|| format!("Failed to read level file: {}", level_path.display()))?;
-    let mut level: LevelDefinition = serde_json::from_str(&contents)
+    let mut level_json: serde_json::Value = serde_json::from_str(&contents)
         .with_context(|| format!("Failed to parse level JSON: {}", level_path.display()))?;
 
+    let removed = level_json
+        .as_object_mut()
+        .map(normalize::dedup_positions)
+        .unwrap_or_default();
+    if !removed.is_empty() {
+        if write_back {
+            levels::write_json_pretty(level_path, &level_json).with_context(|| {
+                format!(
+                    "Failed to write normalized level JSON: {}",
+                    level_path.display()
+                )
+            })?;
+            eprintln!(
+                "Warning: removed duplicate positions from {} ({})",
+                level_path.display(),
+                normalize::describe(&removed)
+            );
+        } else {
+            eprintln!(
+                "Warning: ignoring duplicate positions in {} ({}); run normalize-levels to remove them",
+                level_path.display(),
+                normalize::describe(&removed)
+            );
+        }
+    }
+
+    let mut level: LevelDefinition = serde_json::from_value(level_json)
+        .with_context(|| format!("Failed to parse level JSON: {}", level_path.display()))?;
     if let Some(derived_total_food) = ensure_total_food(&mut level) {
-        migrate_missing_total_food(level_path, derived_total_food)?;
+        if write_back {
+            migrate_missing_total_food(level_path, derived_total_food)?;
+        }
     }
 
     Ok(level)
@@ -126,12 +664,6 @@ fn ensure_total_food(level: &mut LevelDefinition) -> Option<u32> {
     None
 }
 
-fn derive_total_food(level: &LevelDefinition) -> u32 {
-    let total = level.food.len() + level.floating_food.len() + level.falling_food.len();
-    // Level arrays cannot practically exceed u32::MAX in real-world usage.
-    total as u32
-}
-
 fn migrate_missing_total_food(level_path: &Path, total_food: u32) -> Result<()> {
     let contents = std::fs::read_to_string(level_path).with_context(|| {
         format!(
@@ -162,40 +694,23 @@ fn migrate_missing_total_food(level_path: &Path, total_food: u32) -> Result<()>
         "totalFood".to_string(),
         serde_json::Value::Number(serde_json::Number::from(total_food)),
     );
-    let migrated = serde_json::to_string_pretty(&level_json).with_context(|| {
-        format!(
-            "Failed to serialize migrated level JSON with totalFood: {}",
-            level_path.display()
-        )
-    })?;
-
-    std::fs::write(level_path, format!("{migrated}\n")).with_context(|| {
+    levels::write_json_pretty(level_path, &level_json).with_context(|| {
         format!(
             "Failed to write migrated level JSON with totalFood: {}",
             level_path.display()
         )
-    })?;
-
-    Ok(())
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::levels::{LevelMeta, LevelsToml};
-    use anyhow::anyhow;
     use serde_json::json;
     use std::fs;
     use std::path::Path;
-    use std::sync::MutexGuard;
     use tempfile::TempDir;
 
-    fn lock_cwd_mutex() -> Result<MutexGuard<'static, ()>> {
-        crate::test_cwd::cwd_mutex()
-            .lock()
-            .map_err(|_| anyhow!("Failed to lock cwd mutex"))
-    }
-
     fn create_test_level_json(levels_dir: &Path, filename: &str, name: &str) -> Result<()> {
         fs::create_dir_all(levels_dir)?;
         let level_json = json!({
@@ -248,22 +763,45 @@ mod tests {
         Ok(())
     }
 
+    fn easy_filter() -> AggregateFilter<'static> {
+        AggregateFilter {
+            difficulties: Some("easy"),
+            tags: None,
+        }
+    }
+
+    fn known_difficulties() -> Vec<String> {
+        levels::DEFAULT_DIFFICULTIES.map(str::to_string).to_vec()
+    }
+
     #[test]
     fn test_parse_filter_defaults_to_all_difficulties() -> Result<()> {
-        assert_eq!(parse_filter(None)?, levels::DEFAULT_DIFFICULTIES.to_vec());
+        assert_eq!(
+            parse_filter(None, &known_difficulties())?,
+            known_difficulties()
+        );
         Ok(())
     }
 
     #[test]
     fn test_parse_filter_is_case_insensitive_and_ordered() -> Result<()> {
-        let filtered = parse_filter(Some(" hard , EASY "))?;
+        let filtered = parse_filter(Some(" hard , EASY "), &known_difficulties())?;
         assert_eq!(filtered, vec!["easy", "hard"]);
         Ok(())
     }
 
+    #[test]
+    fn test_parse_filter_accepts_discovered_difficulties() -> Result<()> {
+        let mut known = known_difficulties();
+        known.push("expert".to_string());
+        assert_eq!(parse_filter(Some("expert"), &known)?, vec!["expert"]);
+        assert!(parse_filter(Some("expert"), &known_difficulties()).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_parse_filter_unknown_value_fails() {
-        let result = parse_filter(Some("legendary"));
+        let result = parse_filter(Some("legendary"), &known_difficulties());
         assert!(result.is_err());
         let error = result
             .expect_err("Expected invalid filter error")
@@ -273,41 +811,254 @@ mod tests {
 
     #[test]
     fn test_run_generate_levels_json_success_from_package_directory() -> Result<()> {
-        let _lock = lock_cwd_mutex()?;
-
         let temp_dir = TempDir::new()?;
         let easy_dir = temp_dir.path().join("levels/easy");
         create_test_level_json(&easy_dir, "level_001.json", "Package Level")?;
         write_levels_toml(&easy_dir, "easy", "level_001.json")?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
 
-        run_generate_levels_json(Some("easy"), true, false)
+        run_generate_levels_json(
+            &[levels::find_levels_root_from(temp_dir.path())?],
+            easy_filter(),
+            false,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                dry_run: true,
+                ..AggregateOutput::default()
+            },
+        )
     }
 
     #[test]
     fn test_run_generate_levels_json_success_from_repo_root() -> Result<()> {
-        let _lock = lock_cwd_mutex()?;
-
         let temp_dir = TempDir::new()?;
         let easy_dir = temp_dir.path().join("gsnake-levels/levels/easy");
         create_test_level_json(&easy_dir, "level_001.json", "Nested Level")?;
         write_levels_toml(&easy_dir, "easy", "level_001.json")?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
 
-        run_generate_levels_json(Some("easy"), true, false)
+        run_generate_levels_json(
+            &[levels::find_levels_root_from(temp_dir.path())?],
+            easy_filter(),
+            false,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                dry_run: true,
+                ..AggregateOutput::default()
+            },
+        )
     }
 
     #[test]
-    fn test_run_generate_levels_json_missing_level_file_fails() -> Result<()> {
-        let _lock = lock_cwd_mutex()?;
+    fn test_run_generate_levels_json_gzip_matches_uncompressed_output() -> Result<()> {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
 
+        let temp_dir = TempDir::new()?;
+        let easy_dir = temp_dir.path().join("levels/easy");
+        create_test_level_json(&easy_dir, "level_001.json", "Gzip Level")?;
+        write_levels_toml(&easy_dir, "easy", "level_001.json")?;
+        let levels_roots = [temp_dir.path().join("levels")];
+
+        let plain_path = temp_dir.path().join("levels.json");
+        let gzip_path = temp_dir.path().join("levels.json.gz");
+        for (path, gzip) in [(&plain_path, false), (&gzip_path, true)] {
+            run_generate_levels_json(
+                &levels_roots,
+                easy_filter(),
+                false,
+                None,
+                true,
+                AggregateChecks::default(),
+                AggregateOutput {
+                    minify: true,
+                    path: Some(path),
+                    gzip,
+                    ..AggregateOutput::default()
+                },
+            )?;
+        }
+
+        let plain = fs::read(&plain_path)?;
+        let mut decompressed = Vec::new();
+        GzDecoder::new(fs::File::open(&gzip_path)?).read_to_end(&mut decompressed)?;
+        assert_eq!(decompressed, plain);
+        assert!(plain.ends_with(b"]\n"));
+
+        let error = run_generate_levels_json(
+            &levels_roots,
+            easy_filter(),
+            false,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                gzip: true,
+                ..AggregateOutput::default()
+            },
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("--gzip requires --output"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_manifest_describes_exact_emitted_bytes() -> Result<()> {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        create_limit_fixture(&levels_root)?;
+        // `--gzip` appends the missing `.gz`, and the manifest names the file actually written.
+        let output_path = temp_dir.path().join("levels.json");
+        let manifest_path = temp_dir.path().join("levels.manifest.json");
+
+        run_generate_levels_json(
+            &[levels_root],
+            AggregateFilter::default(),
+            false,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                minify: true,
+                path: Some(&output_path),
+                gzip: true,
+                manifest: Some(&manifest_path),
+                ..AggregateOutput::default()
+            },
+        )?;
+
+        let manifest: AggregateManifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path)?)?;
+        assert!(!output_path.exists());
+        let written = fs::read(temp_dir.path().join("levels.json.gz"))?;
+        assert_eq!(manifest.sha256, format!("{:x}", Sha256::digest(&written)));
+        assert_eq!(manifest.sha256.len(), 64);
+        assert_eq!(manifest.file.as_deref(), Some("levels.json.gz"));
+
+        let mut decompressed = String::new();
+        GzDecoder::new(written.as_slice()).read_to_string(&mut decompressed)?;
+        let levels: Vec<serde_json::Value> = serde_json::from_str(&decompressed)?;
+        assert_eq!(manifest.level_count, levels.len());
+        assert_eq!(manifest.generated_levels.len(), 6);
+        assert_eq!(manifest.generated_levels[0].name, "easy a.json");
+        Ok(())
+    }
+
+    #[test]
+    fn test_require_solved_names_every_unsolved_level() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let easy_dir = levels_root.join("easy");
+        create_limit_fixture(&levels_root)?;
+        let mut levels_toml = levels::read_levels_toml(&easy_dir.join("levels.toml"))?;
+        levels_toml.level[1].solved = Some(false);
+        levels_toml.level[2].solved = None;
+        levels::write_levels_toml(&easy_dir.join("levels.toml"), &levels_toml)?;
+        // Filtered-out levels don't block the release.
+        let medium_toml_path = levels_root.join("medium/levels.toml");
+        let mut medium_toml = levels::read_levels_toml(&medium_toml_path)?;
+        medium_toml.level[0].solved = Some(false);
+        levels::write_levels_toml(&medium_toml_path, &medium_toml)?;
+
+        let run = |require_solved| {
+            run_generate_levels_json(
+                std::slice::from_ref(&levels_root),
+                easy_filter(),
+                false,
+                None,
+                true,
+                AggregateChecks {
+                    require_solved,
+                    ..AggregateChecks::default()
+                },
+                AggregateOutput {
+                    dry_run: true,
+                    ..AggregateOutput::default()
+                },
+            )
+        };
+        run(false)?;
+        let error = run(true).unwrap_err().to_string();
+        assert!(error.contains("2 level(s) are not marked solved"));
+        assert!(error.contains("b.json"));
+        assert!(error.contains("c.json"));
+        assert!(!error.contains("a.json"));
+        assert!(!error.contains("medium"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_errors_reports_every_broken_level_in_one_run() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let easy_dir = levels_root.join("easy");
+        create_test_level_json(&easy_dir, "good.json", "Good")?;
+        fs::write(easy_dir.join("broken.json"), "{not-valid-json}")?;
+        write_levels_toml_entries(
+            &easy_dir,
+            "easy",
+            &["missing.json", "good.json", "broken.json"],
+        )?;
+
+        let mut errors = Vec::new();
+        let collected =
+            collect_levels(&levels_root, &["easy"], &[], None, false, Some(&mut errors))?;
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].level.name, "Good");
+        assert_eq!(errors.len(), 2);
+        assert!(format!("{:#}", errors[0]).contains("Level file not found"));
+        assert!(format!("{:#}", errors[0]).contains("missing.json"));
+        assert!(format!("{:#}", errors[1]).contains("Failed to parse level JSON"));
+        assert!(format!("{:#}", errors[1]).contains("broken.json"));
+
+        let run = |collect_errors| {
+            run_generate_levels_json(
+                std::slice::from_ref(&levels_root),
+                easy_filter(),
+                false,
+                None,
+                true,
+                AggregateChecks {
+                    collect_errors,
+                    ..AggregateChecks::default()
+                },
+                AggregateOutput {
+                    dry_run: true,
+                    ..AggregateOutput::default()
+                },
+            )
+            .unwrap_err()
+            .to_string()
+        };
+        assert_eq!(run(true), "2 level(s) failed to load");
+        assert!(run(false).contains("Level file not found"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_generate_levels_json_missing_level_file_fails() -> Result<()> {
         let temp_dir = TempDir::new()?;
         let easy_dir = temp_dir.path().join("levels/easy");
         fs::create_dir_all(&easy_dir)?;
         write_levels_toml(&easy_dir, "easy", "missing_level.json")?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
 
-        let result = run_generate_levels_json(Some("easy"), true, false);
+        let result = run_generate_levels_json(
+            &[levels::find_levels_root_from(temp_dir.path())?],
+            easy_filter(),
+            false,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                dry_run: true,
+                ..AggregateOutput::default()
+            },
+        );
         assert!(result.is_err());
         let error = result
             .expect_err("Expected missing level error")
@@ -318,16 +1069,24 @@ mod tests {
 
     #[test]
     fn test_run_generate_levels_json_invalid_level_json_fails() -> Result<()> {
-        let _lock = lock_cwd_mutex()?;
-
         let temp_dir = TempDir::new()?;
         let easy_dir = temp_dir.path().join("levels/easy");
         fs::create_dir_all(&easy_dir)?;
         fs::write(easy_dir.join("invalid_level.json"), "{not-valid-json}")?;
         write_levels_toml(&easy_dir, "easy", "invalid_level.json")?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
 
-        let result = run_generate_levels_json(Some("easy"), true, false);
+        let result = run_generate_levels_json(
+            &[levels::find_levels_root_from(temp_dir.path())?],
+            easy_filter(),
+            false,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                dry_run: true,
+                ..AggregateOutput::default()
+            },
+        );
         assert!(result.is_err());
         let error = format!(
             "{:#}",
@@ -340,15 +1099,385 @@ mod tests {
 
     #[test]
     fn test_run_generate_levels_json_with_sync_enabled() -> Result<()> {
-        let _lock = lock_cwd_mutex()?;
-
         let temp_dir = TempDir::new()?;
         fs::create_dir_all(temp_dir.path().join("levels/easy"))?;
         fs::create_dir_all(temp_dir.path().join("levels/medium"))?;
         fs::create_dir_all(temp_dir.path().join("levels/hard"))?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
 
-        run_generate_levels_json(None, true, true)
+        run_generate_levels_json(
+            &[levels::find_levels_root_from(temp_dir.path())?],
+            AggregateFilter::default(),
+            true,
+            None,
+            true,
+            AggregateChecks::default(),
+            AggregateOutput {
+                dry_run: true,
+                ..AggregateOutput::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_dry_run_and_verify_aggregate_leave_duplicate_positions_on_disk() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let easy_dir = temp_dir.path().join("levels/easy");
+        create_test_level_json(&easy_dir, "level_001.json", "Duplicates")?;
+        write_levels_toml(&easy_dir, "easy", "level_001.json")?;
+        let level_path = easy_dir.join("level_001.json");
+        let mut level_json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&level_path)?)?;
+        level_json["obstacles"] = json!([{ "x": 3, "y": 3 }, { "x": 3, "y": 3 }]);
+        write_test_level_json(&easy_dir, "level_001.json", &level_json)?;
+        let before = fs::read_to_string(&level_path)?;
+        let levels_roots = [temp_dir.path().join("levels")];
+        let levels_json_path = temp_dir.path().join("levels.json");
+
+        let generate = |dry_run| {
+            run_generate_levels_json(
+                &levels_roots,
+                easy_filter(),
+                false,
+                None,
+                true,
+                AggregateChecks::default(),
+                AggregateOutput {
+                    dry_run,
+                    path: Some(&levels_json_path),
+                    ..AggregateOutput::default()
+                },
+            )
+        };
+        generate(true)?;
+        assert_eq!(fs::read_to_string(&level_path)?, before);
+
+        generate(false)?;
+        let aggregated: Vec<LevelDefinition> =
+            serde_json::from_str(&fs::read_to_string(&levels_json_path)?)?;
+        assert_eq!(aggregated[0].obstacles.len(), 1);
+        let rewritten = fs::read_to_string(&level_path)?;
+        assert_ne!(rewritten, before);
+
+        write_test_level_json(&easy_dir, "level_001.json", &level_json)?;
+        run_verify_aggregate(&levels_roots[0], &levels_json_path)?;
+        assert_eq!(fs::read_to_string(&level_path)?, before);
+        Ok(())
+    }
+
+    fn write_levels_toml_entries(
+        levels_dir: &Path,
+        difficulty: &str,
+        files: &[&str],
+    ) -> Result<()> {
+        let levels_toml = LevelsToml {
+            level: files
+                .iter()
+                .map(|file| LevelMeta {
+                    id: Some(file.trim_end_matches(".json").to_string()),
+                    file: Some(file.to_string()),
+                    author: Some("gsnake".to_string()),
+                    solved: Some(true),
+                    difficulty: Some(difficulty.to_string()),
+                    tags: Some(vec![]),
+                    description: Some("Test level".to_string()),
+                })
+                .collect(),
+        };
+        fs::write(
+            levels_dir.join("levels.toml"),
+            toml::to_string_pretty(&levels_toml)?,
+        )?;
+        Ok(())
+    }
+
+    fn create_limit_fixture(levels_root: &Path) -> Result<()> {
+        for difficulty in ["easy", "medium"] {
+            let dir = levels_root.join(difficulty);
+            let files = ["a.json", "b.json", "c.json"];
+            for file in files {
+                create_test_level_json(&dir, file, &format!("{difficulty} {file}"))?;
+            }
+            write_levels_toml_entries(&dir, difficulty, &files)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_levels_without_limit_includes_everything() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        create_limit_fixture(&levels_root)?;
+
+        let aggregated = aggregate_levels(&levels_root, &["easy", "medium"], &[], None)?;
+        assert_eq!(aggregated.len(), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_levels_filters_by_any_matching_tag() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let easy_dir = temp_dir.path().join("levels/easy");
+        let tagged = [
+            ("tutorial.json", Some(vec!["tutorial"])),
+            ("boss.json", Some(vec!["boss", "late"])),
+            ("plain.json", None),
+            ("other.json", Some(vec!["secret"])),
+        ];
+        let mut entries = Vec::new();
+        for (file, tags) in tagged {
+            create_test_level_json(&easy_dir, file, file)?;
+            entries.push(LevelMeta {
+                id: Some(file.trim_end_matches(".json").to_string()),
+                file: Some(file.to_string()),
+                author: Some("gsnake".to_string()),
+                solved: Some(true),
+                difficulty: Some("easy".to_string()),
+                tags: tags.map(|tags| tags.into_iter().map(String::from).collect()),
+                description: None,
+            });
+        }
+        fs::write(
+            easy_dir.join("levels.toml"),
+            toml::to_string_pretty(&LevelsToml { level: entries })?,
+        )?;
+
+        let tags = parse_tags(Some("boss, tutorial"))?;
+        let aggregated = aggregate_levels(&temp_dir.path().join("levels"), &["easy"], &tags, None)?;
+        let names: Vec<&str> = aggregated.iter().map(|level| level.name.as_str()).collect();
+        assert_eq!(names, vec!["tutorial.json", "boss.json"]);
+        assert!(parse_tags(Some(" , ")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_levels_applies_overall_limit_in_toml_order() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        create_limit_fixture(&levels_root)?;
+
+        let limit = AggregateLimit {
+            count: 4,
+            per_difficulty: false,
+        };
+        let aggregated = aggregate_levels(&levels_root, &["easy", "medium"], &[], Some(limit))?;
+        let names: Vec<&str> = aggregated.iter().map(|level| level.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["easy a.json", "easy b.json", "easy c.json", "medium a.json"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_levels_applies_limit_per_difficulty() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        create_limit_fixture(&levels_root)?;
+
+        let limit = AggregateLimit {
+            count: 1,
+            per_difficulty: true,
+        };
+        let aggregated = aggregate_levels(&levels_root, &["easy", "medium"], &[], Some(limit))?;
+        let names: Vec<&str> = aggregated.iter().map(|level| level.name.as_str()).collect();
+        assert_eq!(names, vec!["easy a.json", "medium a.json"]);
+        Ok(())
+    }
+
+    fn level_with_id(id: u32, exit_x: i32) -> LevelDefinition {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": format!("Level {id}"),
+            "difficulty": "easy",
+            "gridSize": { "width": 10, "height": 10 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": exit_x, "y": 5 },
+            "snakeDirection": "East",
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sort_aggregated_orders_by_difficulty_then_id() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        create_root_with_ids(&levels_root, &[(9, "nine"), (2, "two"), (5, "five")])?;
+        let mut hard_level = serde_json::to_value(level_with_id(1, 5))?;
+        hard_level["difficulty"] = json!("hard");
+        write_test_level_json(&levels_root.join("hard"), "level_1.json", &hard_level)?;
+        write_levels_toml_entries(&levels_root.join("hard"), "hard", &["level_1.json"])?;
+        // Custom tiers keep their catalog order instead of interleaving by id.
+        for (difficulty, id) in [("tutorial", 3), ("expert", 4), ("expert", 7)] {
+            let mut level = serde_json::to_value(level_with_id(id, 5))?;
+            level["difficulty"] = json!(difficulty);
+            let file = format!("level_{id}.json");
+            write_test_level_json(&levels_root.join(difficulty), &file, &level)?;
+        }
+        write_levels_toml_entries(&levels_root.join("tutorial"), "tutorial", &["level_3.json"])?;
+        write_levels_toml_entries(
+            &levels_root.join("expert"),
+            "expert",
+            &["level_7.json", "level_4.json"],
+        )?;
+
+        let mut aggregated = aggregate_levels(
+            &levels_root,
+            &["tutorial", "hard", "expert", "easy"],
+            &[],
+            None,
+        )?;
+        let toml_order: Vec<u32> = aggregated.iter().map(|level| level.id).collect();
+        assert_eq!(toml_order, vec![3, 1, 7, 4, 9, 2, 5]);
+
+        let catalog = levels::catalog_difficulties(&levels_root);
+        assert_eq!(catalog, ["easy", "medium", "hard", "expert", "tutorial"]);
+        sort_aggregated(&mut aggregated, &catalog);
+        let ids: Vec<u32> = aggregated.iter().map(|level| level.id).collect();
+        assert_eq!(ids, vec![2, 5, 9, 1, 4, 7, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_aggregated_minify_is_compact_and_equivalent() -> Result<()> {
+        let levels = vec![level_with_id(1, 5), level_with_id(2, 6)];
+
+        let pretty = format_aggregated(&levels, false)?;
+        let minified = format_aggregated(&levels, true)?;
+        assert!(pretty.contains('\n'));
+        assert!(!minified.contains('\n'));
+        assert!(minified.len() < pretty.len());
+
+        let reparsed: Vec<LevelDefinition> = serde_json::from_str(&minified)?;
+        assert_eq!(
+            serde_json::to_value(&reparsed)?,
+            serde_json::to_value(&levels)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_discrepancies_empty_when_matching_in_any_order() -> Result<()> {
+        let source = vec![level_with_id(1, 5), level_with_id(2, 5)];
+        let aggregate = vec![level_with_id(2, 5), level_with_id(1, 5)];
+        assert!(aggregate_discrepancies(&source, &aggregate)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_discrepancies_reports_by_id() -> Result<()> {
+        let source = vec![level_with_id(1, 5), level_with_id(2, 5)];
+        let aggregate = vec![
+            level_with_id(1, 6),
+            level_with_id(3, 5),
+            level_with_id(3, 5),
+        ];
+
+        let discrepancies = aggregate_discrepancies(&source, &aggregate)?;
+        assert_eq!(
+            discrepancies,
+            vec![
+                "level 3: duplicate id in levels.json",
+                "level 1: exit differs from source",
+                "level 2: missing from levels.json",
+                "level 3: not present in the source catalog",
+            ]
+        );
+        Ok(())
+    }
+
+    fn create_root_with_ids(levels_root: &Path, ids: &[(u32, &str)]) -> Result<()> {
+        let dir = levels_root.join("easy");
+        let mut files = Vec::new();
+        for (id, name) in ids {
+            let file = format!("level_{id}.json");
+            let mut level_json = serde_json::to_value(level_with_id(*id, 5))?;
+            level_json["name"] = json!(name);
+            write_test_level_json(&dir, &file, &level_json)?;
+            files.push(file);
+        }
+        let files: Vec<&str> = files.iter().map(String::as_str).collect();
+        write_levels_toml_entries(&dir, "easy", &files)
+    }
+
+    #[test]
+    fn test_aggregate_levels_from_roots_overrides_by_id_in_place() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let base = temp_dir.path().join("base/levels");
+        let community = temp_dir.path().join("community/levels");
+        create_root_with_ids(&base, &[(1, "base one"), (2, "base two")])?;
+        create_root_with_ids(&community, &[(2, "community two"), (3, "community three")])?;
+
+        let merged = aggregate_levels_from_roots(
+            &[base, community],
+            &["easy"],
+            &[],
+            None,
+            true,
+            false,
+            None,
+        )?;
+        let names: Vec<&str> = merged
+            .iter()
+            .map(|entry| entry.level.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["base one", "community two", "community three"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_levels_from_roots_rejects_collision_without_override() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let base = temp_dir.path().join("base/levels");
+        let community = temp_dir.path().join("community/levels");
+        create_root_with_ids(&base, &[(1, "base one")])?;
+        create_root_with_ids(&community, &[(1, "community one")])?;
+
+        let error = aggregate_levels_from_roots(
+            &[base, community],
+            &["easy"],
+            &[],
+            None,
+            false,
+            false,
+            None,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Level id 1 from"));
+        assert!(error.to_string().contains("collides"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_levels_from_roots_applies_limit_after_merge() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let base = temp_dir.path().join("base/levels");
+        let community = temp_dir.path().join("community/levels");
+        create_root_with_ids(&base, &[(1, "base one")])?;
+        create_root_with_ids(&community, &[(2, "community two"), (3, "community three")])?;
+
+        let limit = AggregateLimit {
+            count: 2,
+            per_difficulty: false,
+        };
+        let merged = aggregate_levels_from_roots(
+            &[base, community],
+            &["easy"],
+            &[],
+            Some(limit),
+            true,
+            false,
+            None,
+        )?;
+        let ids: Vec<u32> = merged.iter().map(|entry| entry.level.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        Ok(())
     }
 
     #[test]
@@ -371,8 +1500,13 @@ mod tests {
             "spikes": []
         });
         write_test_level_json(temp_dir.path(), "missing-total-food.json", &level_json)?;
+        let before = fs::read_to_string(&level_path)?;
+
+        let loaded = load_level(&level_path, false)?;
+        assert_eq!(loaded.total_food, Some(4));
+        assert_eq!(fs::read_to_string(&level_path)?, before);
 
-        let loaded = load_level(&level_path)?;
+        let loaded = load_level(&level_path, true)?;
         assert_eq!(loaded.total_food, Some(4));
 
         let migrated_contents = fs::read_to_string(&level_path)?;
@@ -404,7 +1538,7 @@ mod tests {
         write_test_level_json(temp_dir.path(), "explicit-total-food.json", &level_json)?;
         let before = fs::read_to_string(&level_path)?;
 
-        let loaded = load_level(&level_path)?;
+        let loaded = load_level(&level_path, true)?;
         assert_eq!(loaded.total_food, Some(9));
         let after = fs::read_to_string(&level_path)?;
         assert_eq!(before, after);
@@ -0,0 +1,370 @@
+use crate::{playback::load_playback_directions, solver::load_level};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use gsnake_core::{engine::GameEngine, Direction, GameStatus, LevelDefinition, Position};
+use std::path::Path;
+
+/// Glyphs used for each kind of cell when rendering a frame as text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphSet {
+    pub snake_head: &'static str,
+    pub snake_body: &'static str,
+    pub food: &'static str,
+    pub floating_food: &'static str,
+    pub falling_food: &'static str,
+    pub obstacle: &'static str,
+    pub stone: &'static str,
+    pub spike: &'static str,
+    pub exit: &'static str,
+    pub empty: &'static str,
+}
+
+impl GlyphSet {
+    /// Single-width ASCII glyphs that render in any terminal and font
+    pub const ASCII: GlyphSet = GlyphSet {
+        snake_head: "@",
+        snake_body: "o",
+        food: "*",
+        floating_food: "+",
+        falling_food: "v",
+        obstacle: "#",
+        stone: "O",
+        spike: "^",
+        exit: "E",
+        empty: ".",
+    };
+
+    /// Double-width emoji glyphs; every cell, including empty ones, is an emoji so columns line up
+    pub const EMOJI: GlyphSet = GlyphSet {
+        snake_head: "🐲",
+        snake_body: "🟩",
+        food: "🍎",
+        floating_food: "🎈",
+        falling_food: "🍋",
+        obstacle: "🧱",
+        stone: "🪨",
+        spike: "🔺",
+        exit: "🚪",
+        empty: "⬛",
+    };
+
+    fn entries(&self) -> [(&'static str, &'static str); 10] {
+        [
+            (self.snake_head, "snake head"),
+            (self.snake_body, "snake body"),
+            (self.food, "food"),
+            (self.floating_food, "floating food"),
+            (self.falling_food, "falling food"),
+            (self.obstacle, "obstacle"),
+            (self.stone, "stone"),
+            (self.spike, "spike"),
+            (self.exit, "exit"),
+            (self.empty, "empty"),
+        ]
+    }
+}
+
+impl Default for GlyphSet {
+    fn default() -> Self {
+        Self::ASCII
+    }
+}
+
+/// Named glyph presets selectable from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GlyphPreset {
+    #[default]
+    Ascii,
+    Emoji,
+}
+
+impl GlyphPreset {
+    pub fn glyphs(self) -> GlyphSet {
+        match self {
+            GlyphPreset::Ascii => GlyphSet::ASCII,
+            GlyphPreset::Emoji => GlyphSet::EMOJI,
+        }
+    }
+}
+
+pub fn run_headless_replay(
+    level_path: &Path,
+    playback_path: &Path,
+    glyphs: &GlyphSet,
+    legend: bool,
+) -> Result<()> {
+    if legend {
+        println!("{}", format_legend(glyphs));
+    }
+    print!("{}", replay_transcript(level_path, playback_path, glyphs)?);
+    Ok(())
+}
+
+pub fn run_preview(level_path: &Path) -> Result<()> {
+    let level = load_level(level_path)
+        .with_context(|| format!("Failed to load level: {}", level_path.display()))?;
+    println!("{}", preview_level(&level)?);
+    Ok(())
+}
+
+/// Renders the starting board inside an ASCII border, with the snake's heading and a legend
+pub fn preview_level(level: &LevelDefinition) -> Result<String> {
+    let engine = GameEngine::new(level.clone()).with_context(|| "Invalid grid size in level")?;
+    Ok(format!(
+        "{} ({}x{}), heading {:?}\n{}{}",
+        level.name,
+        level.grid_size.width,
+        level.grid_size.height,
+        level.snake_direction,
+        render_board_bordered(level, &engine),
+        format_legend(&heading_glyphs(Some(level.snake_direction)))
+    ))
+}
+
+/// ASCII glyphs with the snake head drawn as an arrow pointing along `direction`, or the
+/// plain head glyph without one
+///
+/// `^` and `v` already mean spike and falling food, so vertical headings use `A` and `V`.
+fn heading_glyphs(direction: Option<Direction>) -> GlyphSet {
+    let snake_head = match direction {
+        Some(Direction::North) => "A",
+        Some(Direction::South) => "V",
+        Some(Direction::East) => ">",
+        Some(Direction::West) => "<",
+        None => GlyphSet::ASCII.snake_head,
+    };
+    GlyphSet {
+        snake_head,
+        ..GlyphSet::ASCII
+    }
+}
+
+/// The engine's board in ASCII glyphs inside a `+---+` border, as shown by `preview`; the
+/// snake head points the way the snake is moving
+pub fn render_board_bordered(level: &LevelDefinition, engine: &GameEngine) -> String {
+    let glyphs = heading_glyphs(engine.level_state().snake.direction);
+    let horizontal = format!("+{}+", "-".repeat(level.grid_size.width.max(0) as usize));
+    let mut output = format!("{horizontal}\n");
+    for row in render_frame_ascii(level, engine, &glyphs).lines() {
+        output.push_str(&format!("|{row}|\n"));
+    }
+    output.push_str(&format!("{horizontal}\n"));
+    output
+}
+
+/// Lists which glyph stands for which cell kind, one per line
+pub fn format_legend(glyphs: &GlyphSet) -> String {
+    let mut output = String::from("Legend:");
+    for (glyph, label) in glyphs.entries() {
+        output.push_str(&format!("\n  {glyph}  {label}"));
+    }
+    output
+}
+
+/// Replays a playback without a terminal UI, rendering the board after every move
+pub fn replay_transcript(
+    level_path: &Path,
+    playback_path: &Path,
+    glyphs: &GlyphSet,
+) -> Result<String> {
+    let level = load_level(level_path)
+        .with_context(|| format!("Failed to load level: {}", level_path.display()))?;
+    let directions = load_playback_directions(playback_path)
+        .with_context(|| format!("Failed to load playback: {}", playback_path.display()))?;
+    let mut engine = GameEngine::new(level.clone())
+        .with_context(|| format!("Invalid grid size in level file: {}", level_path.display()))?;
+
+    let mut output = format!("Start\n{}", render_frame_ascii(&level, &engine, glyphs));
+    for (index, direction) in directions.iter().copied().enumerate() {
+        if engine.game_state().status != GameStatus::Playing {
+            break;
+        }
+        engine
+            .process_move(direction)
+            .with_context(|| format!("Failed to apply move {}", index + 1))?;
+        output.push_str(&format!(
+            "\nMove {}/{}: {direction:?}\n{}",
+            index + 1,
+            directions.len(),
+            render_frame_ascii(&level, &engine, glyphs)
+        ));
+    }
+    output.push_str(&format!("\nStatus: {:?}\n", engine.game_state().status));
+    Ok(output)
+}
+
+/// Renders the engine's current board as text, one line per grid row.
+///
+/// Obstacles and the exit never move, so they come from `level`; everything else
+/// is read from the engine. When several things share a cell, the snake wins,
+/// then stones, food, spikes, the exit and obstacles, in that order.
+pub fn render_frame_ascii(
+    level: &LevelDefinition,
+    engine: &GameEngine,
+    glyphs: &GlyphSet,
+) -> String {
+    let state = engine.level_state();
+    let contains =
+        |cells: &[Position], x: i32, y: i32| cells.iter().any(|cell| cell.x == x && cell.y == y);
+
+    let mut output = String::new();
+    for y in 0..level.grid_size.height {
+        for x in 0..level.grid_size.width {
+            let segments = &state.snake.segments;
+            let glyph = if segments
+                .first()
+                .is_some_and(|head| head.x == x && head.y == y)
+            {
+                glyphs.snake_head
+            } else if contains(segments, x, y) {
+                glyphs.snake_body
+            } else if contains(&state.stones, x, y) {
+                glyphs.stone
+            } else if contains(&state.food, x, y) {
+                glyphs.food
+            } else if contains(&state.floating_food, x, y) {
+                glyphs.floating_food
+            } else if contains(&state.falling_food, x, y) {
+                glyphs.falling_food
+            } else if contains(&state.spikes, x, y) {
+                glyphs.spike
+            } else if level.exit.x == x && level.exit.y == y {
+                glyphs.exit
+            } else if contains(&level.obstacles, x, y) {
+                glyphs.obstacle
+            } else {
+                glyphs.empty
+            };
+            output.push_str(glyph);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn test_level() -> LevelDefinition {
+        serde_json::from_value(json!({
+            "id": 1,
+            "name": "Render",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 2 },
+            "snake": [{ "x": 1, "y": 0 }, { "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [
+                { "x": 0, "y": 1 }, { "x": 1, "y": 1 }, { "x": 2, "y": 1 },
+                { "x": 3, "y": 1 }, { "x": 4, "y": 1 }
+            ],
+            "food": [{ "x": 2, "y": 0 }],
+            "exit": { "x": 4, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 1
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_frame_ascii_default_glyphs() {
+        let level = test_level();
+        let engine = GameEngine::new(level.clone()).unwrap();
+        assert_eq!(
+            render_frame_ascii(&level, &engine, &GlyphSet::default()),
+            "o@*.E\n#####\n"
+        );
+    }
+
+    #[test]
+    fn test_render_frame_ascii_emoji_glyphs() {
+        let level = test_level();
+        let engine = GameEngine::new(level.clone()).unwrap();
+        let rendered = render_frame_ascii(&level, &engine, &GlyphPreset::Emoji.glyphs());
+        assert_eq!(rendered.lines().next(), Some("🟩🐲🍎⬛🚪"));
+    }
+
+    #[test]
+    fn test_format_legend_lists_every_glyph() {
+        let legend = format_legend(&GlyphSet::ASCII);
+        assert_eq!(legend.lines().count(), 11);
+        assert!(legend.contains("@  snake head"));
+        assert!(legend.contains("^  spike"));
+    }
+
+    #[test]
+    fn test_preview_level_snapshot() {
+        let level: LevelDefinition = serde_json::from_value(json!({
+            "id": 2,
+            "name": "Preview",
+            "difficulty": "easy",
+            "gridSize": { "width": 6, "height": 3 },
+            "snake": [{ "x": 1, "y": 1 }, { "x": 1, "y": 2 }],
+            "snakeDirection": "North",
+            "obstacles": [{ "x": 3, "y": 0 }],
+            "food": [{ "x": 2, "y": 1 }],
+            "exit": { "x": 5, "y": 2 },
+            "floatingFood": [{ "x": 0, "y": 0 }],
+            "fallingFood": [{ "x": 4, "y": 0 }],
+            "stones": [{ "x": 3, "y": 1 }],
+            "spikes": [{ "x": 3, "y": 2 }],
+            "totalFood": 1
+        }))
+        .unwrap();
+
+        assert_eq!(
+            preview_level(&level).unwrap(),
+            "\
+Preview (6x3), heading North
++------+
+|+..#v.|
+|.A*O..|
+|.o.^.E|
++------+
+Legend:
+  A  snake head
+  o  snake body
+  *  food
+  +  floating food
+  v  falling food
+  #  obstacle
+  O  stone
+  ^  spike
+  E  exit
+  .  empty"
+        );
+
+        let mut west = level;
+        west.snake = vec![Position { x: 1, y: 1 }, Position { x: 2, y: 1 }];
+        west.food.clear();
+        west.snake_direction = Direction::West;
+        let preview = preview_level(&west).unwrap();
+        assert!(preview.contains("\n|.<oO..|\n"), "{preview}");
+        assert!(preview.contains("\n  <  snake head\n"));
+    }
+
+    #[test]
+    fn test_replay_transcript_renders_each_move() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("level.json");
+        let playback_path = temp_dir.path().join("playback.json");
+        fs::write(&level_path, serde_json::to_string(&test_level()).unwrap()).unwrap();
+        fs::write(
+            &playback_path,
+            r#"[{"key":"Right","delay_ms":1},{"key":"Right","delay_ms":1}]"#,
+        )
+        .unwrap();
+
+        let transcript = replay_transcript(&level_path, &playback_path, &GlyphSet::ASCII).unwrap();
+        assert!(transcript.starts_with("Start\no@*.E\n"));
+        assert!(transcript.contains("Move 1/2: East\noo@.E\n"));
+        assert!(transcript.contains("Move 2/2: East\n"));
+        assert!(transcript.contains("Status: "));
+    }
+}
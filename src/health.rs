@@ -0,0 +1,228 @@
+use crate::{analysis, levels, solver, validate_levels_toml, verify};
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// Outcome of one category of the catalog health check
+#[derive(Debug, Default)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub checked: usize,
+    pub failures: Vec<String>,
+}
+
+impl HealthCheck {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            ..Self::default()
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Consolidated result of every health check category
+#[derive(Debug)]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(HealthCheck::passed)
+    }
+
+    fn format(&self) -> String {
+        let mut output = String::from("Catalog health:");
+        for check in &self.checks {
+            let marker = if check.passed() { "✓" } else { "✗" };
+            output.push_str(&format!(
+                "\n  {marker} {}: {} checked, {} issue(s)",
+                check.name,
+                check.checked,
+                check.failures.len()
+            ));
+            for failure in &check.failures {
+                output.push_str(&format!("\n      - {failure}"));
+            }
+        }
+        let overall = if self.passed() { "PASS" } else { "FAIL" };
+        output.push_str(&format!("\nOverall: {overall}"));
+        output
+    }
+}
+
+pub fn run_health(levels_root: &Path) -> Result<()> {
+    let report = check_health(levels_root)?;
+    println!("{}", report.format());
+
+    if !report.passed() {
+        bail!("Catalog health check failed");
+    }
+    Ok(())
+}
+
+/// Runs levels.toml validation, geometry, reachability and playback verification
+pub fn check_health(levels_root: &Path) -> Result<HealthReport> {
+    let mut validation = HealthCheck::new("levels.toml validation");
+    // Geometry and reachability have their own checks below; don't report them twice.
+    let validation_report =
+        validate_levels_toml::validate_all_levels_toml_with_root(levels_root, false)
+            .without_level_analysis();
+    let difficulties = levels::catalog_difficulties(levels_root);
+    validation.checked = difficulties.len();
+    if !validation_report.is_empty() {
+        validation
+            .failures
+            .extend(validation_report.summary_lines());
+    }
+
+    let mut geometry = HealthCheck::new("geometry");
+    let mut reachability = HealthCheck::new("food/exit reachability");
+    let mut verification = HealthCheck::new("playback verification");
+
+    for difficulty in &difficulties {
+        let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
+        // Missing or unparsable levels.toml files are reported by the validation check.
+        let Ok(levels_toml) = levels::read_levels_toml(&levels_toml_path) else {
+            continue;
+        };
+
+        for entry in &levels_toml.level {
+            let Some(file) = entry.file.as_deref() else {
+                continue;
+            };
+            let level_path = levels_root.join(difficulty).join(file);
+            let Ok(level) = solver::load_level(&level_path) else {
+                continue;
+            };
+            let label = format!("{difficulty}/{file}");
+
+            geometry.checked += 1;
+            for issue in analysis::geometry_issues(&level) {
+                geometry.failures.push(format!("{label}: {issue}"));
+            }
+            if !analysis::snake_can_move(&level) {
+                geometry
+                    .failures
+                    .push(format!("{label}: snake has no legal opening move"));
+            }
+
+            reachability.checked += 1;
+            for target in analysis::unreachable_targets(&level) {
+                reachability.failures.push(format!(
+                    "{label}: target ({}, {}) is unreachable from the snake",
+                    target.x, target.y
+                ));
+            }
+
+            verification.checked += 1;
+            let playback_path = levels::infer_playback_path(levels_root, &level_path)?;
+            if !playback_path.exists() {
+                verification
+                    .failures
+                    .push(format!("{label}: playback not found"));
+                continue;
+            }
+            if let Err(error) = verify::verify_level(&level_path, &playback_path) {
+                verification.failures.push(format!("{label}: {error}"));
+            }
+        }
+    }
+
+    Ok(HealthReport {
+        checks: vec![validation, geometry, reachability, verification],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{write_levels_toml, LevelMeta, LevelsToml};
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_corridor_level(path: &Path, exit_x: i32) {
+        let level = json!({
+            "id": 1,
+            "name": "Corridor",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 2 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [
+                { "x": 0, "y": 1 }, { "x": 1, "y": 1 }, { "x": 2, "y": 1 },
+                { "x": 3, "y": 1 }, { "x": 4, "y": 1 }
+            ],
+            "food": [],
+            "exit": { "x": exit_x, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        });
+        fs::write(path, serde_json::to_string_pretty(&level).unwrap()).unwrap();
+    }
+
+    fn write_catalog(root: &Path, exit_x: i32, playback: &str) {
+        for difficulty in levels::DEFAULT_DIFFICULTIES {
+            fs::create_dir_all(root.join("levels").join(difficulty)).unwrap();
+            write_levels_toml(
+                &root.join("levels").join(difficulty).join("levels.toml"),
+                &LevelsToml { level: vec![] },
+            )
+            .unwrap();
+        }
+        let easy_dir = root.join("levels/easy");
+        write_corridor_level(&easy_dir.join("level.json"), exit_x);
+        write_levels_toml(
+            &easy_dir.join("levels.toml"),
+            &LevelsToml {
+                level: vec![LevelMeta {
+                    id: Some("level".to_string()),
+                    file: Some("level.json".to_string()),
+                    author: Some("gsnake".to_string()),
+                    solved: Some(true),
+                    difficulty: Some("easy".to_string()),
+                    tags: Some(vec![]),
+                    description: Some("Corridor".to_string()),
+                }],
+            },
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("playbacks/easy")).unwrap();
+        fs::write(root.join("playbacks/easy/level.json"), playback).unwrap();
+    }
+
+    #[test]
+    fn test_check_health_passes_for_healthy_catalog() {
+        let temp_dir = TempDir::new().unwrap();
+        let four_rights = r#"[{"key":"Right","delay_ms":1},{"key":"Right","delay_ms":1},{"key":"Right","delay_ms":1},{"key":"Right","delay_ms":1}]"#;
+        write_catalog(temp_dir.path(), 4, four_rights);
+
+        let report = check_health(&temp_dir.path().join("levels")).unwrap();
+        assert!(report.passed(), "{}", report.format());
+        assert_eq!(report.checks.len(), 4);
+        assert_eq!(report.checks[1].checked, 1);
+    }
+
+    #[test]
+    fn test_check_health_reports_each_failing_category() {
+        let temp_dir = TempDir::new().unwrap();
+        // Exit outside the grid breaks geometry, reachability and verification at once.
+        write_catalog(temp_dir.path(), 7, r#"[{"key":"Right","delay_ms":1}]"#);
+
+        let report = check_health(&temp_dir.path().join("levels")).unwrap();
+        assert!(!report.passed());
+        assert!(report.checks[0].passed());
+        assert_eq!(report.checks[1].failures.len(), 1);
+        assert!(report.checks[1].failures[0].contains("easy/level.json: exit position"));
+        assert_eq!(report.checks[2].failures.len(), 1);
+        assert_eq!(report.checks[3].failures.len(), 1);
+        assert!(report.format().ends_with("Overall: FAIL"));
+    }
+}
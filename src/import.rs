@@ -0,0 +1,189 @@
+use crate::{levels, toml_generator::generate_levels_toml};
+use anyhow::{bail, Context, Result};
+use gsnake_core::LevelDefinition;
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn run_import_levels_json(levels_root: &Path, input: &Path, force: bool) -> Result<()> {
+    let written = import_levels_json(levels_root, input, force)?;
+    for path in &written {
+        println!("  {}", path.display());
+    }
+    println!(
+        "Imported {} level(s) from {}",
+        written.len(),
+        input.display()
+    );
+    Ok(())
+}
+
+/// Splits an aggregated levels.json into `levels_root/<difficulty>/level_<id>.json` files and
+/// regenerates levels.toml for every difficulty that received a level.
+///
+/// Nothing is written when a level has an unknown difficulty, or when a target file exists and
+/// `force` is not set. Returns the written paths in input order.
+pub fn import_levels_json(levels_root: &Path, input: &Path, force: bool) -> Result<Vec<PathBuf>> {
+    let contents =
+        fs::read_to_string(input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let raw_levels: Vec<serde_json::Value> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {} as a JSON array", input.display()))?;
+
+    let known_difficulties = levels::catalog_difficulties(levels_root);
+    let mut targets = Vec::with_capacity(raw_levels.len());
+    for (index, raw_level) in raw_levels.into_iter().enumerate() {
+        let level: LevelDefinition =
+            serde_json::from_value(raw_level.clone()).with_context(|| {
+                format!("Failed to parse level at index {index} as LevelDefinition")
+            })?;
+        let Some(difficulty) = level.difficulty.as_deref().and_then(|difficulty| {
+            known_difficulties
+                .iter()
+                .find(|known| known.as_str() == difficulty)
+        }) else {
+            bail!(
+                "Level {} has unknown difficulty {:?}. Expected one of: {}",
+                level.id,
+                level.difficulty,
+                known_difficulties.join(", ")
+            );
+        };
+
+        let path = levels_root
+            .join(difficulty)
+            .join(format!("level_{}.json", level.id));
+        if path.exists() && !force {
+            bail!(
+                "Refusing to overwrite existing level file {} (use --force)",
+                path.display()
+            );
+        }
+        if targets.iter().any(|(existing, _, _)| *existing == path) {
+            bail!("Duplicate level id {} in {}", level.id, input.display());
+        }
+        targets.push((path, difficulty, raw_level));
+    }
+
+    let mut difficulties = BTreeSet::new();
+    let mut written = Vec::with_capacity(targets.len());
+    for (path, difficulty, raw_level) in targets {
+        let difficulty_dir = levels_root.join(difficulty);
+        fs::create_dir_all(&difficulty_dir)
+            .with_context(|| format!("Failed to create {}", difficulty_dir.display()))?;
+        fs::write(&path, serde_json::to_string_pretty(&raw_level)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        difficulties.insert(difficulty);
+        written.push(path);
+    }
+
+    for difficulty in difficulties {
+        generate_levels_toml(&levels_root.join(difficulty), difficulty)
+            .with_context(|| format!("Failed to regenerate levels.toml for {difficulty}"))?;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn level_json(id: u32, difficulty: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": format!("Imported {id}"),
+            "difficulty": difficulty,
+            "gridSize": { "width": 5, "height": 2 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 4, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        })
+    }
+
+    fn write_input(dir: &Path, levels: &[serde_json::Value]) -> PathBuf {
+        let input = dir.join("levels.json");
+        fs::write(&input, serde_json::to_string_pretty(levels).unwrap()).unwrap();
+        input
+    }
+
+    #[test]
+    fn test_import_splits_levels_and_regenerates_toml() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let input = write_input(
+            temp_dir.path(),
+            &[level_json(7, "easy"), level_json(12, "hard")],
+        );
+
+        let written = import_levels_json(&levels_root, &input, false)?;
+        assert_eq!(
+            written,
+            vec![
+                levels_root.join("easy/level_7.json"),
+                levels_root.join("hard/level_12.json")
+            ]
+        );
+
+        let contents = fs::read_to_string(&written[0])?;
+        assert!(contents.ends_with("}\n"));
+        let imported: serde_json::Value = serde_json::from_str(&contents)?;
+        assert_eq!(imported, level_json(7, "easy"));
+
+        let easy_toml = levels::read_levels_toml(&levels_root.join("easy/levels.toml"))?;
+        assert_eq!(easy_toml.level.len(), 1);
+        assert_eq!(easy_toml.level[0].file.as_deref(), Some("level_7.json"));
+        let hard_toml = levels::read_levels_toml(&levels_root.join("hard/levels.toml"))?;
+        assert_eq!(hard_toml.level[0].file.as_deref(), Some("level_12.json"));
+        assert!(!levels_root.join("medium").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_refuses_to_overwrite_without_force() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let input = write_input(temp_dir.path(), &[level_json(7, "easy")]);
+        import_levels_json(&levels_root, &input, false)?;
+
+        let error = import_levels_json(&levels_root, &input, false).unwrap_err();
+        assert!(error.to_string().contains("Refusing to overwrite"));
+        import_levels_json(&levels_root, &input, true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_difficulty_before_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        let input = write_input(
+            temp_dir.path(),
+            &[level_json(1, "easy"), level_json(2, "legendary")],
+        );
+
+        let error = import_levels_json(&levels_root, &input, false).unwrap_err();
+        assert!(error.to_string().contains("unknown difficulty"));
+        assert!(!levels_root.join("easy/level_1.json").exists());
+    }
+    #[test]
+    fn test_import_accepts_custom_difficulty_folder() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        fs::create_dir_all(levels_root.join("expert"))?;
+        fs::write(levels_root.join("expert/levels.toml"), "")?;
+        let input = write_input(temp_dir.path(), &[level_json(3, "expert")]);
+
+        let written = import_levels_json(&levels_root, &input, false)?;
+        assert_eq!(written, vec![levels_root.join("expert/level_3.json")]);
+        Ok(())
+    }
+}
@@ -7,6 +7,9 @@ use std::{
 
 pub const DEFAULT_DIFFICULTIES: [&str; 3] = ["easy", "medium", "hard"];
 
+/// Environment variable naming the levels directory when `--levels-root` is not given
+pub const LEVELS_ROOT_ENV: &str = "GSNAKE_LEVELS_ROOT";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LevelsToml {
     #[serde(default)]
@@ -53,12 +56,7 @@ pub fn update_solved_status(level_path: &Path, solved: bool) -> Result<()> {
         return Ok(());
     }
 
-    let output = toml::to_string_pretty(&levels_toml)
-        .with_context(|| format!("Failed to serialize {}", levels_toml_path.display()))?;
-    fs::write(&levels_toml_path, output)
-        .with_context(|| format!("Failed to write {}", levels_toml_path.display()))?;
-
-    Ok(())
+    write_toml_pretty(&levels_toml_path, &levels_toml)
 }
 
 pub fn levels_toml_path_for(level_path: &Path) -> PathBuf {
@@ -77,26 +75,213 @@ pub fn read_levels_toml(path: &Path) -> Result<LevelsToml> {
 }
 
 pub fn write_levels_toml(path: &Path, levels_toml: &LevelsToml) -> Result<()> {
-    let output = toml::to_string_pretty(levels_toml)
+    write_toml_pretty(path, levels_toml)
+}
+
+/// Writes `value` as pretty-printed JSON ending in exactly one newline
+pub fn write_json_pretty<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let output = serde_json::to_string_pretty(value)
+        .with_context(|| format!("Failed to serialize {}", path.display()))?;
+    write_with_single_newline(path, &output)
+}
+
+/// [`write_json_pretty`] that leaves the file (and its mtime) alone when the serialized
+/// bytes match `current`; returns whether it wrote
+pub fn write_json_pretty_if_changed<T: Serialize + ?Sized>(
+    path: &Path,
+    current: &str,
+    value: &T,
+) -> Result<bool> {
+    let output = serde_json::to_string_pretty(value)
         .with_context(|| format!("Failed to serialize {}", path.display()))?;
-    fs::write(path, output).with_context(|| format!("Failed to write {}", path.display()))?;
-    Ok(())
+    if format!("{}\n", output.trim_end_matches('\n')) == current {
+        return Ok(false);
+    }
+    write_with_single_newline(path, &output)?;
+    Ok(true)
+}
+
+/// Writes `value` as pretty-printed TOML ending in exactly one newline
+pub fn write_toml_pretty<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let output = toml::to_string_pretty(value)
+        .with_context(|| format!("Failed to serialize {}", path.display()))?;
+    write_with_single_newline(path, &output)
+}
+
+fn write_with_single_newline(path: &Path, output: &str) -> Result<()> {
+    fs::write(path, format!("{}\n", output.trim_end_matches('\n')))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Resolves the levels directory from `explicit` (the `--levels-root` flag), then
+/// `GSNAKE_LEVELS_ROOT`, then by looking for ./levels or ./gsnake-levels/levels in the
+/// current directory or its parents
+pub fn resolve_levels_root(explicit: Option<&Path>) -> Result<PathBuf> {
+    let configured = explicit.map(Path::to_path_buf).or_else(|| {
+        std::env::var_os(LEVELS_ROOT_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    });
+    match configured {
+        Some(root) if root.is_dir() => Ok(root),
+        Some(root) => bail!("Levels directory not found: {}", root.display()),
+        None => find_levels_root(),
+    }
+}
+
+/// Difficulty folders of a catalog: immediate subdirectories of `levels_root` that hold a
+/// levels.toml, default tiers first in their usual order and custom ones alphabetically
+/// after. Falls back to `DEFAULT_DIFFICULTIES` when none are found.
+pub fn discover_difficulties(levels_root: &Path) -> Vec<String> {
+    let mut discovered: Vec<String> = fs::read_dir(levels_root)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("levels.toml").is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    if discovered.is_empty() {
+        return DEFAULT_DIFFICULTIES.map(str::to_string).to_vec();
+    }
+
+    discovered.sort_by_key(|difficulty| {
+        let rank = DEFAULT_DIFFICULTIES
+            .iter()
+            .position(|known| known == difficulty)
+            .unwrap_or(DEFAULT_DIFFICULTIES.len());
+        (rank, difficulty.clone())
+    });
+    discovered
+}
+
+/// The default tiers followed by any discovered custom ones, for commands that must still
+/// handle a default tier whose levels.toml is missing or not generated yet
+pub fn catalog_difficulties(levels_root: &Path) -> Vec<String> {
+    let mut difficulties = DEFAULT_DIFFICULTIES.map(str::to_string).to_vec();
+    for difficulty in discover_difficulties(levels_root) {
+        if !difficulties.contains(&difficulty) {
+            difficulties.push(difficulty);
+        }
+    }
+    difficulties
+}
+
+/// Normalizes a `--difficulty` filter and checks it against the catalog's difficulties
+pub fn resolve_difficulty_filter(levels_root: &Path, raw: &str) -> Result<String> {
+    let normalized = raw.trim().to_ascii_lowercase();
+    if normalized.is_empty() {
+        bail!("Difficulty filter cannot be empty");
+    }
+
+    let known = catalog_difficulties(levels_root);
+    if known.contains(&normalized) {
+        return Ok(normalized);
+    }
+    bail!(
+        "Unknown difficulty '{}'. Expected one of: {}",
+        raw,
+        known.join(", ")
+    )
 }
 
+/// The `playbacks` directory next to `levels_root`, or `./playbacks` when the root has
+/// no parent
+pub fn playbacks_root(levels_root: &Path) -> PathBuf {
+    levels_root
+        .parent()
+        .map(|parent| parent.join("playbacks"))
+        .unwrap_or_else(|| PathBuf::from("playbacks"))
+}
+
+/// Mirrors a level path under `levels_root` into the playbacks directory
+pub fn infer_playback_path(levels_root: &Path, level_path: &Path) -> Result<PathBuf> {
+    let relative = level_path.strip_prefix(levels_root).with_context(|| {
+        format!(
+            "Level path {} is not under levels root {}",
+            level_path.display(),
+            levels_root.display()
+        )
+    })?;
+    let mut playback = playbacks_root(levels_root);
+    for component in relative.components() {
+        playback.push(component);
+    }
+    Ok(playback)
+}
+
+/// How many parent directories [`find_levels_root_from`] climbs before giving up
+const LEVELS_ROOT_SEARCH_PARENTS: usize = 4;
+
 pub fn find_levels_root() -> Result<PathBuf> {
     let cwd = std::env::current_dir().context("Failed to read current directory")?;
-    let direct = cwd.join("levels");
-    if direct.is_dir() {
-        return Ok(direct);
-    }
+    find_levels_root_from(&cwd)
+}
+
+/// Looks for `levels` or `gsnake-levels/levels` in `base`, then in up to
+/// `LEVELS_ROOT_SEARCH_PARENTS` of its ancestors, so commands work from any subdirectory of
+/// the repo
+pub fn find_levels_root_from(base: &Path) -> Result<PathBuf> {
+    for dir in base.ancestors().take(LEVELS_ROOT_SEARCH_PARENTS + 1) {
+        let direct = dir.join("levels");
+        if direct.is_dir() {
+            return Ok(direct);
+        }
 
-    let nested = cwd.join("gsnake-levels").join("levels");
-    if nested.is_dir() {
-        return Ok(nested);
+        let nested = dir.join("gsnake-levels").join("levels");
+        if nested.is_dir() {
+            return Ok(nested);
+        }
     }
 
     bail!(
-        "Could not find levels directory. Expected ./levels or ./gsnake-levels/levels from {}",
-        cwd.display()
+        "Could not find levels directory. Expected ./levels or ./gsnake-levels/levels in {} or \
+         its {LEVELS_ROOT_SEARCH_PARENTS} nearest parent directories",
+        base.display()
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_levels_root_from_package_root_and_monorepo_root() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let package_root = temp_dir.path().join("gsnake-levels");
+        fs::create_dir_all(package_root.join("levels/easy"))?;
+
+        assert_eq!(
+            find_levels_root_from(&package_root)?,
+            package_root.join("levels")
+        );
+        assert_eq!(
+            find_levels_root_from(temp_dir.path())?,
+            package_root.join("levels")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_levels_root_from_walks_up_from_nested_subdir() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let package_root = temp_dir.path().join("gsnake-levels");
+        let nested = package_root.join("src/a/b");
+        fs::create_dir_all(package_root.join("levels"))?;
+        fs::create_dir_all(&nested)?;
+
+        assert_eq!(find_levels_root_from(&nested)?, package_root.join("levels"));
+        assert_eq!(
+            find_levels_root_from(&package_root.join("levels/easy"))?,
+            package_root.join("levels")
+        );
+
+        let too_deep = package_root.join("src/a/b/c/d");
+        fs::create_dir_all(&too_deep)?;
+        let error = find_levels_root_from(&too_deep).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Could not find levels directory"));
+        Ok(())
+    }
+}
@@ -6,8 +6,12 @@ pub mod playback;
 pub mod playback_generator;
 pub mod solver;
 pub mod sync_metadata;
+pub mod sync_progress;
 #[cfg(test)]
-pub mod test_cwd;
+pub mod test_fixtures;
 pub mod toml_generator;
 pub mod validate_levels_toml;
 pub mod verify;
+
+pub use solver::solve_level_to_playback;
+pub use verify::{verify_run, VerifyOutcome};
@@ -0,0 +1,168 @@
+use crate::{levels, playback::load_playback_directions};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// One levels.toml entry with the move count of its stored playback
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LevelListing {
+    pub difficulty: String,
+    pub id: Option<String>,
+    /// The entry's levels.toml description
+    pub name: Option<String>,
+    pub solved: Option<bool>,
+    /// `None` when the playback is missing or cannot be loaded
+    pub moves: Option<usize>,
+}
+
+pub fn run_list_levels(levels_root: &Path, difficulty: Option<&str>, json: bool) -> Result<()> {
+    let listings = list_levels(levels_root, difficulty)?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&listings)
+                .with_context(|| "Failed to serialize level listing")?
+        );
+    } else {
+        println!("{}", format_listing_table(&listings));
+    }
+    Ok(())
+}
+
+/// Lists every levels.toml entry of `difficulty`, or of all difficulties, in toml order
+pub fn list_levels(levels_root: &Path, difficulty: Option<&str>) -> Result<Vec<LevelListing>> {
+    let difficulties = match difficulty {
+        Some(raw) => vec![levels::resolve_difficulty_filter(levels_root, raw)?],
+        None => levels::discover_difficulties(levels_root),
+    };
+
+    let mut listings = Vec::new();
+    for difficulty in difficulties {
+        let levels_toml_path = levels_root.join(&difficulty).join("levels.toml");
+        if !levels_toml_path.exists() {
+            continue;
+        }
+        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+
+        for entry in levels_toml.level {
+            let moves = entry.file.as_deref().and_then(|file| {
+                let level_path = levels_root.join(&difficulty).join(file);
+                let playback_path = levels::infer_playback_path(levels_root, &level_path).ok()?;
+                load_playback_directions(&playback_path)
+                    .ok()
+                    .map(|directions| directions.len())
+            });
+            listings.push(LevelListing {
+                difficulty: difficulty.clone(),
+                id: entry.id,
+                name: entry.description,
+                solved: entry.solved,
+                moves,
+            });
+        }
+    }
+    Ok(listings)
+}
+
+/// Aligned columns of difficulty, id, name, solved and moves; unknown values are left blank
+fn format_listing_table(listings: &[LevelListing]) -> String {
+    let rows: Vec<[String; 5]> = listings
+        .iter()
+        .map(|listing| {
+            [
+                listing.difficulty.clone(),
+                listing.id.clone().unwrap_or_default(),
+                listing.name.clone().unwrap_or_default(),
+                listing
+                    .solved
+                    .map(|solved| solved.to_string())
+                    .unwrap_or_default(),
+                listing
+                    .moves
+                    .map(|moves| moves.to_string())
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+    let header = ["DIFFICULTY", "ID", "NAME", "SOLVED", "MOVES"].map(str::to_string);
+
+    let mut widths = header.clone().map(|column| column.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    std::iter::once(&header)
+        .chain(&rows)
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{write_levels_toml, LevelMeta, LevelsToml};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn level_meta(file: &str, solved: Option<bool>) -> LevelMeta {
+        LevelMeta {
+            id: Some(file.trim_end_matches(".json").to_string()),
+            file: Some(file.to_string()),
+            author: None,
+            solved,
+            difficulty: Some("easy".to_string()),
+            tags: None,
+            description: Some(format!("Level {file}")),
+        }
+    }
+
+    #[test]
+    fn test_list_levels_reports_move_counts_and_blank_for_missing_playbacks() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        fs::create_dir_all(levels_root.join("easy"))?;
+        write_levels_toml(
+            &levels_root.join("easy/levels.toml"),
+            &LevelsToml {
+                level: vec![
+                    level_meta("a.json", Some(true)),
+                    level_meta("b.json", Some(false)),
+                ],
+            },
+        )?;
+        fs::create_dir_all(temp_dir.path().join("playbacks/easy"))?;
+        fs::write(
+            temp_dir.path().join("playbacks/easy/a.json"),
+            r#"[{"key":"Right","delay_ms":1},{"key":"Down","delay_ms":1},{"key":"Left","delay_ms":1}]"#,
+        )?;
+
+        let listings = list_levels(&levels_root, None)?;
+        assert_eq!(listings.len(), 2);
+        assert_eq!(listings[0].id.as_deref(), Some("a"));
+        assert_eq!(listings[0].name.as_deref(), Some("Level a.json"));
+        assert_eq!(listings[0].moves, Some(3));
+        assert_eq!(listings[1].solved, Some(false));
+        assert_eq!(listings[1].moves, None);
+
+        assert_eq!(
+            format_listing_table(&listings),
+            "DIFFICULTY  ID  NAME          SOLVED  MOVES\n\
+             easy        a   Level a.json  true    3\n\
+             easy        b   Level b.json  false"
+        );
+        let json = serde_json::to_value(&listings)?;
+        assert_eq!(json[1]["moves"], serde_json::Value::Null);
+        Ok(())
+    }
+}
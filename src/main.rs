@@ -1,19 +1,33 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 mod analysis;
+mod dedup;
 mod generate;
+mod headless;
+mod health;
+mod import;
 mod levels;
+mod list;
+mod manifest;
 mod migration;
 mod name_generator;
+mod normalize;
 mod playback;
+mod playback_diff;
 mod playback_generator;
 mod render;
+mod solutions;
 mod solver;
+mod stats;
+mod suggest;
+mod suspicious;
 mod sync_metadata;
+mod sync_progress;
 #[cfg(test)]
-mod test_cwd;
+mod test_fixtures;
 mod toml_generator;
 mod validate_levels_toml;
 mod verify;
@@ -22,6 +36,10 @@ mod verify_all;
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 struct Args {
+    /// Levels directory to operate on; overrides GSNAKE_LEVELS_ROOT and ./levels detection
+    #[arg(long, global = true)]
+    levels_root: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -36,6 +54,10 @@ enum Command {
         /// Optional explicit playback file path
         #[arg(long)]
         playback: Option<PathBuf>,
+
+        /// Fail if any snake segment leaves the grid after any move
+        #[arg(long)]
+        strict_bounds: bool,
     },
 
     /// Replay a level solution visually in the terminal
@@ -45,10 +67,44 @@ enum Command {
 
         /// Path to the playback JSON file
         playback: PathBuf,
+
+        /// Print each frame as text instead of launching gsnake-cli
+        #[arg(long)]
+        headless: bool,
+
+        /// Animate the replay in this terminal without gsnake-cli, honoring step delays
+        #[arg(long, conflicts_with = "headless")]
+        native: bool,
+
+        /// Glyph set for headless frames
+        #[arg(long, value_enum, default_value_t, requires = "headless")]
+        glyphs: headless::GlyphPreset,
+
+        /// Print the glyph legend before the headless frames
+        #[arg(long, requires = "headless")]
+        legend: bool,
+    },
+
+    /// Print the starting board of a level as text, without gsnake-cli
+    Preview {
+        /// Path to the level JSON file
+        level: PathBuf,
     },
 
     /// Verify all levels in all difficulty folders
-    VerifyAll,
+    VerifyAll {
+        /// Only verify this difficulty (easy, medium, hard, or a custom folder)
+        #[arg(long)]
+        difficulty: Option<String>,
+
+        /// Warn when a playback is longer than the solver's optimal solution
+        #[arg(long = "check-optimal")]
+        check_optimal: bool,
+
+        /// Write a JSON entry per level (difficulty, file, status, solved, error) to this path
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
 
     /// Aggregate levels into a single levels.json on stdout
     GenerateLevelsJson {
@@ -56,6 +112,10 @@ enum Command {
         #[arg(long)]
         filter: Option<String>,
 
+        /// Only include levels tagged with any of these levels.toml tags, e.g. "tutorial,boss"
+        #[arg(long)]
+        tags: Option<String>,
+
         /// Dry run: do not output JSON
         #[arg(long)]
         dry_run: bool,
@@ -63,54 +123,374 @@ enum Command {
         /// Disable automatic metadata sync before aggregation
         #[arg(long)]
         no_sync: bool,
+
+        /// Only include the first N levels (in levels.toml order)
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Apply --limit to each difficulty instead of overall
+        #[arg(long, requires = "limit")]
+        per_difficulty: bool,
+
+        /// Levels root to aggregate; repeat to layer packs, later roots override by id
+        #[arg(long = "root")]
+        roots: Vec<PathBuf>,
+
+        /// Fail on duplicate level ids across --root directories instead of overriding
+        #[arg(long, requires = "roots")]
+        no_override: bool,
+
+        /// Emit compact JSON instead of pretty-printed
+        #[arg(long)]
+        minify: bool,
+
+        /// Write the JSON to this file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Gzip-compress the --output file, appending .gz to its name if missing
+        #[arg(long, requires = "output")]
+        gzip: bool,
+
+        /// Also write a manifest with the level list and SHA-256 of the emitted JSON
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+
+        /// Fail if any included level is not marked solved in levels.toml
+        #[arg(long)]
+        require_solved: bool,
+
+        /// Report every level that fails to load instead of stopping at the first
+        #[arg(long)]
+        collect_errors: bool,
+    },
+
+    /// Split an aggregated levels.json into per-difficulty level files
+    ImportLevelsJson {
+        /// Path to the levels.json array to import
+        input: PathBuf,
+
+        /// Overwrite level files that already exist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check that a generated levels.json matches the source catalog
+    VerifyAggregate {
+        /// Path to the levels.json to check
+        levels_json: PathBuf,
     },
 
-    /// Render asciinema and SVG documentation
+    /// Render asciinema and SVG or GIF documentation
     Render {
         /// Path to the level JSON file
         level: PathBuf,
 
         /// Path to the playback JSON file
         playback: PathBuf,
+
+        /// Playback speed multiplier; step delays are divided by it (2.0 = twice as fast)
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+
+        /// Output format; svg uses svg-term, gif uses agg
+        #[arg(long, value_enum, default_value_t)]
+        format: render::RenderFormat,
     },
 
-    /// Sync level metadata (names, levels.toml, playbacks)
+    /// Sync level metadata (names, levels.toml, playbacks), keeping curated levels.toml
+    /// author, tags and descriptions
     SyncMetadata {
-        /// Optional difficulty filter (easy, medium, or hard)
+        /// Only sync this difficulty (easy, medium, hard, or a custom folder)
         #[arg(long)]
         difficulty: Option<String>,
+
+        /// Ignore the checkpoint of an interrupted run and regenerate every playback
+        #[arg(long = "no-resume")]
+        no_resume: bool,
+
+        /// Warn when more than this fraction of a difficulty's levels get generic names
+        #[arg(long = "generic-name-threshold", default_value_t = name_generator::DEFAULT_GENERIC_NAME_THRESHOLD)]
+        generic_name_threshold: f64,
+
+        /// Keep hand-written level names; only unnamed or auto-named levels get new names
+        #[arg(long = "keep-names")]
+        keep_names: bool,
+
+        /// Seed for reproducible adjective picks when generated names collide
+        #[arg(long = "name-seed")]
+        name_seed: Option<u64>,
+
+        /// Replay each generated playback and mark levels whose playback fails as unsolved
+        #[arg(long = "self-check")]
+        self_check: bool,
+
+        /// Re-solve every level, even when its playback is newer than the level JSON
+        #[arg(long)]
+        force: bool,
+
+        /// Print a "[difficulty n/total]" line per level while generating playbacks
+        #[arg(long)]
+        progress: bool,
+
+        /// Solve at most this many levels at once (default: available parallelism)
+        #[arg(long)]
+        jobs: Option<NonZeroUsize>,
     },
 
     /// Validate levels.toml files for all difficulties
-    ValidateLevelsToml,
+    ValidateLevelsToml {
+        /// Rewrite level JSON difficulty fields that disagree with their folder
+        #[arg(long)]
+        fix: bool,
+
+        /// Also require a non-empty playback for every level marked solved
+        #[arg(long = "with-playbacks")]
+        with_playbacks: bool,
+
+        /// Print nothing when validation passes; failures are still reported on stderr
+        #[arg(long, short)]
+        quiet: bool,
+    },
+
+    /// Print a JSON manifest of every level and its playback/render artifacts
+    Manifest,
+
+    /// Run validation, geometry, reachability and verification checks together
+    Health,
+
+    /// Print the mechanics, obstacle pattern and complexity analysis of a level
+    Analyze {
+        /// Path to the level JSON file
+        level: PathBuf,
+
+        /// Print the serialized analysis as JSON instead of a report
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Print the serialized analysis in this format instead of a report
+        #[arg(long, value_enum)]
+        format: Option<stats::OutputFormat>,
+    },
+
+    /// Print the analysis of every level in the catalog
+    Stats {
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: stats::OutputFormat,
+
+        /// Only include levels with this kind of symmetry
+        #[arg(long, value_enum)]
+        symmetry: Option<stats::SymmetryFilter>,
+    },
+
+    /// Report playbacks with identical move sequences
+    DedupPlaybacks {
+        /// Replace duplicates with relative symlinks to the first playback in each group with
+        /// the same step delays
+        #[arg(long)]
+        link: bool,
+    },
+
+    /// Report levels whose layouts are rotations or mirror images of each other
+    DuplicateLevels,
+
+    /// Remove duplicate obstacle, food, stone and spike positions from level files
+    NormalizeLevels,
+
+    /// List every levels.toml entry with its solved flag and playback move count
+    ListLevels {
+        /// Only list this difficulty (easy, medium, hard, or a custom folder)
+        #[arg(long)]
+        difficulty: Option<String>,
+
+        /// Print a JSON array instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare each level's declared difficulty with one suggested by its analysis metrics
+    SuggestDifficulty {
+        /// Also solve each level (up to this depth) so solution length weighs in
+        #[arg(long = "solve-depth")]
+        solve_depth: Option<usize>,
+    },
+
+    /// List solved levels whose solutions look like they exploit game quirks
+    Suspicious {
+        /// Maximum search depth for solver
+        #[arg(long, default_value = "500")]
+        max_depth: usize,
+    },
+
+    /// Count a level's shortest solutions and print the first few
+    Solutions {
+        /// Path to the level JSON file
+        level: PathBuf,
+
+        /// How many of the optimal solutions to print
+        #[arg(long, default_value = "5")]
+        limit: usize,
+
+        /// Maximum search depth for solver
+        #[arg(long, default_value = "500")]
+        max_depth: usize,
+    },
+
+    /// Show where two playbacks of the same level diverge
+    DiffPlayback {
+        /// First playback JSON file
+        a: PathBuf,
+
+        /// Second playback JSON file
+        b: PathBuf,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let levels_root = || levels::resolve_levels_root(args.levels_root.as_deref());
 
     match args.command {
-        Command::Verify { level, playback } => {
+        Command::Verify {
+            level,
+            playback,
+            strict_bounds,
+        } => {
             let playback_path = verify::resolve_playback_path(&level, playback)
                 .with_context(|| "Failed to resolve playback path")?;
-            let result = verify::verify_level(&level, &playback_path);
+            let options = verify::VerifyOptions { strict_bounds };
+            let result =
+                verify::verify_level_detailed_with_options(&level, &playback_path, options);
+            if let Ok(outcome) = &result {
+                if outcome.already_complete {
+                    eprintln!(
+                        "Note: level {} is already complete before any move; skipping its playback",
+                        level.display()
+                    );
+                }
+            }
             let solved = result.is_ok();
             levels::update_solved_status(&level, solved)
                 .with_context(|| "Failed to update levels.toml metadata")?;
-            result
+            result.map(|_| ())
+        },
+        Command::Replay {
+            level,
+            playback,
+            headless,
+            native,
+            glyphs,
+            legend,
+        } => {
+            if headless {
+                headless::run_headless_replay(&level, &playback, &glyphs.glyphs(), legend)
+            } else if native {
+                render::run_replay_native(&level, &playback)
+            } else {
+                render::run_replay(&level, &playback)
+            }
+        },
+        Command::Preview { level } => headless::run_preview(&level),
+        Command::VerifyAll {
+            difficulty,
+            check_optimal,
+            report,
+        } => {
+            let summary = verify_all::run_verify_all(
+                &levels_root()?,
+                difficulty.as_deref(),
+                check_optimal,
+                report.as_deref(),
+            )?;
+            println!("Verify-all: {summary}");
+            Ok(())
         },
-        Command::Replay { level, playback } => render::run_replay(&level, &playback),
-        Command::VerifyAll => verify_all::run_verify_all(),
         Command::GenerateLevelsJson {
             filter,
+            tags,
             dry_run,
             no_sync,
+            limit,
+            per_difficulty,
+            roots,
+            no_override,
+            minify,
+            output,
+            gzip,
+            manifest,
+            require_solved,
+            collect_errors,
         } => {
             let sync = !no_sync;
-            generate::run_generate_levels_json(filter.as_deref(), dry_run, sync)
+            let limit = limit.map(|count| generate::AggregateLimit {
+                count,
+                per_difficulty,
+            });
+            let levels_roots = if roots.is_empty() {
+                vec![levels_root()?]
+            } else {
+                roots
+            };
+            generate::run_generate_levels_json(
+                &levels_roots,
+                generate::AggregateFilter {
+                    difficulties: filter.as_deref(),
+                    tags: tags.as_deref(),
+                },
+                sync,
+                limit,
+                !no_override,
+                generate::AggregateChecks {
+                    require_solved,
+                    collect_errors,
+                },
+                generate::AggregateOutput {
+                    dry_run,
+                    minify,
+                    path: output.as_deref(),
+                    gzip,
+                    manifest: manifest.as_deref(),
+                },
+            )
         },
-        Command::Render { level, playback } => render::run_render(&level, &playback),
-        Command::SyncMetadata { difficulty } => {
-            let summary = sync_metadata::sync_metadata(difficulty.as_deref())?;
+        Command::ImportLevelsJson { input, force } => {
+            import::run_import_levels_json(&levels_root()?, &input, force)
+        },
+        Command::VerifyAggregate { levels_json } => {
+            generate::run_verify_aggregate(&levels_root()?, &levels_json)
+        },
+        Command::Render {
+            level,
+            playback,
+            speed,
+            format,
+        } => render::run_render(&level, &playback, speed, format),
+        Command::SyncMetadata {
+            difficulty,
+            no_resume,
+            generic_name_threshold,
+            keep_names,
+            name_seed,
+            self_check,
+            force,
+            progress,
+            jobs,
+        } => {
+            let summary = sync_metadata::sync_metadata(
+                &levels_root()?,
+                difficulty.as_deref(),
+                sync_metadata::SyncOptions {
+                    resume: !no_resume,
+                    generic_name_threshold,
+                    keep_names,
+                    name_seed,
+                    self_check,
+                    force,
+                    show_progress: progress,
+                    jobs,
+                },
+            )?;
             println!("\nSync completed successfully:");
             println!("  - Generated {} names", summary.names_generated);
             println!(
@@ -118,8 +498,54 @@ fn main() -> Result<()> {
                 summary.toml_files_updated
             );
             println!("  - Created {} playbacks", summary.playbacks_created);
+            let unsolved = summary.unsolved();
+            if !unsolved.is_empty() {
+                println!("\nUnsolved levels ({}):", unsolved.len());
+                for level in unsolved {
+                    println!("  - {level}");
+                }
+            }
             Ok(())
         },
-        Command::ValidateLevelsToml => validate_levels_toml::run_validate_levels_toml(),
+        Command::ValidateLevelsToml {
+            fix,
+            with_playbacks,
+            quiet,
+        } => {
+            let report = validate_levels_toml::run_validate_levels_toml(
+                &levels_root()?,
+                fix,
+                with_playbacks,
+                quiet,
+            );
+            if !report.is_empty() {
+                std::process::exit(report.exit_code());
+            }
+            Ok(())
+        },
+        Command::Manifest => manifest::run_manifest(&levels_root()?),
+        Command::Health => health::run_health(&levels_root()?),
+        Command::Analyze {
+            level,
+            json,
+            format,
+        } => stats::run_analyze(&level, json.then_some(stats::OutputFormat::Json).or(format)),
+        Command::Stats { format, symmetry } => stats::run_stats(&levels_root()?, format, symmetry),
+        Command::DedupPlaybacks { link } => dedup::run_dedup_playbacks(&levels_root()?, link),
+        Command::DuplicateLevels => dedup::run_duplicate_levels(&levels_root()?),
+        Command::NormalizeLevels => normalize::run_normalize_levels(&levels_root()?),
+        Command::ListLevels { difficulty, json } => {
+            list::run_list_levels(&levels_root()?, difficulty.as_deref(), json)
+        },
+        Command::SuggestDifficulty { solve_depth } => {
+            suggest::run_suggest_difficulty(&levels_root()?, solve_depth)
+        },
+        Command::Suspicious { max_depth } => suspicious::run_suspicious(&levels_root()?, max_depth),
+        Command::Solutions {
+            level,
+            limit,
+            max_depth,
+        } => solutions::run_solutions(&level, max_depth, limit),
+        Command::DiffPlayback { a, b } => playback_diff::run_diff_playback(&a, &b),
     }
 }
@@ -0,0 +1,138 @@
+use crate::{levels, render};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Deployment view of a single level and its derived artifacts
+#[derive(Debug, Serialize)]
+pub struct ManifestEntry {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub difficulty: String,
+    pub solved: Option<bool>,
+    pub level_path: PathBuf,
+    pub playback_path: PathBuf,
+    pub playback_exists: bool,
+    pub render_path: PathBuf,
+    pub render_exists: bool,
+}
+
+pub fn run_manifest(levels_root: &Path) -> Result<()> {
+    let entries = build_manifest(levels_root)?;
+    let output = serde_json::to_string_pretty(&entries)
+        .with_context(|| "Failed to serialize artifact manifest")?;
+    println!("{output}");
+    Ok(())
+}
+
+/// Collects every level listed in a levels.toml together with its playback and render paths
+pub fn build_manifest(levels_root: &Path) -> Result<Vec<ManifestEntry>> {
+    let levels_root = levels_root.to_path_buf();
+    let mut entries = Vec::new();
+
+    for difficulty in &levels::catalog_difficulties(&levels_root) {
+        let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
+        if !levels_toml_path.exists() {
+            continue;
+        }
+
+        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+        for entry in levels_toml.level {
+            let Some(file) = entry.file.as_deref() else {
+                continue;
+            };
+            let level_path = levels_root.join(difficulty).join(file);
+            let playback_path = levels::infer_playback_path(&levels_root, &level_path)?;
+            let render_path = render::infer_render_path(&playback_path, "svg")?;
+
+            entries.push(ManifestEntry {
+                id: entry.id.clone(),
+                name: entry.description.clone(),
+                difficulty: entry
+                    .difficulty
+                    .as_deref()
+                    .unwrap_or(difficulty)
+                    .to_string(),
+                solved: entry.solved,
+                playback_exists: playback_path.exists(),
+                render_exists: render_path.exists(),
+                level_path,
+                playback_path,
+                render_path,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{write_levels_toml, LevelMeta, LevelsToml};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn level_meta(file: &str, solved: bool) -> LevelMeta {
+        LevelMeta {
+            id: Some(file.trim_end_matches(".json").to_string()),
+            file: Some(file.to_string()),
+            author: Some("gsnake".to_string()),
+            solved: Some(solved),
+            difficulty: Some("easy".to_string()),
+            tags: Some(vec![]),
+            description: Some(format!("Level {file}")),
+        }
+    }
+
+    #[test]
+    fn test_build_manifest_reports_artifact_existence() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let easy_dir = levels_root.join("easy");
+        let playbacks_dir = temp_dir.path().join("playbacks/easy");
+        let renders_dir = temp_dir.path().join("renders/easy");
+        fs::create_dir_all(&easy_dir)?;
+        fs::create_dir_all(&playbacks_dir)?;
+        fs::create_dir_all(&renders_dir)?;
+
+        fs::write(easy_dir.join("a.json"), "{}")?;
+        fs::write(easy_dir.join("b.json"), "{}")?;
+        fs::write(playbacks_dir.join("a.json"), "[]")?;
+        fs::write(renders_dir.join("a.svg"), "<svg/>")?;
+        write_levels_toml(
+            &easy_dir.join("levels.toml"),
+            &LevelsToml {
+                level: vec![level_meta("a.json", true), level_meta("b.json", false)],
+            },
+        )?;
+
+        let manifest = build_manifest(&levels_root)?;
+        assert_eq!(manifest.len(), 2);
+
+        assert_eq!(manifest[0].id.as_deref(), Some("a"));
+        assert_eq!(manifest[0].name.as_deref(), Some("Level a.json"));
+        assert_eq!(manifest[0].difficulty, "easy");
+        assert_eq!(manifest[0].solved, Some(true));
+        assert_eq!(manifest[0].playback_path, playbacks_dir.join("a.json"));
+        assert!(manifest[0].playback_exists);
+        assert_eq!(manifest[0].render_path, renders_dir.join("a.svg"));
+        assert!(manifest[0].render_exists);
+
+        assert_eq!(manifest[1].solved, Some(false));
+        assert!(!manifest[1].playback_exists);
+        assert!(!manifest[1].render_exists);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_manifest_skips_missing_difficulties() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        fs::create_dir_all(&levels_root)?;
+
+        let manifest = build_manifest(&levels_root)?;
+        assert!(manifest.is_empty());
+        Ok(())
+    }
+}
@@ -1,7 +1,36 @@
-use anyhow::{anyhow, Context, Result};
+use crate::analysis::geometry_issues;
+use crate::levels::catalog_difficulties;
+use anyhow::{anyhow, bail, Context, Result};
+use gsnake_core::models::LevelDefinition;
 use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// One level whose string ID was replaced by a numeric one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigratedId {
+    pub path: PathBuf,
+    pub old_id: String,
+    pub new_id: u32,
+}
+
+/// How [`migrate_level_id_with_options`] treats the file on disk
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MigrationOptions {
+    /// Compute and validate the migrated JSON without writing anything
+    pub dry_run: bool,
+    /// Copy the original to `<file>.bak` before rewriting it
+    pub backup: bool,
+    /// Also require sane geometry (on-grid positions, contiguous snake) before writing
+    pub strict: bool,
+}
+
+/// Result of [`migrate_all_level_ids`]
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub migrated: Vec<MigratedId>,
+}
 
 /// Parses a string-based level ID and extracts the numeric timestamp portion.
 ///
@@ -21,6 +50,23 @@ use std::path::Path;
 /// * Timestamp exceeds u32::MAX (4,294,967,295)
 #[allow(dead_code)] // Will be used in US-002
 pub fn parse_string_id(id: &str) -> Result<u32> {
+    let timestamp_u64 = parse_string_id_u64(id)?;
+
+    // Check if it fits in u32 range
+    if timestamp_u64 > u32::MAX as u64 {
+        return Err(anyhow!(
+            "Timestamp {} exceeds u32::MAX ({})",
+            timestamp_u64,
+            u32::MAX
+        ));
+    }
+
+    Ok(timestamp_u64 as u32)
+}
+
+/// Like [`parse_string_id`], but keeps the full timestamp, so real 13-digit
+/// millisecond timestamps such as "1769977122223-g36bwe" parse too.
+pub fn parse_string_id_u64(id: &str) -> Result<u64> {
     // Split on hyphen
     let parts: Vec<&str> = id.split('-').collect();
 
@@ -32,25 +78,132 @@ pub fn parse_string_id(id: &str) -> Result<u32> {
     }
 
     let timestamp_str = parts[0];
-
-    // Parse as u64 first to check if it exceeds u32 range
-    let timestamp_u64: u64 = timestamp_str.parse().map_err(|_| {
+    timestamp_str.parse().map_err(|_| {
         anyhow!(
             "Invalid timestamp: '{}' is not a valid number",
             timestamp_str
         )
-    })?;
+    })
+}
 
-    // Check if it fits in u32 range
-    if timestamp_u64 > u32::MAX as u64 {
-        return Err(anyhow!(
-            "Timestamp {} exceeds u32::MAX ({})",
-            timestamp_u64,
-            u32::MAX
-        ));
+/// Migrates every level under `levels_root/<difficulty>/` whose `id` is a string to a
+/// numeric ID that is unique across all difficulties.
+///
+/// A string ID whose timestamp fits in u32 keeps it as the new ID; any other string ID
+/// gets the next free number after the highest ID in the catalog. `options` applies to
+/// every file as in [`migrate_level_id_with_options`]. Nothing is written when an ID would
+/// collide with an existing numeric ID or another migrated level, or when any migrated level
+/// fails validation.
+///
+/// # Errors
+/// * A difficulty directory or level file cannot be read or parsed
+/// * A level's `id` is neither a number nor a string
+/// * Two levels would end up with the same numeric ID
+/// * A migrated level fails validation, including the geometry checks in strict mode
+#[allow(dead_code)]
+pub fn migrate_all_level_ids(
+    levels_root: &Path,
+    options: MigrationOptions,
+) -> Result<MigrationReport> {
+    let mut numeric: BTreeMap<u64, PathBuf> = BTreeMap::new();
+    let mut pending: Vec<(PathBuf, String)> = Vec::new();
+
+    for difficulty in &catalog_difficulties(levels_root) {
+        let difficulty_dir = levels_root.join(difficulty);
+        if !difficulty_dir.exists() {
+            continue;
+        }
+        let mut level_paths: Vec<PathBuf> = fs::read_dir(&difficulty_dir)
+            .with_context(|| format!("Failed to read {}", difficulty_dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .collect();
+        level_paths.sort();
+
+        for path in level_paths {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let level: Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            match level.get("id") {
+                Some(Value::Number(id)) => {
+                    let id = id
+                        .as_u64()
+                        .ok_or_else(|| anyhow!("Invalid numeric id in {}", path.display()))?;
+                    if let Some(other) = numeric.insert(id, path.clone()) {
+                        bail!(
+                            "Level id {} is already used by both {} and {}",
+                            id,
+                            other.display(),
+                            path.display()
+                        );
+                    }
+                },
+                Some(Value::String(id)) => pending.push((path, id.clone())),
+                _ => bail!("Level {} has no string or numeric id", path.display()),
+            }
+        }
     }
 
-    Ok(timestamp_u64 as u32)
+    // Timestamps that fit keep their value; collect them first so sequential IDs start above them.
+    let mut assigned: Vec<(PathBuf, String, Option<u32>)> = Vec::with_capacity(pending.len());
+    let mut taken: HashSet<u64> = numeric.keys().copied().collect();
+    for (path, old_id) in pending {
+        let timestamp = parse_string_id_u64(&old_id)
+            .ok()
+            .and_then(|timestamp| u32::try_from(timestamp).ok());
+        if let Some(new_id) = timestamp {
+            if !taken.insert(u64::from(new_id)) {
+                let holder = numeric
+                    .get(&u64::from(new_id))
+                    .map(|holder| holder.display().to_string())
+                    .unwrap_or_else(|| "another migrated level".to_string());
+                bail!(
+                    "Migrating {} ('{}') to id {} would collide with {}",
+                    path.display(),
+                    old_id,
+                    new_id,
+                    holder
+                );
+            }
+        }
+        assigned.push((path, old_id, timestamp));
+    }
+
+    let mut next_id = taken.iter().max().map_or(1, |max| max + 1);
+    let mut report = MigrationReport::default();
+    for (path, old_id, timestamp) in assigned {
+        let new_id = match timestamp {
+            Some(new_id) => new_id,
+            None => {
+                let new_id = u32::try_from(next_id).map_err(|_| {
+                    anyhow!("Ran out of u32 ids while migrating {}", path.display())
+                })?;
+                next_id += 1;
+                new_id
+            },
+        };
+        report.migrated.push(MigratedId {
+            path,
+            old_id,
+            new_id,
+        });
+    }
+
+    // Validate every level before writing any, so a bad one leaves the catalog untouched.
+    let plan = MigrationOptions {
+        dry_run: true,
+        ..options
+    };
+    for migrated in &report.migrated {
+        migrate_level_id_with_options(&migrated.path, migrated.new_id, plan)?;
+    }
+    if !options.dry_run {
+        for migrated in &report.migrated {
+            migrate_level_id_with_options(&migrated.path, migrated.new_id, options)?;
+        }
+    }
+    Ok(report)
 }
 
 /// Migrates a level JSON file from string ID to numeric ID.
@@ -72,6 +225,38 @@ pub fn parse_string_id(id: &str) -> Result<u32> {
 /// * Updated level fails LevelDefinition validation
 #[allow(dead_code)] // Will be used in US-009
 pub fn migrate_level_id<P: AsRef<Path>>(level_path: P, new_id: u32) -> Result<()> {
+    migrate_level_id_with_options(level_path, new_id, MigrationOptions::default())?;
+    Ok(())
+}
+
+/// Returns the JSON [`migrate_level_id`] would write, without touching the file.
+///
+/// The result is validated as a LevelDefinition in memory, so validation failures
+/// show up before anything is written. Note the output is re-pretty-printed, so
+/// whitespace may differ from the original even apart from the `id`.
+#[allow(dead_code)]
+pub fn plan_migration<P: AsRef<Path>>(level_path: P, new_id: u32) -> Result<String> {
+    migrate_level_id_with_options(
+        level_path,
+        new_id,
+        MigrationOptions {
+            dry_run: true,
+            ..MigrationOptions::default()
+        },
+    )
+}
+
+/// Migrates a level JSON file from string ID to numeric ID, optionally as a dry run
+/// or with a `.bak` copy of the original.
+///
+/// # Returns
+/// * `Ok(String)` - The migrated JSON, as written (or as it would be written on a dry run)
+/// * `Err` - If reading, validation, backup or writing fails
+pub fn migrate_level_id_with_options<P: AsRef<Path>>(
+    level_path: P,
+    new_id: u32,
+    options: MigrationOptions,
+) -> Result<String> {
     let path = level_path.as_ref();
 
     // Read the level file
@@ -99,49 +284,54 @@ pub fn migrate_level_id<P: AsRef<Path>>(level_path: P, new_id: u32) -> Result<()
             "Migration step 'serialize migrated level' failed for {}",
             path.display()
         )
-    })?;
+    })? + "\n";
 
-    // Write back to file
-    fs::write(path, updated_json + "\n").with_context(|| {
+    // Everything is validated in memory, so a level that fails is never written.
+    validate_level_content(&updated_json, path).with_context(|| {
         format!(
-            "Migration step 'write migrated level' failed for {}",
+            "Migration step 'validate migrated level' failed for {}",
             path.display()
         )
     })?;
+    if options.strict {
+        validate_level_geometry(&updated_json, path)?;
+    }
+    if options.dry_run {
+        return Ok(updated_json);
+    }
+
+    if options.backup {
+        let backup_path = backup_path(path);
+        fs::write(&backup_path, &content).with_context(|| {
+            format!(
+                "Migration step 'write backup' failed for {}",
+                backup_path.display()
+            )
+        })?;
+    }
 
-    // Validate the updated file can be parsed as LevelDefinition
-    validate_level_file(path).with_context(|| {
+    fs::write(path, &updated_json).with_context(|| {
         format!(
-            "Migration step 'validate migrated level' failed for {}",
+            "Migration step 'write migrated level' failed for {}",
             path.display()
         )
     })?;
 
-    Ok(())
+    Ok(updated_json)
 }
 
-/// Validates that a level JSON file can be parsed as gsnake-core's LevelDefinition.
-///
-/// This ensures the migrated level is compatible with the game engine.
-///
-/// # Arguments
-/// * `level_path` - Path to the level JSON file to validate
-///
-/// # Returns
-/// * `Ok(())` - If level parses successfully
-/// * `Err` - If parsing fails
-fn validate_level_file<P: AsRef<Path>>(level_path: P) -> Result<()> {
-    let path = level_path.as_ref();
-    let content = fs::read_to_string(path).with_context(|| {
-        format!(
-            "Validation step 'read migrated level' failed for {}",
-            path.display()
-        )
-    })?;
+/// `<file>.bak` next to `path`, e.g. `level_001.json.bak`
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
 
+/// Validates level JSON held in memory; `path` is only used in error messages.
+fn validate_level_content(content: &str, path: &Path) -> Result<()> {
     // Parse as LevelDefinition to validate structure
     let _: gsnake_core::models::LevelDefinition =
-        serde_json::from_str(&content).with_context(|| {
+        serde_json::from_str(content).with_context(|| {
             format!(
                 "Validation step 'parse LevelDefinition' failed for {}",
                 path.display()
@@ -151,6 +341,25 @@ fn validate_level_file<P: AsRef<Path>>(level_path: P) -> Result<()> {
     Ok(())
 }
 
+/// Checks geometry with [`geometry_issues`]; `content` must already parse as a LevelDefinition.
+fn validate_level_geometry(content: &str, path: &Path) -> Result<()> {
+    let level: LevelDefinition = serde_json::from_str(content).with_context(|| {
+        format!(
+            "Validation step 'parse LevelDefinition' failed for {}",
+            path.display()
+        )
+    })?;
+    let issues = geometry_issues(&level);
+    if !issues.is_empty() {
+        bail!(
+            "Validation step 'check geometry' failed for {}: {}",
+            path.display(),
+            issues.join("; ")
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,6 +586,191 @@ mod tests {
         Ok(())
     }
 
+    fn write_level_with_id(dir: &Path, file: &str, id: Value) -> Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let level = serde_json::json!({
+            "id": id,
+            "name": file,
+            "difficulty": "easy",
+            "gridSize": { "width": 10, "height": 10 },
+            "snake": [{ "x": 5, "y": 5 }],
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 8, "y": 8 },
+            "snakeDirection": "East"
+        });
+        let path = dir.join(file);
+        fs::write(&path, serde_json::to_string_pretty(&level)?)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn test_parse_string_id_u64_accepts_real_timestamps() -> Result<()> {
+        assert_eq!(parse_string_id_u64("1769977122223-g36bwe")?, 1769977122223);
+        assert!(parse_string_id_u64("no-timestamp-here").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_all_level_ids_assigns_unique_ids_across_difficulties() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        write_level_with_id(&root.join("easy"), "a.json", Value::from(7))?;
+        let easy_string = write_level_with_id(
+            &root.join("easy"),
+            "b.json",
+            Value::from("1769977122223-g36bwe"),
+        )?;
+        let medium_small =
+            write_level_with_id(&root.join("medium"), "c.json", Value::from("12-abc"))?;
+        let hard_string = write_level_with_id(
+            &root.join("hard"),
+            "d.json",
+            Value::from("1769978263873-eupaj5"),
+        )?;
+
+        let report = migrate_all_level_ids(root, MigrationOptions::default())?;
+        assert_eq!(
+            report.migrated,
+            vec![
+                MigratedId {
+                    path: easy_string.clone(),
+                    old_id: "1769977122223-g36bwe".to_string(),
+                    new_id: 13,
+                },
+                MigratedId {
+                    path: medium_small,
+                    old_id: "12-abc".to_string(),
+                    new_id: 12,
+                },
+                MigratedId {
+                    path: hard_string,
+                    old_id: "1769978263873-eupaj5".to_string(),
+                    new_id: 14,
+                },
+            ]
+        );
+
+        let migrated: Value = serde_json::from_str(&fs::read_to_string(&easy_string)?)?;
+        assert_eq!(migrated["id"], 13);
+        assert!(migrate_all_level_ids(root, MigrationOptions::default())?
+            .migrated
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_all_level_ids_refuses_collision_without_writing() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        write_level_with_id(&root.join("easy"), "numeric.json", Value::from(12345))?;
+        let string_level =
+            write_level_with_id(&root.join("hard"), "string.json", Value::from("12345-abc"))?;
+        let before = fs::read_to_string(&string_level)?;
+
+        let err = migrate_all_level_ids(root, MigrationOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("would collide with"), "{err}");
+        assert!(err.to_string().contains("numeric.json"), "{err}");
+        assert_eq!(fs::read_to_string(&string_level)?, before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_migration_leaves_file_untouched() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let path = write_level_with_id(temp_dir.path(), "level.json", Value::from("12-abc"))?;
+        let before = fs::read(&path)?;
+
+        let planned = plan_migration(&path, 5)?;
+        let planned_level: Value = serde_json::from_str(&planned)?;
+        assert_eq!(planned_level["id"], 5);
+        assert_eq!(fs::read(&path)?, before);
+        assert!(!backup_path(&path).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_migration_validates_in_memory() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let path = temp_dir.path().join("invalid.json");
+        fs::write(&path, r#"{"id": "1234-test", "name": "Invalid Level"}"#)?;
+
+        let err_chain = format!("{:#}", plan_migration(&path, 1).unwrap_err());
+        assert!(err_chain.contains("Migration step 'validate migrated level' failed"));
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            r#"{"id": "1234-test", "name": "Invalid Level"}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_level_id_with_backup_keeps_original() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let path = write_level_with_id(temp_dir.path(), "level.json", Value::from("12-abc"))?;
+        let original = fs::read_to_string(&path)?;
+
+        let written = migrate_level_id_with_options(
+            &path,
+            5,
+            MigrationOptions {
+                backup: true,
+                ..MigrationOptions::default()
+            },
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("level.json.bak"))?,
+            original
+        );
+        assert_eq!(fs::read_to_string(&path)?, written);
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_migration_rejects_off_grid_level_before_writing() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let path = write_level_with_id(
+            &temp_dir.path().join("easy"),
+            "level.json",
+            Value::from("12-abc"),
+        )?;
+        let mut level: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        level["exit"] = serde_json::json!({ "x": 10, "y": 3 });
+        fs::write(&path, serde_json::to_string_pretty(&level)?)?;
+        let original = fs::read(&path)?;
+        let modified = fs::metadata(&path)?.modified()?;
+        let strict = MigrationOptions {
+            strict: true,
+            backup: true,
+            ..MigrationOptions::default()
+        };
+
+        let err = migrate_level_id_with_options(&path, 5, strict).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Validation step 'check geometry' failed"));
+        assert!(err
+            .to_string()
+            .contains("exit position (10, 3) is outside the 10x10 grid"));
+        assert_eq!(fs::read(&path)?, original);
+        assert_eq!(fs::metadata(&path)?.modified()?, modified);
+        assert!(!backup_path(&path).exists());
+
+        // Strict mode applies to whole-catalog migrations too.
+        let err = migrate_all_level_ids(temp_dir.path(), strict).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Validation step 'check geometry' failed"));
+        assert_eq!(fs::read(&path)?, original);
+
+        // Without strict mode the same level migrates as before.
+        migrate_level_id_with_options(&path, 5, MigrationOptions::default())?;
+        let migrated: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        assert_eq!(migrated["id"], 5);
+        Ok(())
+    }
+
     #[test]
     fn test_migrate_level_id_reports_missing_file() {
         let missing_path = Path::new("/definitely-missing-level.json");
@@ -1,13 +1,282 @@
-use crate::analysis::{analyze_level, LevelAnalysis, ObstaclePattern};
+use crate::analysis::{analyze_level, fnv1a, LevelAnalysis, ObstaclePattern};
+use crate::levels::write_json_pretty_if_changed;
 use gsnake_core::models::LevelDefinition;
 use std::collections::HashSet;
 use std::fs;
 use std::io;
 use std::path::Path;
 
+/// Default fraction of generic names in a directory above which a warning is printed
+pub const DEFAULT_GENERIC_NAME_THRESHOLD: f64 = 0.5;
+
+/// Directories with fewer levels than this never trigger the generic-name warning
+const MIN_LEVELS_FOR_GENERIC_WARNING: usize = 3;
+
+/// Every word [`generate_name`] can produce
+const GENERATED_NAME_WORDS: [&str; 12] = [
+    "Floating",
+    "Falling",
+    "Stone",
+    "Spike",
+    "Tower",
+    "Bridge",
+    "Islands",
+    "Labyrinth",
+    "Dense",
+    "Feast",
+    "Maze",
+    "Simple",
+];
+
+/// Levels with at least this many dead ends are named a "Labyrinth"
+pub const LABYRINTH_MIN_DEAD_ENDS: usize = 4;
+
+/// Adjectives prefixed to tell apart levels that share a base name
+const NAME_ADJECTIVES: [&str; 16] = [
+    "Quiet", "Crooked", "Hidden", "Narrow", "Winding", "Lonely", "Twisted", "Sunny", "Misty",
+    "Hollow", "Silent", "Tiny", "Ancient", "Restless", "Golden", "Shadow",
+];
+
+/// Nouns appended to tell apart levels that share a base name
+const NAME_NOUNS: [&str; 16] = [
+    "Meadow", "Field", "Trail", "Glade", "Plains", "Path", "Valley", "Orchard", "Pond", "Dunes",
+    "Harbor", "Summit", "Canyon", "Grove", "Lagoon", "Ridge",
+];
+
+/// Extra disambiguation words for levels with a given mechanic or obstacle pattern
+struct NameTheme {
+    adjectives: &'static [&'static str],
+    nouns: &'static [&'static str],
+}
+
+const FLOATING_THEME: NameTheme = NameTheme {
+    adjectives: &["Airy", "Drifting", "Buoyant"],
+    nouns: &["Clouds", "Balloons", "Skies"],
+};
+const FALLING_THEME: NameTheme = NameTheme {
+    adjectives: &["Tumbling", "Plunging", "Steep"],
+    nouns: &["Cascade", "Rainfall", "Landslide"],
+};
+const STONE_THEME: NameTheme = NameTheme {
+    adjectives: &["Rocky", "Heavy", "Rugged"],
+    nouns: &["Quarry", "Boulders", "Cairn"],
+};
+const SPIKE_THEME: NameTheme = NameTheme {
+    adjectives: &["Prickly", "Jagged", "Perilous"],
+    nouns: &["Thorns", "Needles", "Brambles"],
+};
+const TOWER_THEME: NameTheme = NameTheme {
+    adjectives: &["Lofty", "Towering"],
+    nouns: &["Spire", "Pillars", "Keep"],
+};
+const BRIDGE_THEME: NameTheme = NameTheme {
+    adjectives: &["Endless", "Flat"],
+    nouns: &["Causeway", "Span", "Ledge"],
+};
+const ISLANDS_THEME: NameTheme = NameTheme {
+    adjectives: &["Scattered", "Distant"],
+    nouns: &["Reef", "Atoll", "Shoals"],
+};
+const LABYRINTH_THEME: NameTheme = NameTheme {
+    adjectives: &["Tangled", "Blind"],
+    nouns: &["Passages", "Warren"],
+};
+const ALL_THEMES: [&NameTheme; 8] = [
+    &FLOATING_THEME,
+    &FALLING_THEME,
+    &STONE_THEME,
+    &SPIKE_THEME,
+    &TOWER_THEME,
+    &BRIDGE_THEME,
+    &ISLANDS_THEME,
+    &LABYRINTH_THEME,
+];
+
+/// Options for naming the levels of a directory
+#[derive(Debug, Clone, Copy)]
+pub struct NamingOptions {
+    /// Warn when more than this fraction of the names are generic
+    pub generic_threshold: f64,
+    /// Rename levels that already carry a hand-written name
+    pub overwrite: bool,
+    /// Seed for picking adjectives on name collisions instead of numeric suffixes
+    pub seed: Option<u64>,
+}
+
+impl Default for NamingOptions {
+    fn default() -> Self {
+        Self {
+            generic_threshold: DEFAULT_GENERIC_NAME_THRESHOLD,
+            overwrite: true,
+            seed: None,
+        }
+    }
+}
+
 /// Generates a creative name for a level based on its analysis
 #[allow(dead_code)]
 pub fn generate_name(analysis: &LevelAnalysis, used_names: &mut HashSet<String>) -> String {
+    generate_name_seeded(analysis, used_names, None)
+}
+
+/// Like [`generate_name`], but with a seed the disambiguating words are picked starting
+/// from a position derived from the seed and the base name.
+///
+/// A taken base name is first retried as "Adjective Base", "Base Noun" and
+/// "Adjective Base Noun" from word pools themed by the level's mechanics and pattern; numeric
+/// suffixes are only appended once every combination is taken.
+pub fn generate_name_seeded(
+    analysis: &LevelAnalysis,
+    used_names: &mut HashSet<String>,
+    seed: Option<u64>,
+) -> String {
+    let mut name_parts = descriptive_name_parts(analysis);
+
+    // If we have no parts yet, use a generic name based on complexity
+    if name_parts.is_empty() {
+        if analysis.complexity.obstacle_density > 0.1 {
+            name_parts.push("Maze");
+        } else {
+            name_parts.push("Simple");
+        }
+    }
+
+    // Ensure name is 1-4 words (trim if needed)
+    if name_parts.len() > 4 {
+        name_parts.truncate(4);
+    }
+
+    // Create base name
+    let mut name = name_parts.join(" ");
+
+    if used_names.contains(&name) {
+        // Keep within 4 words once an adjective or noun is added, or 2 words for both
+        let short_base = name_parts[..name_parts.len().min(3)].join(" ");
+        let shorter_base = name_parts[..name_parts.len().min(2)].join(" ");
+        let start = seed.map_or(0, |seed| {
+            splitmix64(seed ^ fnv1a(short_base.as_bytes())) as usize
+        });
+        let (adjectives, nouns) = theme_words(analysis);
+        let rotate = |words: &[&'static str]| -> Vec<&'static str> {
+            (0..words.len())
+                .map(|offset| words[(start + offset) % words.len()])
+                .collect()
+        };
+        let (adjectives, nouns) = (rotate(&adjectives), rotate(&nouns));
+
+        let prefixed = adjectives
+            .iter()
+            .map(|adjective| format!("{adjective} {short_base}"));
+        let suffixed = nouns.iter().map(|noun| format!("{short_base} {noun}"));
+        let shorter_base = &shorter_base;
+        let combined = adjectives.iter().flat_map(|adjective| {
+            nouns
+                .iter()
+                .map(move |noun| format!("{adjective} {shorter_base} {noun}"))
+        });
+        let candidate = prefixed
+            .chain(suffixed)
+            .chain(combined)
+            .find(|candidate| !used_names.contains(candidate));
+        if let Some(candidate) = candidate {
+            used_names.insert(candidate.clone());
+            return candidate;
+        }
+    }
+
+    // Every combination is taken; append numbers instead
+    let mut counter = 1;
+    let base_name = name.clone();
+    while used_names.contains(&name) {
+        counter += 1;
+        name = format!("{} {}", base_name, counter);
+    }
+
+    used_names.insert(name.clone());
+    name
+}
+
+/// Disambiguating adjectives and nouns for a level: the themes of its mechanics and
+/// obstacle pattern first, then the general pools
+fn theme_words(analysis: &LevelAnalysis) -> (Vec<&'static str>, Vec<&'static str>) {
+    let themes = [
+        (analysis.mechanics.has_floating_food, &FLOATING_THEME),
+        (analysis.mechanics.has_falling_food, &FALLING_THEME),
+        (analysis.mechanics.has_stones, &STONE_THEME),
+        (analysis.mechanics.has_spikes, &SPIKE_THEME),
+        (
+            analysis.pattern == ObstaclePattern::VerticalWall,
+            &TOWER_THEME,
+        ),
+        (
+            analysis.pattern == ObstaclePattern::HorizontalWall,
+            &BRIDGE_THEME,
+        ),
+        (
+            analysis.pattern == ObstaclePattern::Scattered
+                && analysis.complexity.obstacle_density > 0.0,
+            &ISLANDS_THEME,
+        ),
+        (
+            analysis.complexity.dead_ends >= LABYRINTH_MIN_DEAD_ENDS,
+            &LABYRINTH_THEME,
+        ),
+    ];
+
+    let mut adjectives = Vec::new();
+    let mut nouns = Vec::new();
+    for (_, theme) in themes.into_iter().filter(|(present, _)| *present) {
+        adjectives.extend_from_slice(theme.adjectives);
+        nouns.extend_from_slice(theme.nouns);
+    }
+    adjectives.extend_from_slice(&NAME_ADJECTIVES);
+    nouns.extend_from_slice(&NAME_NOUNS);
+    (adjectives, nouns)
+}
+
+/// Whether `name` looks like output of [`generate_name`], e.g. "Stone Bridge" or "Simple 3"
+pub fn is_generated_name(name: &str) -> bool {
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    // Uniqueness suffix appended by generate_name
+    if words.len() > 1 && words.last().is_some_and(|word| word.parse::<u32>().is_ok()) {
+        words.pop();
+    }
+    // Adjective prefixed and noun appended to tell apart names sharing a base
+    if words.len() > 1 && is_name_adjective(words[0]) {
+        words.remove(0);
+    }
+    if words.len() > 1 && words.last().is_some_and(|word| is_name_noun(word)) {
+        words.pop();
+    }
+    !words.is_empty() && words.iter().all(|word| GENERATED_NAME_WORDS.contains(word))
+}
+
+fn is_name_adjective(word: &str) -> bool {
+    NAME_ADJECTIVES.contains(&word)
+        || ALL_THEMES
+            .iter()
+            .any(|theme| theme.adjectives.contains(&word))
+}
+
+fn is_name_noun(word: &str) -> bool {
+    NAME_NOUNS.contains(&word) || ALL_THEMES.iter().any(|theme| theme.nouns.contains(&word))
+}
+
+/// SplitMix64 finalizer, spreading seed bits over the whole word
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Whether a level has no distinguishing features and gets a generic "Simple"/"Maze" name
+pub fn is_generic_name(analysis: &LevelAnalysis) -> bool {
+    descriptive_name_parts(analysis).is_empty()
+}
+
+/// Name words derived from mechanics, obstacle pattern and complexity
+fn descriptive_name_parts(analysis: &LevelAnalysis) -> Vec<&'static str> {
     let mut name_parts = Vec::new();
 
     // Priority 1: Special mechanics
@@ -42,6 +311,9 @@ pub fn generate_name(analysis: &LevelAnalysis, used_names: &mut HashSet<String>)
     if let Some(pattern) = pattern_word {
         name_parts.push(pattern);
     }
+    if analysis.complexity.dead_ends >= LABYRINTH_MIN_DEAD_ENDS {
+        name_parts.push("Labyrinth");
+    }
 
     // Priority 3: Complexity indicators
     if analysis.complexity.obstacle_density > 0.15 {
@@ -50,38 +322,26 @@ pub fn generate_name(analysis: &LevelAnalysis, used_names: &mut HashSet<String>)
         name_parts.push("Feast");
     }
 
-    // If we have no parts yet, use a generic name based on complexity
-    if name_parts.is_empty() {
-        if analysis.complexity.obstacle_density > 0.1 {
-            name_parts.push("Maze");
-        } else {
-            name_parts.push("Simple");
-        }
-    }
-
-    // Ensure name is 1-4 words (trim if needed)
-    if name_parts.len() > 4 {
-        name_parts.truncate(4);
-    }
-
-    // Create base name
-    let mut name = name_parts.join(" ");
-
-    // Ensure uniqueness by appending numbers if needed
-    let mut counter = 1;
-    let base_name = name.clone();
-    while used_names.contains(&name) {
-        counter += 1;
-        name = format!("{} {}", base_name, counter);
-    }
-
-    used_names.insert(name.clone());
-    name
+    name_parts
 }
 
-/// Updates a level JSON file with a generated name
+/// Updates a level JSON file with a generated name that no other level in its directory
+/// already carries
 #[allow(dead_code)]
 pub fn update_level_name(file_path: &Path) -> io::Result<()> {
+    update_level_name_with(file_path, None).map(|_| ())
+}
+
+/// Updates a level JSON file with a generated name and returns it.
+///
+/// The name is reserved in `used_names` when given, so a caller naming levels one by one
+/// across directories can share a single set; otherwise the names of the file's sibling
+/// levels are reserved.
+#[allow(dead_code)]
+pub fn update_level_name_with(
+    file_path: &Path,
+    used_names: Option<&mut HashSet<String>>,
+) -> io::Result<String> {
     // Read the JSON file
     let contents = fs::read_to_string(file_path)?;
     let mut level: serde_json::Value = serde_json::from_str(&contents)?;
@@ -92,70 +352,193 @@ pub fn update_level_name(file_path: &Path) -> io::Result<()> {
     // Analyze the level
     let analysis = analyze_level(&level_def);
 
-    // Generate name (use a temporary set since we're processing one level)
-    let mut used_names = HashSet::new();
-    let new_name = generate_name(&analysis, &mut used_names);
+    let mut sibling_names;
+    let used_names = match used_names {
+        Some(used_names) => used_names,
+        None => {
+            let dir_path = file_path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            sibling_names = existing_level_names(dir_path, Some(file_path))?;
+            &mut sibling_names
+        },
+    };
+    let new_name = generate_name(&analysis, used_names);
 
     // Update the name field
     if let Some(obj) = level.as_object_mut() {
-        obj.insert("name".to_string(), serde_json::Value::String(new_name));
+        obj.insert(
+            "name".to_string(),
+            serde_json::Value::String(new_name.clone()),
+        );
     }
 
-    // Write back to file with pretty formatting
-    let updated_json = serde_json::to_string_pretty(&level)?;
-    fs::write(file_path, updated_json)?;
+    // Write back to file with pretty formatting, skipping unchanged files
+    write_json_pretty_if_changed(file_path, &contents, &level).map_err(io::Error::other)?;
+
+    Ok(new_name)
+}
 
-    Ok(())
+/// Non-empty names of the level JSON files in `dir_path`, other than `exclude`; files that
+/// cannot be parsed are skipped
+pub fn existing_level_names(
+    dir_path: &Path,
+    exclude: Option<&Path>,
+) -> io::Result<HashSet<String>> {
+    let mut names = HashSet::new();
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json")
+            || exclude.is_some_and(|exclude| path.file_name() == exclude.file_name())
+        {
+            continue;
+        }
+        let Ok(level) = serde_json::from_str::<serde_json::Value>(&fs::read_to_string(&path)?)
+        else {
+            continue;
+        };
+        if let Some(name) = level["name"].as_str().map(str::trim) {
+            if !name.is_empty() {
+                names.insert(name.to_string());
+            }
+        }
+    }
+    Ok(names)
 }
 
 /// Generates names for all levels in a directory, ensuring uniqueness
+///
+/// Warns on stderr when more than [`DEFAULT_GENERIC_NAME_THRESHOLD`] of the names are generic.
 #[allow(dead_code)]
 pub fn generate_names_for_directory(
     dir_path: &Path,
     used_names: &mut HashSet<String>,
 ) -> io::Result<Vec<(String, String)>> {
-    let mut results = Vec::new();
-
-    // Read all JSON files in the directory
-    let entries = fs::read_dir(dir_path)?;
+    generate_names_for_directory_preserving(dir_path, used_names, true)
+}
 
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+/// Generates names for the levels in a directory; with `overwrite` false, levels that carry
+/// a hand-written name keep it
+#[allow(dead_code)]
+pub fn generate_names_for_directory_preserving(
+    dir_path: &Path,
+    used_names: &mut HashSet<String>,
+    overwrite: bool,
+) -> io::Result<Vec<(String, String)>> {
+    generate_names_for_directory_with_options(
+        dir_path,
+        used_names,
+        NamingOptions {
+            overwrite,
+            ..NamingOptions::default()
+        },
+    )
+}
 
+/// Generates names for all levels in a directory, warning when the fraction of generic
+/// names exceeds `options.generic_threshold`
+///
+/// Files are named in sorted path order, so the same directory and seed always yield the
+/// same names. Unless `options.overwrite` is set, a non-empty name that [`is_generated_name`]
+/// does not recognise is left untouched and only reserved in `used_names`. Returns the names
+/// that were written.
+pub fn generate_names_for_directory_with_options(
+    dir_path: &Path,
+    used_names: &mut HashSet<String>,
+    options: NamingOptions,
+) -> io::Result<Vec<(String, String)>> {
+    let mut level_paths = Vec::new();
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
         if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            // Read and parse the level
-            let contents = fs::read_to_string(&path)?;
-            let level_def: LevelDefinition = serde_json::from_str(&contents)?;
-
-            // Analyze and generate name
-            let analysis = analyze_level(&level_def);
-            let new_name = generate_name(&analysis, used_names);
-
-            // Update the JSON file
-            let mut level: serde_json::Value = serde_json::from_str(&contents)?;
-            if let Some(obj) = level.as_object_mut() {
-                obj.insert(
-                    "name".to_string(),
-                    serde_json::Value::String(new_name.clone()),
-                );
-            }
+            level_paths.push(path);
+        }
+    }
+    level_paths.sort();
+
+    let mut levels = Vec::new();
+    for path in level_paths {
+        let contents = fs::read_to_string(&path)?;
+        let level_def: LevelDefinition = serde_json::from_str(&contents)?;
+        levels.push((path, contents, level_def));
+    }
 
-            // Write back
-            let updated_json = serde_json::to_string_pretty(&level)?;
-            fs::write(&path, updated_json)?;
+    // Reserve kept names first so generated ones never collide with them
+    let mut to_name = Vec::new();
+    for (path, contents, level_def) in levels {
+        let name = level_def.name.trim();
+        if !options.overwrite && !name.is_empty() && !is_generated_name(name) {
+            used_names.insert(name.to_string());
+        } else {
+            to_name.push((path, contents, level_def));
+        }
+    }
+
+    let mut results = Vec::new();
+    let mut generic_count = 0;
 
-            results.push((path.display().to_string(), new_name));
+    for (path, contents, level_def) in to_name {
+        // Analyze and generate name
+        let analysis = analyze_level(&level_def);
+        if is_generic_name(&analysis) {
+            generic_count += 1;
         }
+        let new_name = generate_name_seeded(&analysis, used_names, options.seed);
+
+        // Update the JSON file
+        let mut level: serde_json::Value = serde_json::from_str(&contents)?;
+        if let Some(obj) = level.as_object_mut() {
+            obj.insert(
+                "name".to_string(),
+                serde_json::Value::String(new_name.clone()),
+            );
+        }
+
+        // Write back only on change, so playback freshness checks keep seeing the old mtime
+        write_json_pretty_if_changed(&path, &contents, &level).map_err(io::Error::other)?;
+
+        results.push((path.display().to_string(), new_name));
+    }
+
+    if let Some(warning) = generic_name_warning(
+        dir_path,
+        generic_count,
+        results.len(),
+        options.generic_threshold,
+    ) {
+        eprintln!("{warning}");
     }
 
     Ok(results)
 }
 
+/// Warning text when too many levels in `dir_path` fell back to generic names
+fn generic_name_warning(
+    dir_path: &Path,
+    generic_count: usize,
+    total: usize,
+    threshold: f64,
+) -> Option<String> {
+    if total < MIN_LEVELS_FOR_GENERIC_WARNING {
+        return None;
+    }
+    let fraction = generic_count as f64 / total as f64;
+    (fraction > threshold).then(|| {
+        format!(
+            "Warning: {} of {} levels in {} got generic names (\"Simple\"/\"Maze\"); \
+             consider adding mechanics, obstacle patterns or tags to tell them apart",
+            generic_count,
+            total,
+            dir_path.display()
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analysis::{ComplexityMetrics, LevelMechanics};
+    use crate::analysis::{ComplexityMetrics, LevelMechanics, SymmetrySet};
     use std::collections::HashSet;
 
     fn create_analysis(
@@ -179,7 +562,10 @@ mod tests {
                 obstacle_density: density,
                 food_count,
                 grid_area: 100,
+                dead_ends: 0,
+                corridor_ratio: 0.0,
             },
+            symmetry: SymmetrySet::default(),
         }
     }
 
@@ -195,6 +581,19 @@ mod tests {
         assert!(used.contains(&name));
     }
 
+    #[test]
+    fn test_generate_name_uses_labyrinth_for_many_dead_ends() {
+        let mut analysis =
+            create_analysis(false, false, false, false, ObstaclePattern::None, 0.1, 2);
+        analysis.complexity.dead_ends = LABYRINTH_MIN_DEAD_ENDS - 1;
+        assert!(!generate_name(&analysis, &mut HashSet::new()).contains("Labyrinth"));
+
+        analysis.complexity.dead_ends = LABYRINTH_MIN_DEAD_ENDS;
+        let name = generate_name(&analysis, &mut HashSet::new());
+        assert_eq!(name, "Labyrinth");
+        assert!(is_generated_name(&name));
+    }
+
     #[test]
     fn test_generate_name_with_pattern() {
         let analysis = create_analysis(
@@ -260,6 +659,204 @@ mod tests {
         assert!(name.contains("Simple"));
     }
 
+    #[test]
+    fn test_is_generic_name() {
+        let plain = create_analysis(false, false, false, false, ObstaclePattern::None, 0.02, 1);
+        let stones = create_analysis(false, false, true, false, ObstaclePattern::None, 0.02, 1);
+
+        assert!(is_generic_name(&plain));
+        assert!(!is_generic_name(&stones));
+    }
+
+    #[test]
+    fn test_generic_name_warning_threshold() {
+        let dir = Path::new("levels/easy");
+
+        let warning = generic_name_warning(dir, 3, 4, DEFAULT_GENERIC_NAME_THRESHOLD)
+            .expect("3 of 4 generic names should warn");
+        assert!(warning.contains("3 of 4 levels in levels/easy"));
+
+        assert!(generic_name_warning(dir, 2, 4, DEFAULT_GENERIC_NAME_THRESHOLD).is_none());
+        assert!(generic_name_warning(dir, 2, 2, DEFAULT_GENERIC_NAME_THRESHOLD).is_none());
+        assert!(generic_name_warning(dir, 4, 4, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_is_generated_name() {
+        assert!(is_generated_name("Simple"));
+        assert!(is_generated_name("Stone Bridge Dense"));
+        assert!(is_generated_name("Maze 3"));
+        assert!(!is_generated_name("Snake Charmer"));
+        assert!(!is_generated_name("Stone Garden"));
+        assert!(!is_generated_name("3"));
+        assert!(is_generated_name("Rocky Stone Quarry"));
+        assert!(is_generated_name("Simple Meadow 2"));
+        assert!(!is_generated_name("Meadow"));
+    }
+
+    #[test]
+    fn test_identical_levels_get_word_combinations_before_numbers() {
+        let analysis = create_analysis(false, false, true, false, ObstaclePattern::None, 0.02, 1);
+        let mut used = HashSet::new();
+        let names: Vec<String> = (0..50)
+            .map(|_| generate_name(&analysis, &mut used))
+            .collect();
+
+        let numbered = names
+            .iter()
+            .filter(|name| {
+                name.split_whitespace()
+                    .last()
+                    .is_some_and(|word| word.parse::<u32>().is_ok())
+            })
+            .count();
+        assert!(numbered < 5, "{numbered} numbered names: {names:?}");
+        assert_eq!(used.len(), 50);
+        assert_eq!(names[0], "Stone");
+        // Themed words come before the general pools
+        assert_eq!(names[1], "Rocky Stone");
+        for name in &names {
+            assert!(name.split_whitespace().count() <= 4, "{name}");
+            assert!(is_generated_name(name), "{name}");
+        }
+    }
+
+    fn write_named_level(dir: &Path, file: &str, name: &str) -> io::Result<()> {
+        let level = serde_json::json!({
+            "id": 1,
+            "name": name,
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 2 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 4, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        });
+        fs::write(dir.join(file), serde_json::to_string_pretty(&level)?)
+    }
+
+    #[test]
+    fn test_generate_names_preserving_keeps_custom_names() -> io::Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        write_named_level(temp_dir.path(), "custom.json", "Snake Charmer")?;
+        write_named_level(temp_dir.path(), "unnamed.json", "")?;
+
+        let mut used = HashSet::new();
+        let results = generate_names_for_directory_preserving(temp_dir.path(), &mut used, false)?;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].0.ends_with("unnamed.json"));
+        assert!(used.contains("Snake Charmer"));
+
+        let read_name = |file: &str| {
+            let contents = fs::read_to_string(temp_dir.path().join(file)).unwrap();
+            let level: serde_json::Value = serde_json::from_str(&contents).unwrap();
+            level["name"].as_str().unwrap().to_string()
+        };
+        assert_eq!(read_name("custom.json"), "Snake Charmer");
+        assert_eq!(read_name("unnamed.json"), results[0].1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_name_writers_end_with_single_newline_and_are_idempotent() -> io::Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        write_named_level(temp_dir.path(), "a.json", "")?;
+        write_named_level(temp_dir.path(), "b.json", "")?;
+        let read = |file: &str| fs::read_to_string(temp_dir.path().join(file)).unwrap();
+
+        update_level_name(&temp_dir.path().join("a.json"))?;
+        assert!(read("a.json").ends_with("}\n"));
+        assert!(!read("a.json").ends_with("\n\n"));
+
+        generate_names_for_directory(temp_dir.path(), &mut HashSet::new())?;
+        let first = (read("a.json"), read("b.json"));
+        // Unchanged files are not rewritten, so their mtime survives a rerun.
+        let old = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        fs::File::options()
+            .write(true)
+            .open(temp_dir.path().join("a.json"))?
+            .set_modified(old)?;
+        generate_names_for_directory(temp_dir.path(), &mut HashSet::new())?;
+        assert_eq!((read("a.json"), read("b.json")), first);
+        assert_eq!(
+            fs::metadata(temp_dir.path().join("a.json"))?.modified()?,
+            old
+        );
+        assert!(first.1.ends_with("}\n") && !first.1.ends_with("\n\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_level_name_avoids_sibling_and_shared_names() -> io::Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        write_named_level(temp_dir.path(), "a.json", "")?;
+        write_named_level(temp_dir.path(), "b.json", "Simple")?;
+
+        update_level_name(&temp_dir.path().join("a.json"))?;
+        let names = existing_level_names(temp_dir.path(), None)?;
+        assert_eq!(names.len(), 2, "{names:?}");
+        assert!(names.contains("Simple"));
+
+        let mut used = HashSet::from(["Simple".to_string(), "Quiet Simple".to_string()]);
+        let name = update_level_name_with(&temp_dir.path().join("b.json"), Some(&mut used))?;
+        assert!(!["Simple", "Quiet Simple"].contains(&name.as_str()));
+        assert!(used.contains(&name));
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded_generate_name_prefixes_adjective_on_collision() {
+        let analysis = create_analysis(false, false, false, false, ObstaclePattern::None, 0.02, 1);
+        let mut used = HashSet::new();
+
+        assert_eq!(
+            generate_name_seeded(&analysis, &mut used, Some(7)),
+            "Simple"
+        );
+        let second = generate_name_seeded(&analysis, &mut used, Some(7));
+        let (adjective, base) = second.split_once(' ').unwrap();
+        assert!(NAME_ADJECTIVES.contains(&adjective));
+        assert_eq!(base, "Simple");
+        assert!(is_generated_name(&second));
+    }
+
+    #[test]
+    fn test_seeded_directory_names_are_reproducible() -> io::Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        for index in 0..6 {
+            write_named_level(temp_dir.path(), &format!("level_{index:03}.json"), "")?;
+        }
+        let options = NamingOptions {
+            seed: Some(42),
+            ..NamingOptions::default()
+        };
+
+        let first = generate_names_for_directory_with_options(
+            temp_dir.path(),
+            &mut HashSet::new(),
+            options,
+        )?;
+        let second = generate_names_for_directory_with_options(
+            temp_dir.path(),
+            &mut HashSet::new(),
+            options,
+        )?;
+
+        assert_eq!(first, second);
+        assert!(first[0].0.ends_with("level_000.json"));
+        assert_eq!(first[0].1, "Simple");
+        let distinct: HashSet<&String> = first.iter().map(|(_, name)| name).collect();
+        assert_eq!(distinct.len(), first.len());
+        Ok(())
+    }
+
     #[test]
     fn test_generate_name_all_mechanics() {
         let analysis = create_analysis(
@@ -0,0 +1,222 @@
+use crate::levels;
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Level JSON arrays that describe sets of cells, where a repeated position is a mistake
+const POSITION_FIELDS: [&str; 6] = [
+    "obstacles",
+    "food",
+    "floatingFood",
+    "fallingFood",
+    "stones",
+    "spikes",
+];
+
+/// Fields counted by `totalFood`
+const FOOD_FIELDS: [&str; 3] = ["food", "floatingFood", "fallingFood"];
+
+/// Duplicates removed from one position array of a level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedDuplicates {
+    pub field: &'static str,
+    pub count: usize,
+}
+
+pub fn run_normalize_levels(levels_root: &Path) -> Result<()> {
+    let mut normalized = 0;
+    let mut checked = 0;
+
+    for difficulty in levels::catalog_difficulties(levels_root) {
+        let dir = levels_root.join(&difficulty);
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            checked += 1;
+            let removed = dedup_level_positions(&path)?;
+            if removed.is_empty() {
+                continue;
+            }
+            normalized += 1;
+            println!("{}: {}", path.display(), describe(&removed));
+        }
+    }
+
+    println!("Normalized {normalized} of {checked} level(s)");
+    Ok(())
+}
+
+/// Removes repeated coordinates from each position array of the level at `level_path`,
+/// keeping the first occurrence, and rewrites the file only when something was removed.
+///
+/// When food was deduplicated and `totalFood` matched the old food count, it is lowered to
+/// the new count so the two stay consistent.
+pub fn dedup_level_positions(level_path: &Path) -> Result<Vec<RemovedDuplicates>> {
+    let contents = fs::read_to_string(level_path)
+        .with_context(|| format!("Failed to read level file: {}", level_path.display()))?;
+    let mut level_json: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse level JSON: {}", level_path.display()))?;
+    let Some(level_object) = level_json.as_object_mut() else {
+        bail!(
+            "Level JSON is not an object and cannot be normalized: {}",
+            level_path.display()
+        );
+    };
+
+    let removed = dedup_positions(level_object);
+    if removed.is_empty() {
+        return Ok(removed);
+    }
+    levels::write_json_pretty(level_path, &level_json).with_context(|| {
+        format!(
+            "Failed to write normalized level JSON: {}",
+            level_path.display()
+        )
+    })?;
+    Ok(removed)
+}
+
+/// In-memory counterpart of [`dedup_level_positions`]: removes repeated coordinates from
+/// each position array of `level_object` and lowers a matching `totalFood` the same way
+pub fn dedup_positions(
+    level_object: &mut serde_json::Map<String, Value>,
+) -> Vec<RemovedDuplicates> {
+    let food_count = |object: &serde_json::Map<String, Value>| -> usize {
+        FOOD_FIELDS
+            .iter()
+            .filter_map(|field| object.get(*field).and_then(Value::as_array))
+            .map(Vec::len)
+            .sum()
+    };
+    let food_before = food_count(level_object);
+
+    let mut removed = Vec::new();
+    for field in POSITION_FIELDS {
+        let Some(Value::Array(positions)) = level_object.get_mut(field) else {
+            continue;
+        };
+        let before = positions.len();
+        let mut seen = Vec::with_capacity(before);
+        positions.retain(|position| {
+            if seen.contains(position) {
+                return false;
+            }
+            seen.push(position.clone());
+            true
+        });
+        if positions.len() < before {
+            removed.push(RemovedDuplicates {
+                field,
+                count: before - positions.len(),
+            });
+        }
+    }
+    if removed.is_empty() {
+        return removed;
+    }
+
+    let food_after = food_count(level_object);
+    if level_object.get("totalFood").and_then(Value::as_u64) == Some(food_before as u64) {
+        level_object.insert("totalFood".to_string(), Value::from(food_after));
+    }
+    removed
+}
+
+/// Summarizes removed duplicates as "obstacles: 1 duplicate(s), food: 2 duplicate(s)"
+pub fn describe(removed: &[RemovedDuplicates]) -> String {
+    removed
+        .iter()
+        .map(|removed| format!("{}: {} duplicate(s)", removed.field, removed.count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn write_level(path: &Path, obstacles: Value, food: Value, total_food: u32) {
+        let level = json!({
+            "id": 1,
+            "name": "Duplicates",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 5 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": obstacles,
+            "food": food,
+            "exit": { "x": 4, "y": 4 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": total_food
+        });
+        fs::write(path, serde_json::to_string_pretty(&level).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_dedup_level_positions_keeps_one_copy_and_rewrites() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("level.json");
+        write_level(
+            &path,
+            json!([{ "x": 2, "y": 2 }, { "x": 3, "y": 1 }, { "x": 2, "y": 2 }]),
+            json!([{ "x": 1, "y": 1 }, { "x": 1, "y": 1 }]),
+            2,
+        );
+
+        let removed = dedup_level_positions(&path)?;
+        assert_eq!(
+            removed,
+            vec![
+                RemovedDuplicates {
+                    field: "obstacles",
+                    count: 1
+                },
+                RemovedDuplicates {
+                    field: "food",
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            describe(&removed),
+            "obstacles: 1 duplicate(s), food: 1 duplicate(s)"
+        );
+
+        let rewritten: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        assert_eq!(
+            rewritten["obstacles"],
+            json!([{ "x": 2, "y": 2 }, { "x": 3, "y": 1 }])
+        );
+        assert_eq!(rewritten["food"], json!([{ "x": 1, "y": 1 }]));
+        assert_eq!(rewritten["totalFood"], json!(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_level_positions_leaves_clean_file_untouched() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("level.json");
+        write_level(&path, json!([{ "x": 2, "y": 2 }]), json!([]), 0);
+        let before = fs::read_to_string(&path)?;
+
+        assert!(dedup_level_positions(&path)?.is_empty());
+        assert_eq!(fs::read_to_string(&path)?, before);
+        Ok(())
+    }
+}
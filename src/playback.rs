@@ -1,26 +1,112 @@
+use crate::solver::{is_level_complete, navigation_exit};
 use anyhow::{bail, Context, Result};
-use gsnake_core::Direction;
-use serde::Deserialize;
+use gsnake_core::{engine::GameEngine, Direction, Frame, GameStatus, LevelDefinition};
+use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct PlaybackFileStep {
     key: String,
-    #[allow(dead_code)]
     delay_ms: u64,
 }
 
+/// A parsed playback step: the move and how long to wait before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackStep {
+    pub direction: Direction,
+    pub delay_ms: u64,
+}
+
+/// How long each generated step waits before its move
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum DelayStrategy {
+    /// The same delay for every step
+    Constant(u64),
+    /// Starts at `start` and shrinks by `step` per move, never going below `min`
+    Accelerate { start: u64, min: u64, step: u64 },
+    /// An explicit delay per move; moves past the end reuse the last delay
+    PerMove(Vec<u64>),
+}
+
+impl Default for DelayStrategy {
+    fn default() -> Self {
+        Self::Constant(DEFAULT_STEP_DELAY_MS)
+    }
+}
+
+impl DelayStrategy {
+    /// Delay before the move at `index`
+    pub fn delay_for(&self, index: usize) -> u64 {
+        match self {
+            Self::Constant(delay_ms) => *delay_ms,
+            Self::Accelerate { start, min, step } => {
+                let reduction = step.saturating_mul(index as u64);
+                start.saturating_sub(reduction).max(*min)
+            },
+            Self::PerMove(delays) => delays
+                .get(index)
+                .or(delays.last())
+                .copied()
+                .unwrap_or(DEFAULT_STEP_DELAY_MS),
+        }
+    }
+}
+
+/// Optional header describing which level and solver produced a playback
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaybackMeta {
+    pub level_id: u32,
+    pub generated_by: String,
+    pub optimal: bool,
+    pub moves: usize,
+}
+
+/// The `{ "meta": ..., "steps": [...] }` playback format; the other JSON format is a bare
+/// array of steps
+#[derive(Debug, Deserialize)]
+struct MetaPlaybackFile {
+    #[allow(dead_code)]
+    meta: PlaybackMeta,
+    steps: Vec<PlaybackFileStep>,
+}
+
+/// Delay written for each generated step and given to each move of a compact `"RRDU"`
+/// playback, which carries no timing
+pub const DEFAULT_STEP_DELAY_MS: u64 = 200;
+
 pub fn load_playback_directions(path: &Path) -> Result<Vec<Direction>> {
+    Ok(load_playback_steps(path)?
+        .into_iter()
+        .map(|step| step.direction)
+        .collect())
+}
+
+/// Loads a playback file: an array of `{key, delay_ms}` steps, the same wrapped with a
+/// `meta` header, or a compact JSON string such as `"RRDU"`
+pub fn load_playback_steps(path: &Path) -> Result<Vec<PlaybackStep>> {
     let contents = fs::read_to_string(path)
         .with_context(|| format!("Failed to read playback file: {}", path.display()))?;
-    let raw_steps: Vec<PlaybackFileStep> =
-        serde_json::from_str(&contents).with_context(|| "Failed to parse playback JSON")?;
+    if contents.trim_start().starts_with('"') {
+        return parse_compact_playback(&contents, path);
+    }
+
+    // The first character picks the format, so a parse error describes what is wrong with
+    // that format instead of only saying that no format matched.
+    let raw_steps: Vec<PlaybackFileStep> = if contents.trim_start().starts_with('{') {
+        serde_json::from_str::<MetaPlaybackFile>(&contents)
+            .with_context(|| "Failed to parse playback JSON as an object with meta and steps")?
+            .steps
+    } else {
+        serde_json::from_str(&contents)
+            .with_context(|| "Failed to parse playback JSON as an array of steps")?
+    };
 
     if raw_steps.is_empty() {
         bail!("Playback input file is empty");
     }
 
-    let mut directions = Vec::with_capacity(raw_steps.len());
+    let mut steps = Vec::with_capacity(raw_steps.len());
     for (index, step) in raw_steps.into_iter().enumerate() {
         let direction = parse_key(&step.key).with_context(|| {
             format!(
@@ -29,30 +115,230 @@ pub fn load_playback_directions(path: &Path) -> Result<Vec<Direction>> {
                 path.display()
             )
         })?;
-        directions.push(direction);
+        steps.push(PlaybackStep {
+            direction,
+            delay_ms: step.delay_ms,
+        });
+    }
+
+    Ok(steps)
+}
+
+fn parse_compact_playback(contents: &str, path: &Path) -> Result<Vec<PlaybackStep>> {
+    let moves: String =
+        serde_json::from_str(contents).with_context(|| "Failed to parse playback JSON")?;
+    if moves.is_empty() {
+        bail!("Playback input file is empty");
+    }
+
+    moves
+        .chars()
+        .enumerate()
+        .map(|(index, ch)| {
+            let direction = parse_string_char(ch).with_context(|| {
+                format!(
+                    "Failed to parse playback character at index {} in {}",
+                    index,
+                    path.display()
+                )
+            })?;
+            Ok(PlaybackStep {
+                direction,
+                delay_ms: DEFAULT_STEP_DELAY_MS,
+            })
+        })
+        .collect()
+}
+
+/// Divides every delay by `speed`, so 2.0 plays twice as fast and 0.5 half as fast
+pub fn scale_delays(steps: &[PlaybackStep], speed: f64) -> Result<Vec<PlaybackStep>> {
+    if !speed.is_finite() || speed <= 0.0 {
+        bail!("Playback speed must be a positive number, got {speed}");
+    }
+
+    Ok(steps
+        .iter()
+        .map(|step| PlaybackStep {
+            direction: step.direction,
+            delay_ms: (step.delay_ms as f64 / speed).round() as u64,
+        })
+        .collect())
+}
+
+#[derive(Serialize)]
+struct PlaybackFileWithMeta<'a> {
+    meta: &'a PlaybackMeta,
+    steps: Vec<PlaybackFileStep>,
+}
+
+/// Steps for `directions`, with delays chosen by `delays`
+pub fn playback_steps(directions: &[Direction], delays: &DelayStrategy) -> Vec<PlaybackStep> {
+    directions
+        .iter()
+        .enumerate()
+        .map(|(index, &direction)| PlaybackStep {
+            direction,
+            delay_ms: delays.delay_for(index),
+        })
+        .collect()
+}
+
+/// Writes `directions` in the bare-array playback format, the counterpart of
+/// [`load_playback_directions`]
+pub fn write_playback_directions(
+    path: &Path,
+    directions: &[Direction],
+    delay_ms: u64,
+) -> Result<()> {
+    write_playback(
+        path,
+        &playback_steps(directions, &DelayStrategy::Constant(delay_ms)),
+        None,
+    )
+}
+
+/// Writes steps in the bare-array playback format
+pub fn write_playback_steps(path: &Path, steps: &[PlaybackStep]) -> Result<()> {
+    write_playback(path, steps, None)
+}
+
+/// Writes steps as pretty JSON with long key names and a trailing newline, wrapped in a
+/// `meta` header when one is given
+///
+/// Missing parent directories are created, and a playback deduplicated into a symlink is
+/// replaced rather than written through to the shared file.
+pub fn write_playback(
+    path: &Path,
+    steps: &[PlaybackStep],
+    meta: Option<&PlaybackMeta>,
+) -> Result<()> {
+    let raw_steps: Vec<PlaybackFileStep> = steps
+        .iter()
+        .map(|step| PlaybackFileStep {
+            key: direction_key(step.direction).to_string(),
+            delay_ms: step.delay_ms,
+        })
+        .collect();
+    let contents = match meta {
+        Some(meta) => serde_json::to_string_pretty(&PlaybackFileWithMeta {
+            meta,
+            steps: raw_steps,
+        })?,
+        None => serde_json::to_string_pretty(&raw_steps)?,
+    };
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        fs::remove_file(path)
+            .with_context(|| format!("Failed to remove symlink {}", path.display()))?;
     }
+    fs::write(path, contents + "\n").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Compact one-letter-per-move form (e.g. "RRDU"), as accepted by `parse_key`
+#[allow(dead_code)]
+pub fn compact_directions(directions: &[Direction]) -> String {
+    directions
+        .iter()
+        .map(|direction| match direction {
+            Direction::North => 'U',
+            Direction::South => 'D',
+            Direction::East => 'R',
+            Direction::West => 'L',
+        })
+        .collect()
+}
+
+/// Canonical playback key for a direction, as written by the solver
+pub fn direction_key(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "Up",
+        Direction::South => "Down",
+        Direction::East => "Right",
+        Direction::West => "Left",
+    }
+}
 
-    Ok(directions)
+/// Shell script that replays `level_path` with `playback_path` through the
+/// `gsnake-levels replay` command of the crate at `manifest_path`
+///
+/// The script is plain POSIX `sh`: every path is single-quoted, and the paths in the header
+/// comment are escaped so a newline in a file name cannot end the comment. Extra arguments
+/// given to the script (e.g. `--headless`) are forwarded to `replay`.
+#[allow(dead_code)]
+pub fn replay_script(manifest_path: &Path, level_path: &Path, playback_path: &Path) -> String {
+    format!(
+        "#!/bin/sh\n\
+         # Replays {level} with the solution in {playback}\n\
+         set -e\n\
+         exec cargo run --quiet --manifest-path {manifest} --bin gsnake-levels -- \\\n  \
+         replay {level_arg} {playback_arg} \"$@\"\n",
+        level = level_path.display().to_string().escape_debug(),
+        playback = playback_path.display().to_string().escape_debug(),
+        manifest = shell_quote(manifest_path),
+        level_arg = shell_quote(level_path),
+        playback_arg = shell_quote(playback_path),
+    )
 }
 
+/// Writes [`replay_script`] to `script_path` and marks it executable
+#[allow(dead_code)]
+pub fn write_replay_script(
+    script_path: &Path,
+    manifest_path: &Path,
+    level_path: &Path,
+    playback_path: &Path,
+) -> Result<()> {
+    fs::write(
+        script_path,
+        replay_script(manifest_path, level_path, playback_path),
+    )
+    .with_context(|| format!("Failed to write {}", script_path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(script_path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", script_path.display()))?;
+    }
+    Ok(())
+}
+
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', "'\\''"))
+}
+
+/// Parses a playback key. Single uppercase R/D/L/U keep their compact meaning, so "D" is
+/// down; WASD only applies to the other single letters, making lowercase "d" right.
 fn parse_key(key: &str) -> Result<Direction> {
     if key.len() == 1 {
         let ch = key
             .chars()
             .next()
             .ok_or_else(|| anyhow::anyhow!("Playback key cannot be empty"))?;
-        if matches!(ch, 'R' | 'D' | 'L' | 'U') {
-            return parse_string_char(ch);
+        match ch {
+            'R' | 'D' | 'L' | 'U' => return parse_string_char(ch),
+            'w' | 'W' => return Ok(Direction::North),
+            'a' | 'A' => return Ok(Direction::West),
+            's' | 'S' => return Ok(Direction::South),
+            'd' => return Ok(Direction::East),
+            _ => {},
         }
     }
 
     let normalized = key.trim().to_lowercase();
     match normalized.as_str() {
-        "right" | "east" => Ok(Direction::East),
-        "down" | "south" => Ok(Direction::South),
-        "left" | "west" => Ok(Direction::West),
-        "up" | "north" => Ok(Direction::North),
-        _ => bail!("Invalid key '{key}'. Use Right/Left/Up/Down (or R/L/U/D)."),
+        "right" | "east" | "arrowright" => Ok(Direction::East),
+        "down" | "south" | "arrowdown" => Ok(Direction::South),
+        "left" | "west" | "arrowleft" => Ok(Direction::West),
+        "up" | "north" | "arrowup" => Ok(Direction::North),
+        _ => bail!("Invalid key '{key}'. Use Right/Left/Up/Down (or R/L/U/D, WASD, ArrowUp...)."),
     }
 }
 
@@ -66,11 +352,144 @@ fn parse_string_char(ch: char) -> Result<Direction> {
     }
 }
 
+/// Steps a fresh engine through `directions`, collecting the starting frame and the frame
+/// after every move. Stops once the level is finished or lost, so trailing moves are ignored
+/// and the frame count is the moves consumed plus one.
+#[allow(dead_code)]
+pub fn replay_frames(level: &LevelDefinition, directions: &[Direction]) -> Result<Vec<Frame>> {
+    let mut frames = Vec::new();
+    replay_engine(level, directions, |_, engine| {
+        frames.push(engine.generate_frame());
+    })?;
+    Ok(frames)
+}
+
+/// Drives a fresh engine through `directions` like `replay_frames`, handing `on_state` the
+/// number of moves applied so far and the engine, once at the start and once after each move
+pub fn replay_engine(
+    level: &LevelDefinition,
+    directions: &[Direction],
+    mut on_state: impl FnMut(usize, &GameEngine),
+) -> Result<()> {
+    let navigation_exit = navigation_exit(level);
+    let mut engine =
+        GameEngine::new(level.clone()).context("Invalid grid size in level definition")?;
+    on_state(0, &engine);
+
+    for (index, &direction) in directions.iter().enumerate() {
+        if engine.game_state().status != GameStatus::Playing
+            || is_level_complete(&engine, navigation_exit)
+        {
+            break;
+        }
+        engine
+            .process_move(direction)
+            .with_context(|| format!("Failed to apply move {}", index + 1))?;
+        on_state(index + 1, &engine);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Write;
-    use tempfile::NamedTempFile;
+    use tempfile::{NamedTempFile, TempDir};
+
+    #[test]
+    fn test_playback_steps_follow_delay_strategy() {
+        let directions = [Direction::East; 5];
+        let delays = |strategy: DelayStrategy| -> Vec<u64> {
+            playback_steps(&directions, &strategy)
+                .iter()
+                .map(|step| step.delay_ms)
+                .collect()
+        };
+
+        assert_eq!(delays(DelayStrategy::default()), vec![200; 5]);
+        assert_eq!(
+            delays(DelayStrategy::Accelerate {
+                start: 200,
+                min: 120,
+                step: 30
+            }),
+            vec![200, 170, 140, 120, 120]
+        );
+        assert_eq!(
+            delays(DelayStrategy::PerMove(vec![300, 100])),
+            vec![300, 100, 100, 100, 100]
+        );
+    }
+
+    #[test]
+    fn test_write_playback_directions_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("easy/level.json");
+        let directions = vec![
+            Direction::East,
+            Direction::South,
+            Direction::West,
+            Direction::North,
+        ];
+
+        write_playback_directions(&path, &directions, 200).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("[\n  {\n    \"key\": \"Right\",\n    \"delay_ms\": 200\n  }"));
+        assert!(contents.ends_with("]\n"));
+        assert_eq!(load_playback_directions(&path).unwrap(), directions);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_playback_replaces_symlink_instead_of_writing_through() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared.json");
+        let linked = temp_dir.path().join("linked.json");
+        fs::write(&shared, "[]\n").unwrap();
+        std::os::unix::fs::symlink(&shared, &linked).unwrap();
+
+        write_playback_directions(&linked, &[Direction::East], DEFAULT_STEP_DELAY_MS).unwrap();
+
+        assert_eq!(fs::read_to_string(&shared).unwrap(), "[]\n");
+        assert!(!fs::symlink_metadata(&linked)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert!(fs::read_to_string(&linked).unwrap().contains("Right"));
+    }
+
+    #[test]
+    fn test_load_playback_steps_compact_string() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, r#"  "RDLU""#).unwrap();
+
+        let steps = load_playback_steps(file.path()).unwrap();
+        let directions: Vec<Direction> = steps.iter().map(|step| step.direction).collect();
+        assert_eq!(
+            directions,
+            vec![
+                Direction::East,
+                Direction::South,
+                Direction::West,
+                Direction::North
+            ]
+        );
+        assert!(steps
+            .iter()
+            .all(|step| step.delay_ms == DEFAULT_STEP_DELAY_MS));
+    }
+
+    #[test]
+    fn test_load_playback_steps_compact_string_reports_invalid_index() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, r#""RX""#).unwrap();
+
+        let error = load_playback_directions(file.path()).unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains("character at index 1"), "{message}");
+        assert!(message.contains("Invalid input character 'X'"), "{message}");
+    }
 
     #[test]
     fn test_load_playback_directions_valid_file() {
@@ -148,8 +567,13 @@ mod tests {
         let result = load_playback_directions(file.path());
         assert!(result.is_err());
 
-        let error = result.unwrap_err();
-        assert!(error.to_string().contains("Failed to parse playback JSON"));
+        let error = format!("{:#}", result.unwrap_err());
+        assert!(
+            error.contains(
+                "Failed to parse playback JSON as an array of steps: missing field `key`"
+            ),
+            "{error}"
+        );
     }
 
     #[test]
@@ -214,6 +638,20 @@ mod tests {
         assert_eq!(directions[3], Direction::North);
     }
 
+    #[test]
+    fn test_parse_key_accepts_wasd_and_arrow_names() {
+        assert_eq!(parse_key("w").unwrap(), Direction::North);
+        assert_eq!(parse_key("a").unwrap(), Direction::West);
+        assert_eq!(parse_key("s").unwrap(), Direction::South);
+        assert_eq!(parse_key("d").unwrap(), Direction::East);
+        assert_eq!(parse_key("ArrowLeft").unwrap(), Direction::West);
+        assert_eq!(parse_key("arrowup").unwrap(), Direction::North);
+        // Uppercase D stays the compact "down"
+        assert_eq!(parse_key("D").unwrap(), Direction::South);
+        assert_eq!(parse_key("Right").unwrap(), Direction::East);
+        assert_eq!(parse_key("R").unwrap(), Direction::East);
+    }
+
     #[test]
     fn test_load_playback_directions_invalid_key() {
         let mut file = NamedTempFile::new().unwrap();
@@ -252,4 +690,208 @@ mod tests {
         assert!(message.contains("Failed to parse playback step 1"));
         assert!(message.contains("Invalid key 'X'"));
     }
+
+    #[test]
+    fn test_load_playback_directions_accepts_meta_wrapper() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"{{
+                "meta": {{"level_id": 7, "generated_by": "test", "optimal": true, "moves": 2}},
+                "steps": [
+                    {{"key": "Right", "delay_ms": 200}},
+                    {{"key": "Up", "delay_ms": 200}}
+                ]
+            }}"#
+        )
+        .unwrap();
+
+        let directions = load_playback_directions(file.path()).unwrap();
+        assert_eq!(directions, vec![Direction::East, Direction::North]);
+    }
+
+    #[test]
+    fn test_load_playback_directions_rejects_wrapper_without_steps() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"{{"meta": {{"level_id": 7, "generated_by": "test", "optimal": true, "moves": 0}}}}"#
+        )
+        .unwrap();
+
+        let error = format!("{:#}", load_playback_directions(file.path()).unwrap_err());
+        assert!(
+            error.contains(
+                "Failed to parse playback JSON as an object with meta and steps: missing field `steps`"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_load_playback_steps_keeps_delays() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"[{{"key": "Right", "delay_ms": 150}}, {{"key": "Up", "delay_ms": 90}}]"#
+        )
+        .unwrap();
+
+        let steps = load_playback_steps(file.path()).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                PlaybackStep {
+                    direction: Direction::East,
+                    delay_ms: 150
+                },
+                PlaybackStep {
+                    direction: Direction::North,
+                    delay_ms: 90
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scale_delays_divides_by_speed() {
+        let steps = vec![PlaybackStep {
+            direction: Direction::East,
+            delay_ms: 200,
+        }];
+        assert_eq!(scale_delays(&steps, 2.0).unwrap()[0].delay_ms, 100);
+        assert_eq!(scale_delays(&steps, 0.5).unwrap()[0].delay_ms, 400);
+        assert_eq!(scale_delays(&steps, 3.0).unwrap()[0].delay_ms, 67);
+    }
+
+    #[test]
+    fn test_scale_delays_rejects_non_positive_speed() {
+        for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let error = scale_delays(&[], speed).unwrap_err();
+            assert!(error.to_string().contains("must be a positive number"));
+        }
+    }
+
+    #[test]
+    fn test_write_playback_steps_round_trips() {
+        let file = NamedTempFile::new().unwrap();
+        let steps = vec![
+            PlaybackStep {
+                direction: Direction::West,
+                delay_ms: 50,
+            },
+            PlaybackStep {
+                direction: Direction::South,
+                delay_ms: 75,
+            },
+        ];
+
+        write_playback_steps(file.path(), &steps).unwrap();
+        assert_eq!(load_playback_steps(file.path()).unwrap(), steps);
+    }
+
+    #[test]
+    fn test_compact_directions_round_trips_through_short_keys() {
+        let directions = vec![
+            Direction::East,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+            Direction::North,
+        ];
+        let compact = compact_directions(&directions);
+        assert_eq!(compact, "RRDLU");
+
+        let parsed: Vec<Direction> = compact
+            .chars()
+            .map(|ch| parse_key(&ch.to_string()).unwrap())
+            .collect();
+        assert_eq!(parsed, directions);
+    }
+
+    #[test]
+    fn test_replay_script_quotes_paths_and_forwards_arguments() {
+        let script = replay_script(
+            Path::new("/repo/gsnake-levels/Cargo.toml"),
+            Path::new("/repo/levels/easy/it's.json"),
+            Path::new("/repo/playbacks/easy/it's.json"),
+        );
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("--manifest-path '/repo/gsnake-levels/Cargo.toml'"));
+        assert!(script.contains(
+            "  replay '/repo/levels/easy/it'\\''s.json' '/repo/playbacks/easy/it'\\''s.json' \"$@\"\n"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replay_script_passes_exact_arguments_under_sh() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        // Stand-in for cargo that prints each argument NUL-terminated
+        let fake_cargo = temp_dir.path().join("cargo");
+        fs::write(
+            &fake_cargo,
+            "#!/bin/sh\nfor arg in \"$@\"; do printf '%s\\0' \"$arg\"; done\n",
+        )
+        .unwrap();
+        fs::set_permissions(&fake_cargo, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let manifest = Path::new("/repo/Cargo.toml");
+        let level = Path::new("/repo/levels/easy/it's $HOME\nexit 7.json");
+        let playback = Path::new("/repo/playbacks/easy/a b.json");
+        let script = temp_dir.path().join("replay.sh");
+        fs::write(&script, replay_script(manifest, level, playback)).unwrap();
+
+        let path = format!(
+            "{}:{}",
+            temp_dir.path().display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let output = std::process::Command::new("sh")
+            .arg(&script)
+            .arg("--headless")
+            .env("PATH", path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let args: Vec<&str> = stdout.trim_end_matches('\0').split('\0').collect();
+        assert_eq!(
+            args,
+            vec![
+                "run",
+                "--quiet",
+                "--manifest-path",
+                "/repo/Cargo.toml",
+                "--bin",
+                "gsnake-levels",
+                "--",
+                "replay",
+                "/repo/levels/easy/it's $HOME\nexit 7.json",
+                "/repo/playbacks/easy/a b.json",
+                "--headless",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_frames_ends_on_level_complete() {
+        let level =
+            crate::solver::load_level(&crate::test_fixtures::first_easy_level_fixture()).unwrap();
+        let mut directions = crate::solver::solve_level(level.clone(), 500).unwrap();
+        let moves = directions.len();
+        directions.push(Direction::North);
+
+        let frames = replay_frames(&level, &directions).unwrap();
+        assert_eq!(frames.len(), moves + 1);
+        assert_eq!(
+            frames.last().unwrap().state.status,
+            GameStatus::LevelComplete
+        );
+        assert_eq!(frames[0].state.status, GameStatus::Playing);
+    }
 }
@@ -0,0 +1,125 @@
+use crate::playback::{compact_directions, direction_key, load_playback_directions};
+use anyhow::Result;
+use gsnake_core::Direction;
+use std::path::Path;
+
+/// Where two move sequences agree and where they part ways
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackDiff {
+    pub len_a: usize,
+    pub len_b: usize,
+    /// Number of leading moves both sequences share
+    pub common_prefix: usize,
+    /// Index of the first differing move, or where the shorter sequence ends; `None` when the
+    /// sequences are identical
+    pub first_divergence: Option<usize>,
+    /// Moves that differ at the same index within the overlapping length
+    pub differing_steps: usize,
+}
+
+impl PlaybackDiff {
+    /// `len_b - len_a`; negative when B is the shorter solution
+    pub fn length_delta(&self) -> isize {
+        self.len_b as isize - self.len_a as isize
+    }
+}
+
+pub fn run_diff_playback(a: &Path, b: &Path) -> Result<()> {
+    let directions_a = load_playback_directions(a)?;
+    let directions_b = load_playback_directions(b)?;
+    println!("A: {}\nB: {}", a.display(), b.display());
+    println!("{}", format_diff(&directions_a, &directions_b));
+    Ok(())
+}
+
+pub fn diff_directions(a: &[Direction], b: &[Direction]) -> PlaybackDiff {
+    let common_prefix = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let identical = common_prefix == a.len() && a.len() == b.len();
+    PlaybackDiff {
+        len_a: a.len(),
+        len_b: b.len(),
+        common_prefix,
+        first_divergence: (!identical).then_some(common_prefix),
+        differing_steps: a.iter().zip(b).filter(|(a, b)| a != b).count(),
+    }
+}
+
+/// Counts, the first divergence and both sequences in compact form with a `|` after the
+/// shared prefix
+fn format_diff(a: &[Direction], b: &[Direction]) -> String {
+    let diff = diff_directions(a, b);
+    let mut output = format!(
+        "Moves: A {}, B {} ({:+})\nCommon prefix: {} move(s)\nDiffering steps: {} of {} overlapping",
+        diff.len_a,
+        diff.len_b,
+        diff.length_delta(),
+        diff.common_prefix,
+        diff.differing_steps,
+        diff.len_a.min(diff.len_b)
+    );
+
+    let Some(index) = diff.first_divergence else {
+        output.push_str("\nPlaybacks are identical");
+        return output;
+    };
+    let describe = |directions: &[Direction]| {
+        directions
+            .get(index)
+            .map_or("(end)", |direction| direction_key(*direction))
+    };
+    output.push_str(&format!(
+        "\nFirst divergence at move {} (A {}, B {})",
+        index + 1,
+        describe(a),
+        describe(b)
+    ));
+    for (label, directions) in [("A", a), ("B", b)] {
+        let (prefix, rest) = directions.split_at(diff.common_prefix);
+        output.push_str(&format!(
+            "\n  {label}: {}|{}",
+            compact_directions(prefix),
+            compact_directions(rest)
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gsnake_core::Direction::{East, North, South, West};
+
+    #[test]
+    fn test_diff_directions_reports_first_divergence_and_length_delta() {
+        let a = [East, East, South, South, West, North];
+        let b = [East, East, North, West];
+
+        let diff = diff_directions(&a, &b);
+        assert_eq!(diff.common_prefix, 2);
+        assert_eq!(diff.first_divergence, Some(2));
+        assert_eq!(diff.length_delta(), -2);
+        assert_eq!(diff.differing_steps, 2);
+        assert_eq!(
+            format_diff(&a, &b),
+            "Moves: A 6, B 4 (-2)\nCommon prefix: 2 move(s)\nDiffering steps: 2 of 4 overlapping\n\
+             First divergence at move 3 (A Down, B Up)\n  A: RR|DDLU\n  B: RR|UL"
+        );
+    }
+
+    #[test]
+    fn test_diff_directions_handles_prefix_and_identical_playbacks() {
+        let a = [East, South];
+        let b = [East, South, South];
+
+        let diff = diff_directions(&a, &b);
+        assert_eq!(diff.common_prefix, 2);
+        assert_eq!(diff.first_divergence, Some(2));
+        assert_eq!(diff.differing_steps, 0);
+        assert!(format_diff(&a, &b).contains("First divergence at move 3 (A (end), B Down)"));
+
+        let same = diff_directions(&a, &a);
+        assert_eq!(same.first_divergence, None);
+        assert_eq!(same.length_delta(), 0);
+        assert!(format_diff(&a, &a).ends_with("Playbacks are identical"));
+    }
+}
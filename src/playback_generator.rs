@@ -1,8 +1,24 @@
-use crate::{levels, solver::solve_level_to_playback};
+use crate::{
+    levels,
+    playback::{load_playback_directions, DelayStrategy},
+    solver::{
+        load_level, solve_level_to_playback, solve_level_to_playback_with_options, NoSolution,
+        SolveStrategy, SolveTimeout,
+    },
+    sync_progress::{content_hash, progress_key, SyncProgress},
+    verify::{verify_level, verify_run},
+};
 use anyhow::{Context, Result};
 use std::{
+    collections::BTreeMap,
     fs,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
 };
 
 /// Result of playback generation for a single level
@@ -14,14 +30,84 @@ pub struct PlaybackResult {
     pub playback_path: PathBuf,
     pub solved: bool,
     pub error: Option<String>,
+    /// Why the level is unsolved, when the failure could be classified
+    pub error_kind: Option<SolveErrorKind>,
+}
+
+/// Machine-readable category of a playback generation failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SolveErrorKind {
+    /// The level JSON could not be parsed
+    Parse,
+    /// The search ran out of states, but some branches were cut at `max_depth`
+    DepthLimit,
+    /// The solver hit its wall-clock timeout
+    Timeout,
+    /// The search exhausted every state without completing the level
+    Unsolvable,
+    /// Reading the level or writing the playback failed
+    Io,
+}
+
+impl SolveErrorKind {
+    /// Classifies a generation error from the typed errors in its chain
+    pub fn from_error(err: &anyhow::Error) -> Option<Self> {
+        if err.downcast_ref::<SolveTimeout>().is_some() {
+            return Some(Self::Timeout);
+        }
+        if let Some(no_solution) = err.downcast_ref::<NoSolution>() {
+            return Some(if no_solution.stats.hit_depth_limit {
+                Self::DepthLimit
+            } else {
+                Self::Unsolvable
+            });
+        }
+        err.chain().find_map(|cause| {
+            if cause.is::<serde_json::Error>() {
+                Some(Self::Parse)
+            } else if cause.is::<std::io::Error>() {
+                Some(Self::Io)
+            } else {
+                None
+            }
+        })
+    }
 }
 
 /// Generate playback for a single level file
+///
+/// With `with_meta`, the playback is written in the wrapped `{ "meta", "steps" }` form. With
+/// `verify_after`, the written playback is replayed against the level and the result is marked
+/// unsolved if it does not complete it.
 #[allow(dead_code)]
 pub fn generate_playback_for_level(
     level_path: &Path,
     playback_path: &Path,
     max_depth: usize,
+    with_meta: bool,
+    verify_after: bool,
+) -> Result<PlaybackResult> {
+    generate_playback_with_writer(level_path, playback_path, verify_after, || {
+        if !with_meta {
+            return solve_level_to_playback(level_path, playback_path, max_depth);
+        }
+        solve_level_to_playback_with_options(
+            level_path,
+            playback_path,
+            max_depth,
+            SolveStrategy::Bfs,
+            &DelayStrategy::default(),
+            true,
+        )
+        .map(|_| ())
+    })
+}
+
+fn generate_playback_with_writer(
+    level_path: &Path,
+    playback_path: &Path,
+    verify_after: bool,
+    write_playback: impl FnOnce() -> Result<()>,
 ) -> Result<PlaybackResult> {
     let level_id = level_path
         .file_stem()
@@ -29,10 +115,17 @@ pub fn generate_playback_for_level(
         .ok_or_else(|| anyhow::anyhow!("Invalid level filename"))?
         .to_string();
 
-    let playback_result = solve_level_to_playback(level_path, playback_path, max_depth);
-    let (solved, error) = match playback_result {
-        Ok(_) => (true, None),
-        Err(err) => (false, Some(format!("{err:#}"))),
+    let mut playback_result = write_playback();
+    if verify_after && playback_result.is_ok() {
+        playback_result = self_check_playback(level_path, playback_path);
+    }
+    let (solved, error, error_kind) = match playback_result {
+        Ok(()) => (true, None, None),
+        Err(err) => (
+            false,
+            Some(failure_reason(&err)),
+            SolveErrorKind::from_error(&err),
+        ),
     };
 
     Ok(PlaybackResult {
@@ -41,17 +134,108 @@ pub fn generate_playback_for_level(
         playback_path: playback_path.to_path_buf(),
         solved,
         error,
+        error_kind,
     })
 }
 
+/// Replays a just-written playback, so a solver/engine divergence is caught at generation time
+fn self_check_playback(level_path: &Path, playback_path: &Path) -> Result<()> {
+    let level = load_level(level_path)?;
+    let directions = load_playback_directions(playback_path)?;
+    verify_run(level, &directions).map(|_| ()).with_context(|| {
+        format!(
+            "Self-check failed: {} does not replay to completion",
+            playback_path.display()
+        )
+    })
+}
+
+/// Error text for `PlaybackResult::error`, prefixed with `timeout:` or `unsolvable:`
+/// when the solver gave up for one of those reasons
+fn failure_reason(err: &anyhow::Error) -> String {
+    if err.downcast_ref::<SolveTimeout>().is_some() {
+        format!("timeout: {err:#}")
+    } else if err.downcast_ref::<NoSolution>().is_some() {
+        format!("unsolvable: {err:#}")
+    } else {
+        format!("{err:#}")
+    }
+}
+
+/// Options for generating the playbacks of a whole difficulty
+#[derive(Debug, Clone, Copy)]
+pub struct PlaybackOptions {
+    pub max_depth: usize,
+    /// Replay each freshly written playback and mark the level unsolved if it fails
+    pub verify_after: bool,
+    /// Re-solve levels even when their playback is newer than the level JSON
+    pub force: bool,
+    /// Print a `[difficulty n/total]` line to stderr for every level
+    pub show_progress: bool,
+    /// Solve at most this many levels at once; defaults to the available parallelism
+    pub jobs: Option<NonZeroUsize>,
+}
+
+impl Default for PlaybackOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 500,
+            verify_after: false,
+            force: false,
+            show_progress: false,
+            jobs: None,
+        }
+    }
+}
+
+/// A level that needs solving, with its checkpoint key and content hash
+struct SolveJob {
+    position: String,
+    filename: String,
+    level_path: PathBuf,
+    playback_path: PathBuf,
+    key: String,
+    hash: String,
+}
+
+/// Messages from the solver workers back to the thread that owns the checkpoint
+enum SolveEvent {
+    Started(usize),
+    Finished(usize, Result<PlaybackResult>),
+}
+
 /// Generate playbacks for all levels in a difficulty directory
+///
+/// With a `progress` checkpoint, levels it already records as done (whose JSON is unchanged
+/// and, if solved, whose playback still exists) are skipped, and every newly processed level
+/// is recorded in it. Unless `force`
+/// is set, a level whose playback is newer than its JSON and still verifies is not re-solved;
+/// `force` also ignores the checkpoint.
+/// The remaining levels are solved on up to `jobs` threads; results are sorted by level path.
 #[allow(dead_code)]
 pub fn generate_playbacks_for_difficulty(
     levels_dir: &Path,
     playbacks_dir: &Path,
-    max_depth: usize,
+    options: PlaybackOptions,
+    progress: Option<&mut SyncProgress>,
+) -> Result<Vec<PlaybackResult>> {
+    generate_playbacks_with_reporter(levels_dir, playbacks_dir, options, progress, |line| {
+        if options.show_progress {
+            eprintln!("{line}");
+        }
+    })
+}
+
+/// `generate_playbacks_for_difficulty`, handing each per-level progress line to `report`
+fn generate_playbacks_with_reporter(
+    levels_dir: &Path,
+    playbacks_dir: &Path,
+    options: PlaybackOptions,
+    mut progress: Option<&mut SyncProgress>,
+    mut report: impl FnMut(&str),
 ) -> Result<Vec<PlaybackResult>> {
     let mut results = Vec::new();
+    let mut jobs = Vec::new();
     let mut level_paths = Vec::new();
 
     // Scan for JSON files
@@ -68,51 +252,201 @@ pub fn generate_playbacks_for_difficulty(
     }
 
     level_paths.sort();
+    let mut skipped = 0;
+    let mut up_to_date = 0;
+    let difficulty = levels_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let total = level_paths.len();
 
-    for path in level_paths {
+    for (index, path) in level_paths.into_iter().enumerate() {
         let filename = path
             .file_name()
             .and_then(|s| s.to_str())
             .ok_or_else(|| anyhow::anyhow!("Invalid filename"))?;
+        let position = format!("[{difficulty} {}/{total}]", index + 1);
 
         let playback_path = playbacks_dir.join(filename);
+        let key = progress_key(&path);
+        let hash = content_hash(
+            &fs::read(&path)
+                .with_context(|| format!("Failed to read level file: {}", path.display()))?,
+        );
 
-        match generate_playback_for_level(&path, &playback_path, max_depth) {
-            Ok(result) => {
-                if !result.solved {
-                    eprintln!(
-                        "Warning: Failed to solve level {} - {}",
-                        result.level_id,
-                        result.error.as_deref().unwrap_or("unknown error")
-                    );
-                }
-                results.push(result);
-            },
-            Err(e) => {
-                eprintln!("Error processing level {}: {}", filename, e);
-            },
+        // A checkpointed solve only counts while its playback is still on disk.
+        if let Some(solved) = progress
+            .as_deref()
+            .filter(|_| !options.force)
+            .and_then(|progress| progress.completed(&key, &hash))
+            .filter(|&solved| !solved || playback_path.exists())
+        {
+            skipped += 1;
+            report(&format!(
+                "{position} skipping {filename} (done in a previous run)"
+            ));
+            results.push(PlaybackResult {
+                level_id: path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(filename)
+                    .to_string(),
+                level_path: path.clone(),
+                playback_path,
+                solved,
+                error: (!solved).then(|| "Unsolved in a previous sync run".to_string()),
+                error_kind: None,
+            });
+            continue;
+        }
+
+        if !options.force && playback_is_up_to_date(&path, &playback_path) {
+            up_to_date += 1;
+            report(&format!(
+                "{position} keeping {filename} (playback up to date)"
+            ));
+            if let Some(progress) = progress.as_deref_mut() {
+                progress.record(key, hash, true)?;
+            }
+            results.push(PlaybackResult {
+                level_id: path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(filename)
+                    .to_string(),
+                level_path: path.clone(),
+                playback_path,
+                solved: true,
+                error: None,
+                error_kind: None,
+            });
+            continue;
+        }
+
+        jobs.push(SolveJob {
+            position,
+            filename: filename.to_string(),
+            level_path: path.clone(),
+            playback_path,
+            key,
+            hash,
+        });
+    }
+
+    let workers = options
+        .jobs
+        .map(NonZeroUsize::get)
+        .or_else(|| thread::available_parallelism().ok().map(NonZeroUsize::get))
+        .unwrap_or(1)
+        .min(jobs.len())
+        .max(1);
+    let next_job = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    // Workers write distinct playback files; the checkpoint is only touched from this thread.
+    thread::scope(|scope| -> Result<()> {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (jobs, next_job) = (&jobs, &next_job);
+            scope.spawn(move || loop {
+                let index = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else {
+                    break;
+                };
+                let _ = sender.send(SolveEvent::Started(index));
+                let result = generate_playback_for_level(
+                    &job.level_path,
+                    &job.playback_path,
+                    options.max_depth,
+                    false,
+                    options.verify_after,
+                );
+                let _ = sender.send(SolveEvent::Finished(index, result));
+            });
+        }
+        drop(sender);
+
+        for event in receiver {
+            match event {
+                SolveEvent::Started(index) => {
+                    let job = &jobs[index];
+                    report(&format!("{} solving {}...", job.position, job.filename));
+                },
+                SolveEvent::Finished(index, Ok(result)) => {
+                    let job = &jobs[index];
+                    if let Some(progress) = progress.as_deref_mut() {
+                        progress.record(job.key.clone(), job.hash.clone(), result.solved)?;
+                    }
+                    if !result.solved {
+                        eprintln!(
+                            "Warning: Failed to solve level {} - {}",
+                            result.level_id,
+                            result.error.as_deref().unwrap_or("unknown error")
+                        );
+                    }
+                    results.push(result);
+                },
+                SolveEvent::Finished(index, Err(e)) => {
+                    eprintln!("Error processing level {}: {}", jobs[index].filename, e);
+                },
+            }
         }
+        Ok(())
+    })?;
+    results.sort_by(|a, b| a.level_path.cmp(&b.level_path));
+
+    if skipped > 0 {
+        println!(
+            "  {}: skipped {} levels completed in a previous run",
+            levels_dir.display(),
+            skipped
+        );
+    }
+    if up_to_date > 0 {
+        println!(
+            "  {}: kept {} playbacks newer than their level",
+            levels_dir.display(),
+            up_to_date
+        );
     }
 
     Ok(results)
 }
 
-/// Generate playbacks for all difficulty levels (easy, medium, hard)
+/// Whether the playback was written after the level JSON was last modified and still
+/// completes it; anything unreadable counts as stale so the level gets re-solved
+fn playback_is_up_to_date(level_path: &Path, playback_path: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified());
+    match (modified(level_path), modified(playback_path)) {
+        (Ok(level_modified), Ok(playback_modified)) if playback_modified > level_modified => {
+            verify_level(level_path, playback_path).is_ok()
+        },
+        _ => false,
+    }
+}
+
+/// Generate playbacks for the default difficulties and any custom ones in `levels_root`
 #[allow(dead_code)]
 pub fn generate_all_playbacks(
     levels_root: &Path,
     playbacks_root: &Path,
-    max_depth: usize,
+    options: PlaybackOptions,
+    mut progress: Option<&mut SyncProgress>,
 ) -> Result<Vec<PlaybackResult>> {
     let mut all_results = Vec::new();
 
-    for difficulty in ["easy", "medium", "hard"] {
-        let levels_dir = levels_root.join(difficulty);
-        let playbacks_dir = playbacks_root.join(difficulty);
+    for difficulty in levels::catalog_difficulties(levels_root) {
+        let levels_dir = levels_root.join(&difficulty);
+        let playbacks_dir = playbacks_root.join(&difficulty);
 
         if levels_dir.exists() {
-            let results = generate_playbacks_for_difficulty(&levels_dir, &playbacks_dir, max_depth)
-                .with_context(|| format!("Failed to generate playbacks for {}", difficulty))?;
+            let results = generate_playbacks_for_difficulty(
+                &levels_dir,
+                &playbacks_dir,
+                options,
+                progress.as_deref_mut(),
+            )
+            .with_context(|| format!("Failed to generate playbacks for {}", difficulty))?;
             all_results.extend(results);
         }
     }
@@ -137,6 +471,21 @@ pub fn get_solved_unsolved_lists(results: &[PlaybackResult]) -> (Vec<String>, Ve
     (solved, unsolved)
 }
 
+/// Unsolved level IDs grouped by failure kind; unclassified failures are left out
+#[allow(dead_code)]
+pub fn get_unsolved_by_kind(results: &[PlaybackResult]) -> BTreeMap<SolveErrorKind, Vec<String>> {
+    let mut breakdown: BTreeMap<SolveErrorKind, Vec<String>> = BTreeMap::new();
+    for result in results.iter().filter(|result| !result.solved) {
+        if let Some(kind) = result.error_kind {
+            breakdown
+                .entry(kind)
+                .or_default()
+                .push(result.level_id.clone());
+        }
+    }
+    breakdown
+}
+
 /// Update levels.toml solved status based on playback generation results
 #[allow(dead_code)]
 pub fn update_solved_status_from_results(results: &[PlaybackResult]) -> Result<()> {
@@ -154,30 +503,20 @@ pub fn update_solved_status_from_results(results: &[PlaybackResult]) -> Result<(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::first_easy_level_fixture;
     use serde_json::Value;
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
 
-    fn first_easy_level_fixture() -> PathBuf {
-        let mut fixtures: Vec<PathBuf> = fs::read_dir("levels/easy")
-            .unwrap()
-            .filter_map(|entry| {
-                let path = entry.ok()?.path();
-                (path.extension().and_then(|ext| ext.to_str()) == Some("json")).then_some(path)
-            })
-            .collect();
-        fixtures.sort();
-        fixtures.into_iter().next().expect("Expected easy fixture")
-    }
-
     #[test]
     fn test_generate_playback_for_level_writes_compatible_json() {
         let temp_dir = TempDir::new().unwrap();
         let level_path = first_easy_level_fixture();
         let playback_path = temp_dir.path().join("playbacks/level_001.json");
 
-        let result = generate_playback_for_level(&level_path, &playback_path, 50).unwrap();
+        let result =
+            generate_playback_for_level(&level_path, &playback_path, 50, false, false).unwrap();
         assert!(result.solved);
         assert!(result.error.is_none());
         assert!(playback_path.exists());
@@ -191,6 +530,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generate_playback_for_level_with_meta_writes_wrapped_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = first_easy_level_fixture();
+        let playback_path = temp_dir.path().join("playbacks/level_001.json");
+
+        let result =
+            generate_playback_for_level(&level_path, &playback_path, 50, true, false).unwrap();
+        assert!(result.solved);
+
+        let playback: Value =
+            serde_json::from_str(&fs::read_to_string(&playback_path).unwrap()).unwrap();
+        let steps = playback["steps"].as_array().expect("Expected steps array");
+        assert_eq!(playback["meta"]["moves"].as_u64(), Some(steps.len() as u64));
+        assert_eq!(playback["meta"]["optimal"].as_bool(), Some(true));
+        assert!(playback["meta"]["generated_by"]
+            .as_str()
+            .unwrap()
+            .starts_with("gsnake-levels solver"));
+
+        let directions = crate::playback::load_playback_directions(&playback_path).unwrap();
+        assert_eq!(directions.len(), steps.len());
+    }
+
     #[test]
     fn test_generate_playback_for_level_returns_unsolved_on_parse_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -198,13 +561,99 @@ mod tests {
         let playback_path = temp_dir.path().join("playbacks/broken_level.json");
         fs::write(&level_path, "{not-json}").unwrap();
 
-        let result = generate_playback_for_level(&level_path, &playback_path, 50).unwrap();
+        let result =
+            generate_playback_for_level(&level_path, &playback_path, 50, false, false).unwrap();
         assert!(!result.solved);
         let error = result.error.expect("Expected error message");
         assert!(error.contains("Failed to parse level JSON"));
+        assert_eq!(result.error_kind, Some(SolveErrorKind::Parse));
         assert!(!playback_path.exists());
     }
 
+    #[test]
+    fn test_walled_off_level_error_kind_depends_on_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("walled.json");
+        let level = serde_json::json!({
+            "id": 1,
+            "name": "Walled",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 1 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [{ "x": 2, "y": 0 }],
+            "food": [],
+            "exit": { "x": 4, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        });
+        fs::write(&level_path, level.to_string()).unwrap();
+        let playback_path = temp_dir.path().join("playbacks/walled.json");
+
+        let exhausted =
+            generate_playback_for_level(&level_path, &playback_path, 50, false, false).unwrap();
+        assert_eq!(exhausted.error_kind, Some(SolveErrorKind::Unsolvable));
+
+        let cut_off =
+            generate_playback_for_level(&level_path, &playback_path, 0, false, false).unwrap();
+        assert_eq!(cut_off.error_kind, Some(SolveErrorKind::DepthLimit));
+        assert!(cut_off.error.as_deref().unwrap().contains("depth limit"));
+
+        let breakdown = get_unsolved_by_kind(&[exhausted, cut_off]);
+        assert_eq!(
+            breakdown.get(&SolveErrorKind::Unsolvable),
+            Some(&vec!["walled".to_string()])
+        );
+        assert_eq!(breakdown.len(), 2);
+    }
+
+    #[test]
+    fn test_generate_playback_self_check_catches_corrupted_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = first_easy_level_fixture();
+        let playback_path = temp_dir.path().join("level_001.json");
+        // Drop the final move, as a solver that disagrees with the engine would.
+        let write_truncated = || {
+            let mut directions = crate::solver::solve_level(load_level(&level_path)?, 50)?;
+            directions.pop();
+            crate::playback::write_playback_directions(&playback_path, &directions, 1)
+        };
+
+        let result =
+            generate_playback_with_writer(&level_path, &playback_path, true, write_truncated)
+                .unwrap();
+        assert!(!result.solved);
+        let error = result.error.expect("Expected self-check error");
+        assert!(error.contains("Self-check failed"), "{error}");
+
+        let result =
+            generate_playback_for_level(&level_path, &playback_path, 50, false, true).unwrap();
+        assert!(result.solved, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_failure_reason_distinguishes_timeout_and_unsolvable() {
+        let timeout = anyhow::Error::from(SolveTimeout {
+            elapsed: std::time::Duration::from_millis(12),
+        })
+        .context("No solution found within depth 500");
+        assert_eq!(
+            failure_reason(&timeout),
+            "timeout: No solution found within depth 500: Solver timed out after 12ms"
+        );
+
+        let unsolvable = anyhow::Error::from(NoSolution {
+            stats: Default::default(),
+        });
+        assert_eq!(failure_reason(&unsolvable), "unsolvable: No solution found");
+
+        let other = anyhow::anyhow!("Failed to parse level JSON");
+        assert_eq!(failure_reason(&other), "Failed to parse level JSON");
+    }
+
     #[test]
     fn test_get_solved_unsolved_lists() {
         let results = vec![
@@ -214,6 +663,7 @@ mod tests {
                 playback_path: PathBuf::from("level1-playback.json"),
                 solved: true,
                 error: None,
+                error_kind: None,
             },
             PlaybackResult {
                 level_id: "level2".to_string(),
@@ -221,6 +671,7 @@ mod tests {
                 playback_path: PathBuf::from("level2-playback.json"),
                 solved: false,
                 error: Some("No solution found".to_string()),
+                error_kind: Some(SolveErrorKind::Unsolvable),
             },
             PlaybackResult {
                 level_id: "level3".to_string(),
@@ -228,6 +679,7 @@ mod tests {
                 playback_path: PathBuf::from("level3-playback.json"),
                 solved: true,
                 error: None,
+                error_kind: None,
             },
         ];
 
@@ -258,6 +710,7 @@ mod tests {
                 playback_path: PathBuf::from("level1-playback.json"),
                 solved: true,
                 error: None,
+                error_kind: None,
             },
             PlaybackResult {
                 level_id: "level2".to_string(),
@@ -265,6 +718,7 @@ mod tests {
                 playback_path: PathBuf::from("level2-playback.json"),
                 solved: true,
                 error: None,
+                error_kind: None,
             },
         ];
 
@@ -283,6 +737,7 @@ mod tests {
                 playback_path: PathBuf::from("level1-playback.json"),
                 solved: false,
                 error: Some("No solution".to_string()),
+                error_kind: Some(SolveErrorKind::Unsolvable),
             },
             PlaybackResult {
                 level_id: "level2".to_string(),
@@ -290,6 +745,7 @@ mod tests {
                 playback_path: PathBuf::from("level2-playback.json"),
                 solved: false,
                 error: Some("Too complex".to_string()),
+                error_kind: Some(SolveErrorKind::DepthLimit),
             },
         ];
 
@@ -311,11 +767,274 @@ mod tests {
         // Create a non-JSON file
         fs::write(levels_dir.join("readme.txt"), "test").unwrap();
 
-        let results = generate_playbacks_for_difficulty(&levels_dir, &playbacks_dir, 500).unwrap();
+        let results = generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions::default(),
+            None,
+        )
+        .unwrap();
 
         assert_eq!(results.len(), 0);
     }
 
+    #[test]
+    fn test_generate_playbacks_for_difficulty_skips_checkpointed_levels() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_dir = temp_dir.path().join("easy");
+        let playbacks_dir = temp_dir.path().join("playbacks");
+        fs::create_dir_all(&levels_dir).unwrap();
+
+        let contents = "{\"id\": 1}";
+        fs::write(levels_dir.join("level_001.json"), contents).unwrap();
+
+        let mut progress = SyncProgress::fresh(temp_dir.path());
+        progress
+            .record(
+                "easy/level_001.json".to_string(),
+                content_hash(contents.as_bytes()),
+                true,
+            )
+            .unwrap();
+
+        fs::create_dir_all(&playbacks_dir).unwrap();
+        fs::write(playbacks_dir.join("level_001.json"), "[]").unwrap();
+
+        let results = generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions::default(),
+            Some(&mut progress),
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].level_id, "level_001");
+        assert!(results[0].solved);
+        assert_eq!(
+            fs::read_to_string(playbacks_dir.join("level_001.json")).unwrap(),
+            "[]"
+        );
+
+        // Once the playback is deleted, the unchanged level is solved again (and fails to parse).
+        fs::remove_file(playbacks_dir.join("level_001.json")).unwrap();
+        let regenerated = generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions::default(),
+            Some(&mut progress),
+        )
+        .unwrap();
+        assert_eq!(regenerated.len(), 1);
+        assert_eq!(regenerated[0].error_kind, Some(SolveErrorKind::Parse));
+
+        // --force ignores the checkpoint, so the stub level is re-solved (and fails to parse).
+        let forced = generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions {
+                force: true,
+                ..PlaybackOptions::default()
+            },
+            Some(&mut progress),
+        )
+        .unwrap();
+        assert_eq!(forced.len(), 1);
+        assert!(!forced[0].solved);
+        assert_eq!(forced[0].error_kind, Some(SolveErrorKind::Parse));
+    }
+
+    #[test]
+    fn test_generate_playbacks_for_difficulty_regenerates_only_touched_levels() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_dir = temp_dir.path().join("easy");
+        let playbacks_dir = temp_dir.path().join("playbacks");
+        fs::create_dir_all(&levels_dir).unwrap();
+        fs::create_dir_all(&playbacks_dir).unwrap();
+
+        // Padded playbacks still verify, but the solver would never write them.
+        let padded = serde_json::to_string(&vec![
+            serde_json::json!({ "key": "Right", "delay_ms": 1 });
+            6
+        ])
+        .unwrap();
+        let now = std::time::SystemTime::now();
+        let minutes_ago = |minutes: u64| now - std::time::Duration::from_secs(60 * minutes);
+        let set_modified = |path: &Path, time| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap();
+        };
+        for file in ["touched.json", "untouched.json"] {
+            let level = serde_json::json!({
+                "id": 1,
+                "name": file,
+                "difficulty": "easy",
+                "gridSize": { "width": 5, "height": 1 },
+                "snake": [{ "x": 0, "y": 0 }],
+                "snakeDirection": "East",
+                "obstacles": [],
+                "food": [],
+                "exit": { "x": 4, "y": 0 },
+                "floatingFood": [],
+                "fallingFood": [],
+                "stones": [],
+                "spikes": [],
+                "totalFood": 0
+            });
+            fs::write(levels_dir.join(file), level.to_string()).unwrap();
+            fs::write(playbacks_dir.join(file), &padded).unwrap();
+            set_modified(&levels_dir.join(file), minutes_ago(10));
+            set_modified(&playbacks_dir.join(file), minutes_ago(5));
+        }
+        set_modified(&levels_dir.join("touched.json"), now);
+
+        let results = generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions {
+                max_depth: 50,
+                ..PlaybackOptions::default()
+            },
+            None,
+        )
+        .unwrap();
+        assert!(results.iter().all(|result| result.solved));
+
+        let touched =
+            crate::playback::load_playback_directions(&playbacks_dir.join("touched.json")).unwrap();
+        assert_eq!(touched.len(), 4);
+        assert_eq!(
+            fs::read_to_string(playbacks_dir.join("untouched.json")).unwrap(),
+            padded
+        );
+
+        generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions {
+                max_depth: 50,
+                force: true,
+                ..PlaybackOptions::default()
+            },
+            None,
+        )
+        .unwrap();
+        let forced =
+            crate::playback::load_playback_directions(&playbacks_dir.join("untouched.json"))
+                .unwrap();
+        assert_eq!(forced.len(), 4);
+    }
+
+    #[test]
+    fn test_generate_playbacks_reports_one_progress_line_per_level() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_dir = temp_dir.path().join("easy");
+        let playbacks_dir = temp_dir.path().join("playbacks");
+        fs::create_dir_all(&levels_dir).unwrap();
+        for file in ["a.json", "b.json", "c.json"] {
+            fs::write(levels_dir.join(file), "{not-json}").unwrap();
+        }
+
+        let mut lines = Vec::new();
+        let results = generate_playbacks_with_reporter(
+            &levels_dir,
+            &playbacks_dir,
+            PlaybackOptions {
+                jobs: NonZeroUsize::new(1),
+                ..PlaybackOptions::default()
+            },
+            None,
+            |line| lines.push(line.to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            lines,
+            vec![
+                "[easy 1/3] solving a.json...",
+                "[easy 2/3] solving b.json...",
+                "[easy 3/3] solving c.json...",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parallel_generation_matches_sequential() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_dir = temp_dir.path().join("easy");
+        fs::create_dir_all(&levels_dir).unwrap();
+        for (file, exit_x) in [("a.json", 4), ("b.json", 2), ("c.json", 3), ("d.json", 9)] {
+            let level = serde_json::json!({
+                "id": 1,
+                "name": file,
+                "difficulty": "easy",
+                "gridSize": { "width": 5, "height": 1 },
+                "snake": [{ "x": 0, "y": 0 }],
+                "snakeDirection": "East",
+                "obstacles": [],
+                "food": [],
+                "exit": { "x": exit_x, "y": 0 },
+                "floatingFood": [],
+                "fallingFood": [],
+                "stones": [],
+                "spikes": [],
+                "totalFood": 0
+            });
+            fs::write(levels_dir.join(file), level.to_string()).unwrap();
+        }
+        fs::write(levels_dir.join("e.json"), "{not-json}").unwrap();
+
+        let generate = |jobs: usize, playbacks_dir: &Path| {
+            let results = generate_playbacks_for_difficulty(
+                &levels_dir,
+                playbacks_dir,
+                PlaybackOptions {
+                    max_depth: 20,
+                    jobs: NonZeroUsize::new(jobs),
+                    ..PlaybackOptions::default()
+                },
+                None,
+            )
+            .unwrap();
+            let mut files: Vec<_> = fs::read_dir(playbacks_dir)
+                .unwrap()
+                .map(|entry| {
+                    let path = entry.unwrap().path();
+                    (
+                        path.file_name().unwrap().to_owned(),
+                        fs::read_to_string(&path).unwrap(),
+                    )
+                })
+                .collect();
+            files.sort();
+            let solved: Vec<_> = results
+                .iter()
+                .map(|result| (result.level_id.clone(), result.solved))
+                .collect();
+            (solved, files)
+        };
+
+        let sequential = generate(1, &temp_dir.path().join("sequential"));
+        let parallel = generate(4, &temp_dir.path().join("parallel"));
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            sequential.0,
+            vec![
+                ("a".to_string(), true),
+                ("b".to_string(), true),
+                ("c".to_string(), true),
+                ("d".to_string(), false),
+                ("e".to_string(), false),
+            ]
+        );
+        assert_eq!(sequential.1.len(), 3);
+    }
+
     #[test]
     fn test_generate_all_playbacks_missing_difficulty_dirs() {
         let temp_dir = TempDir::new().unwrap();
@@ -324,7 +1043,13 @@ mod tests {
 
         // Don't create difficulty directories
 
-        let results = generate_all_playbacks(&levels_root, &playbacks_root, 500).unwrap();
+        let results = generate_all_playbacks(
+            &levels_root,
+            &playbacks_root,
+            PlaybackOptions::default(),
+            None,
+        )
+        .unwrap();
 
         // Should succeed but return empty results
         assert_eq!(results.len(), 0);
@@ -380,6 +1105,7 @@ mod tests {
                 playback_path: PathBuf::from("level1-playback.json"),
                 solved: true,
                 error: None,
+                error_kind: None,
             },
             PlaybackResult {
                 level_id: "level2".to_string(),
@@ -387,6 +1113,7 @@ mod tests {
                 playback_path: PathBuf::from("level2-playback.json"),
                 solved: false,
                 error: Some("No solution found".to_string()),
+                error_kind: Some(SolveErrorKind::Unsolvable),
             },
         ];
 
@@ -1,6 +1,16 @@
+use crate::headless::render_board_bordered;
+use crate::playback::{load_playback_steps, replay_engine, scale_delays, write_playback_steps};
+use crate::solver::load_level;
 use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use gsnake_core::{engine::GameEngine, LevelDefinition};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::{thread, time::Duration};
+
+/// ANSI sequence that clears the terminal and moves the cursor to the top-left corner
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
 
 pub fn run_replay(level: &Path, playback: &Path) -> Result<()> {
     let status = Command::new("cargo")
@@ -24,20 +34,86 @@ pub fn run_replay(level: &Path, playback: &Path) -> Result<()> {
     }
 }
 
-pub fn run_render(level: &Path, playback: &Path) -> Result<()> {
-    ensure_command("asciinema")?;
-    ensure_svg_term()?;
+/// Output format of `render`, after the asciinema recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum RenderFormat {
+    #[default]
+    Svg,
+    Gif,
+}
+
+impl RenderFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            RenderFormat::Svg => "svg",
+            RenderFormat::Gif => "gif",
+        }
+    }
+}
+
+/// Animates a playback in the terminal without gsnake-cli, waiting each step's `delay_ms`
+/// before showing the board after that move.
+///
+/// Only the screen is cleared between frames; the cursor is never hidden, so a replay
+/// interrupted with Ctrl-C leaves the terminal as it found it.
+pub fn run_replay_native(level_path: &Path, playback_path: &Path) -> Result<()> {
+    let level = load_level(level_path)
+        .with_context(|| format!("Failed to load level: {}", level_path.display()))?;
+    let steps = load_playback_steps(playback_path)
+        .with_context(|| format!("Failed to load playback: {}", playback_path.display()))?;
+    let directions: Vec<_> = steps.iter().map(|step| step.direction).collect();
+
+    let mut stdout = std::io::stdout();
+    replay_engine(&level, &directions, |moves, engine| {
+        if let Some(step) = moves.checked_sub(1).map(|index| steps[index]) {
+            thread::sleep(Duration::from_millis(step.delay_ms));
+        }
+        let _ = write!(
+            stdout,
+            "{CLEAR_SCREEN}{}",
+            native_frame(&level, engine, moves, steps.len())
+        );
+        let _ = stdout.flush();
+    })
+}
+
+/// One frame of the native replay: a move counter with the game status above the board
+fn native_frame(
+    level: &LevelDefinition,
+    engine: &GameEngine,
+    moves: usize,
+    total: usize,
+) -> String {
+    format!(
+        "{} - move {moves}/{total}, {:?}\n{}",
+        level.name,
+        engine.game_state().status,
+        render_board_bordered(level, engine)
+    )
+}
+
+pub fn run_render(level: &Path, playback: &Path, speed: f64, format: RenderFormat) -> Result<()> {
+    ensure_command("asciinema", ASCIINEMA_INSTALL_HINT)?;
+    match format {
+        RenderFormat::Svg => ensure_svg_term()?,
+        RenderFormat::Gif => ensure_command("agg", AGG_INSTALL_HINT)?,
+    }
+
+    // The cast and any sped-up playback live in a scratch directory that is removed
+    // however the render ends, so nothing but the final render lands in the tree.
+    let work_dir = tempfile::Builder::new()
+        .prefix("gsnake-render-")
+        .tempdir()
+        .context("Failed to create a temporary render directory")?;
+    let scaled_playback = write_scaled_playback(playback, speed, work_dir.path())?;
+    let input_playback = scaled_playback.as_deref().unwrap_or(playback);
 
-    let cast_path = playback.with_extension("cast");
-    let svg_path = infer_svg_path(playback)?;
-    if let Some(parent) = svg_path.parent() {
+    let cast_path = cast_path(work_dir.path(), playback);
+    let render_path = infer_render_path(playback, format.extension())?;
+    if let Some(parent) = render_path.parent() {
         std::fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create {}", parent.display()))?;
     }
-    if cast_path.exists() {
-        std::fs::remove_file(&cast_path)
-            .with_context(|| format!("Failed to remove {}", cast_path.display()))?;
-    }
 
     let status = Command::new("cargo")
         .arg("run")
@@ -49,7 +125,7 @@ pub fn run_render(level: &Path, playback: &Path) -> Result<()> {
         .arg("--level-file")
         .arg(level)
         .arg("--input-file")
-        .arg(playback)
+        .arg(input_playback)
         .arg("--record")
         .arg("--record-output")
         .arg(&cast_path)
@@ -60,34 +136,79 @@ pub fn run_render(level: &Path, playback: &Path) -> Result<()> {
         bail!("Recording failed with exit code {status}");
     }
 
-    let svg_term = svg_term_command()?;
-    let status = Command::new(svg_term)
-        .arg("--in")
-        .arg(&cast_path)
-        .arg("--out")
-        .arg(&svg_path)
-        .status()
-        .with_context(|| "Failed to run svg-term")?;
+    let status = match format {
+        RenderFormat::Svg => Command::new(svg_term_command()?)
+            .arg("--in")
+            .arg(&cast_path)
+            .arg("--out")
+            .arg(&render_path)
+            .status()
+            .with_context(|| "Failed to run svg-term")?,
+        RenderFormat::Gif => Command::new("agg")
+            .arg(&cast_path)
+            .arg(&render_path)
+            .status()
+            .with_context(|| "Failed to run agg")?,
+    };
 
     if !status.success() {
-        bail!("SVG render failed with exit code {status}");
+        bail!(
+            "{} render failed with exit code {status}",
+            format.extension().to_uppercase()
+        );
     }
 
     Ok(())
 }
 
-fn ensure_command(command: &str) -> Result<()> {
+/// Where the intermediate asciinema recording of `playback` goes inside `work_dir`
+fn cast_path(work_dir: &Path, playback: &Path) -> PathBuf {
+    let stem = playback
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("playback");
+    work_dir.join(format!("{stem}.cast"))
+}
+
+/// Writes a copy of `playback` with delays scaled by `speed` into `work_dir`.
+///
+/// Returns `None` at normal speed so the source playback is recorded as-is; the
+/// source file is never modified.
+fn write_scaled_playback(playback: &Path, speed: f64, work_dir: &Path) -> Result<Option<PathBuf>> {
+    if speed == 1.0 {
+        return Ok(None);
+    }
+
+    let steps = load_playback_steps(playback)
+        .with_context(|| format!("Failed to load playback: {}", playback.display()))?;
+    let scaled = scale_delays(&steps, speed)?;
+    let stem = playback
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("playback");
+    let path = work_dir.join(format!("{stem}-x{speed}.json"));
+    write_playback_steps(&path, &scaled)?;
+    Ok(Some(path))
+}
+
+const ASCIINEMA_INSTALL_HINT: &str =
+    "Install it with `pipx install asciinema` (or `brew install asciinema` / `sudo apt install asciinema`)";
+const SVG_TERM_INSTALL_HINT: &str = "Install it with `npm i -g svg-term-cli`";
+const AGG_INSTALL_HINT: &str =
+    "Install it with `cargo install --git https://github.com/asciinema/agg` (or `brew install agg`)";
+
+fn ensure_command(command: &str, install_hint: &str) -> Result<()> {
     let status = Command::new(command).arg("--version").status();
     if matches!(status, Ok(status) if status.success()) {
         Ok(())
     } else {
-        bail!("Required command '{command}' is not available in PATH")
+        bail!("Required command '{command}' is not available in PATH. {install_hint}")
     }
 }
 
 fn ensure_svg_term() -> Result<()> {
     if svg_term_command()?.is_empty() {
-        bail!("svg-term is not available in PATH. Install svg-term-cli")
+        bail!("svg-term is not available in PATH. {SVG_TERM_INSTALL_HINT}")
     }
     Ok(())
 }
@@ -102,7 +223,9 @@ fn svg_term_command() -> Result<String> {
     Ok(String::new())
 }
 
-fn infer_svg_path(playback: &Path) -> Result<PathBuf> {
+/// Output path for a render of `playback`: its first `playbacks` component becomes
+/// `renders` and the extension becomes `extension`
+pub(crate) fn infer_render_path(playback: &Path, extension: &str) -> Result<PathBuf> {
     let mut output = PathBuf::new();
     let mut replaced = false;
     for component in playback.components() {
@@ -116,10 +239,10 @@ fn infer_svg_path(playback: &Path) -> Result<PathBuf> {
     }
 
     if !replaced {
-        return Ok(playback.with_extension("svg"));
+        return Ok(playback.with_extension(extension));
     }
 
-    Ok(output.with_extension("svg"))
+    Ok(output.with_extension(extension))
 }
 
 fn gsnake_core_manifest() -> Result<PathBuf> {
@@ -142,3 +265,86 @@ fn gsnake_core_manifest() -> Result<PathBuf> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gsnake_core::Direction;
+
+    #[test]
+    fn test_ensure_command_error_includes_install_hint() {
+        let error = ensure_command("gsnake-levels-missing-tool", "Install it with `true`")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'gsnake-levels-missing-tool' is not available in PATH"));
+        assert!(error.ends_with("Install it with `true`"));
+    }
+
+    #[test]
+    fn test_infer_render_path_swaps_playbacks_for_renders() {
+        let playback = Path::new("gsnake-levels/playbacks/easy/level_001.json");
+        assert_eq!(
+            infer_render_path(playback, "gif").unwrap(),
+            PathBuf::from("gsnake-levels/renders/easy/level_001.gif")
+        );
+        assert_eq!(
+            infer_render_path(playback, "svg").unwrap(),
+            PathBuf::from("gsnake-levels/renders/easy/level_001.svg")
+        );
+        assert_eq!(
+            infer_render_path(Path::new("elsewhere/level.json"), "gif").unwrap(),
+            PathBuf::from("elsewhere/level.gif")
+        );
+    }
+
+    #[test]
+    fn test_render_scratch_files_stay_out_of_playbacks_tree() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let playbacks_dir = repo.path().join("playbacks/easy");
+        std::fs::create_dir_all(&playbacks_dir).unwrap();
+        let playback = playbacks_dir.join("level_001.json");
+        std::fs::write(&playback, r#"[{"key":"Right","delay_ms":200}]"#).unwrap();
+
+        let work_dir = tempfile::TempDir::new().unwrap();
+        let cast = cast_path(work_dir.path(), &playback);
+        let scaled = write_scaled_playback(&playback, 2.0, work_dir.path())
+            .unwrap()
+            .expect("Expected a scaled playback");
+        assert_eq!(cast, work_dir.path().join("level_001.cast"));
+        assert!(scaled.starts_with(work_dir.path()));
+
+        let playback_files: Vec<_> = std::fs::read_dir(&playbacks_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(playback_files, vec!["level_001.json"]);
+    }
+
+    #[test]
+    fn test_native_frame_renders_board_after_move() {
+        let level: LevelDefinition = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "Native",
+            "difficulty": "easy",
+            "gridSize": { "width": 4, "height": 1 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 3, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        }))
+        .unwrap();
+        let mut engine = GameEngine::new(level.clone()).unwrap();
+        engine.process_move(Direction::East).unwrap();
+
+        assert_eq!(
+            native_frame(&level, &engine, 1, 3),
+            "Native - move 1/3, Playing\n+----+\n|.>.E|\n+----+\n"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    playback::compact_directions,
+    solver::{self, OptimalSolutions},
+};
+use anyhow::Result;
+use std::path::Path;
+
+pub fn run_solutions(level_path: &Path, max_depth: usize, limit: usize) -> Result<()> {
+    let level = solver::load_level(level_path)?;
+    let optimal = solver::solve_level_all_optimal_with_limit(
+        level,
+        max_depth,
+        solver::MAX_OPTIMAL_SOLUTIONS,
+    )?;
+    println!("{}", format_solutions(&optimal, limit));
+    Ok(())
+}
+
+/// Count line followed by the first `limit` solutions in compact form
+fn format_solutions(optimal: &OptimalSolutions, limit: usize) -> String {
+    let moves = optimal.solutions.first().map_or(0, Vec::len);
+    let count = optimal.solutions.len();
+    let mut output = if optimal.capped {
+        format!("At least {count} optimal solutions of {moves} moves (enumeration capped)")
+    } else if count == 1 {
+        format!("1 optimal solution of {moves} moves (unique)")
+    } else {
+        format!("{count} optimal solutions of {moves} moves")
+    };
+    for (index, solution) in optimal.solutions.iter().take(limit).enumerate() {
+        output.push_str(&format!(
+            "\n  {}. {}",
+            index + 1,
+            compact_directions(solution)
+        ));
+    }
+    if count > limit {
+        output.push_str(&format!("\n  ... {} more", count - limit));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gsnake_core::Direction;
+
+    #[test]
+    fn test_format_solutions_lists_first_few_and_marks_cap() {
+        let optimal = OptimalSolutions {
+            solutions: vec![
+                vec![Direction::East, Direction::South],
+                vec![Direction::South, Direction::East],
+                vec![Direction::East, Direction::East],
+            ],
+            capped: true,
+        };
+
+        assert_eq!(
+            format_solutions(&optimal, 2),
+            "At least 3 optimal solutions of 2 moves (enumeration capped)\n  1. RD\n  2. DR\n  ... 1 more"
+        );
+    }
+}
@@ -1,10 +1,16 @@
+use crate::playback::{
+    playback_steps, write_playback, write_playback_directions, DelayStrategy, PlaybackMeta,
+    DEFAULT_STEP_DELAY_MS,
+};
 use anyhow::{bail, Context, Result};
 use gsnake_core::{engine::GameEngine, Direction, GameStatus, LevelDefinition, Position};
-use serde::Serialize;
 use std::{
-    collections::{HashSet, VecDeque},
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     fs,
+    hash::{BuildHasherDefault, Hasher},
     path::Path,
+    time::{Duration, Instant},
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -29,53 +35,775 @@ struct StateKey {
     status: StatusCode,
 }
 
+impl StateKey {
+    /// Whether `engine` is in exactly this state, compared in place without cloning
+    fn matches(&self, engine: &GameEngine) -> bool {
+        let level_state = engine.level_state();
+        let game_state = engine.game_state();
+        self.snake == level_state.snake.segments
+            && self.snake_dir == direction_code(level_state.snake.direction)
+            && self.food == level_state.food
+            && self.floating_food == level_state.floating_food
+            && self.falling_food == level_state.falling_food
+            && self.stones == level_state.stones
+            && self.spikes == level_state.spikes
+            && self.exit_is_solid == level_state.exit_is_solid
+            && self.food_collected == game_state.food_collected
+            && self.status == status_code(game_state.status)
+    }
+}
+
+/// Visited set keyed on a 64-bit fingerprint of each state.
+///
+/// Fingerprinting reads the engine in place, so looking up an already-seen state
+/// allocates nothing; the full `StateKey` is cloned only for new states and only
+/// compared when two states share a fingerprint.
+#[derive(Default)]
+struct VisitedStates {
+    buckets: HashMap<u64, Vec<StateKey>, BuildHasherDefault<FingerprintHasher>>,
+    len: usize,
+}
+
+impl VisitedStates {
+    /// Records the engine's state, returning `false` if it was already visited
+    fn insert(&mut self, engine: &GameEngine) -> bool {
+        self.insert_with_fingerprint(state_fingerprint(engine), engine)
+    }
+
+    fn insert_with_fingerprint(&mut self, fingerprint: u64, engine: &GameEngine) -> bool {
+        let bucket = self.buckets.entry(fingerprint).or_default();
+        if bucket.iter().any(|key| key.matches(engine)) {
+            return false;
+        }
+        bucket.push(state_key(engine));
+        self.len += 1;
+        true
+    }
+
+    fn contains(&self, engine: &GameEngine) -> bool {
+        self.buckets
+            .get(&state_fingerprint(engine))
+            .is_some_and(|bucket| bucket.iter().any(|key| key.matches(engine)))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Passes fingerprints through unchanged; they are already well mixed
+#[derive(Default)]
+struct FingerprintHasher(u64);
+
+impl Hasher for FingerprintHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = fingerprint_mix(self.0, u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.0 = value;
+    }
+}
+
+/// One FxHash-style round: rotate, xor in the word, multiply by an odd constant
+fn fingerprint_mix(hash: u64, word: u64) -> u64 {
+    (hash.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95)
+}
+
+/// 64-bit fingerprint over every field of `StateKey`, read straight from the engine.
+///
+/// Each list is prefixed with its length so that, e.g., a food moving into the
+/// stones list changes the fingerprint.
+fn state_fingerprint(engine: &GameEngine) -> u64 {
+    let level_state = engine.level_state();
+    let game_state = engine.game_state();
+    let mut hash = 0;
+    for positions in [
+        &level_state.snake.segments,
+        &level_state.food,
+        &level_state.floating_food,
+        &level_state.falling_food,
+        &level_state.stones,
+        &level_state.spikes,
+    ] {
+        hash = fingerprint_mix(hash, positions.len() as u64);
+        for position in positions {
+            let packed = (u64::from(position.x as u32) << 32) | u64::from(position.y as u32);
+            hash = fingerprint_mix(hash, packed);
+        }
+    }
+    hash = fingerprint_mix(hash, direction_code(level_state.snake.direction) as u64);
+    hash = fingerprint_mix(hash, u64::from(level_state.exit_is_solid));
+    hash = fingerprint_mix(hash, u64::from(game_state.food_collected));
+    fingerprint_mix(hash, status_code(game_state.status) as u64)
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::East,
+    Direction::West,
+];
+
+/// Search strategy used to produce a playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(dead_code)]
+pub enum SolveStrategy {
+    /// Exact breadth-first search (`solve_level`); move-optimal
+    #[default]
+    Bfs,
+    /// Best-effort beam search keeping this many states per depth (`solve_level_beam`)
+    Beam(usize),
+    /// Right-hand wall follower for levels without food (`solve_level_wallfollower`)
+    WallFollower,
+    /// Best-first search ordered by moves plus a distance bound (`solve_level_astar`)
+    AStar,
+}
+
+impl SolveStrategy {
+    pub fn solve(self, level: LevelDefinition, max_depth: usize) -> Result<Vec<Direction>> {
+        match self {
+            SolveStrategy::Bfs => solve_level(level, max_depth),
+            SolveStrategy::Beam(beam_width) => solve_level_beam(level, max_depth, beam_width),
+            SolveStrategy::WallFollower => solve_level_wallfollower(level, max_depth),
+            SolveStrategy::AStar => solve_level_astar(level, max_depth),
+        }
+    }
+
+    /// Only exact BFS guarantees the shortest path
+    pub fn is_optimal(self) -> bool {
+        self == SolveStrategy::Bfs
+    }
+}
+
+/// Search counters reported by `solve_level_with_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// States whose successors were generated
+    pub nodes_expanded: usize,
+    /// Distinct states recorded in the visited set
+    pub states_visited: usize,
+    /// Largest number of states waiting in the queue at once
+    pub max_queue_len: usize,
+    /// Whether a live, unvisited state was cut because its path was longer than `max_depth`
+    pub hit_depth_limit: bool,
+}
+
+/// Error returned when BFS runs out of states without finishing the level.
+///
+/// Downcast an `anyhow::Error` to this type to read the search counters, e.g. to
+/// tell "raise --max-depth" (`hit_depth_limit`) apart from "truly unsolvable".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoSolution {
+    pub stats: SolveStats,
+}
+
+impl std::fmt::Display for NoSolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.stats.hit_depth_limit {
+            write!(
+                f,
+                "No solution found (search was cut off at the depth limit)"
+            )
+        } else {
+            write!(f, "No solution found")
+        }
+    }
+}
+
+impl std::error::Error for NoSolution {}
+
+/// Error returned when a solve runs past its wall-clock timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveTimeout {
+    pub elapsed: Duration,
+}
+
+impl std::fmt::Display for SolveTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Solver timed out after {}ms", self.elapsed.as_millis())
+    }
+}
+
+impl std::error::Error for SolveTimeout {}
+
+/// How many states the BFS pops between deadline checks
+const TIMEOUT_CHECK_INTERVAL: usize = 1024;
+
 pub fn solve_level(level: LevelDefinition, max_depth: usize) -> Result<Vec<Direction>> {
+    solve_level_with_timeout(level, max_depth, None)
+}
+
+/// Breadth-first search that gives up with a [`SolveTimeout`] error once `timeout`
+/// has elapsed, checked every `TIMEOUT_CHECK_INTERVAL` popped states.
+pub fn solve_level_with_timeout(
+    level: LevelDefinition,
+    max_depth: usize,
+    timeout: Option<Duration>,
+) -> Result<Vec<Direction>> {
+    bfs(level, max_depth, timeout).map(|(path, _)| path)
+}
+
+/// Breadth-first search that also reports how much of the state space it explored.
+///
+/// On failure the error is a [`NoSolution`] carrying the same counters.
+#[allow(dead_code)]
+pub fn solve_level_with_stats(
+    level: LevelDefinition,
+    max_depth: usize,
+) -> Result<(Vec<Direction>, SolveStats)> {
+    bfs(level, max_depth, None)
+}
+
+fn bfs(
+    level: LevelDefinition,
+    max_depth: usize,
+    timeout: Option<Duration>,
+) -> Result<(Vec<Direction>, SolveStats)> {
+    let started = Instant::now();
+    let navigation_exit = navigation_exit(&level);
+    let walls = StaticObstacles::new(&level);
     let engine = GameEngine::new(level).context("Invalid grid size in level definition")?;
     let mut queue: VecDeque<(GameEngine, Vec<Direction>)> = VecDeque::new();
-    let mut visited: HashSet<StateKey> = HashSet::new();
+    let mut visited = VisitedStates::default();
+
+    let mut stats = SolveStats::default();
 
     queue.push_back((engine, Vec::new()));
+    stats.max_queue_len = queue.len();
+
+    let mut popped = 0usize;
 
     while let Some((engine, path)) = queue.pop_front() {
+        popped += 1;
+        if let Some(timeout) = timeout {
+            if popped.is_multiple_of(TIMEOUT_CHECK_INTERVAL) && started.elapsed() >= timeout {
+                return Err(SolveTimeout {
+                    elapsed: started.elapsed(),
+                }
+                .into());
+            }
+        }
+
         if path.len() > max_depth {
+            // Only cutting a live, unvisited state means a deeper search could find more.
+            if engine.game_state().status != GameStatus::GameOver && !visited.contains(&engine) {
+                stats.hit_depth_limit = true;
+            }
             continue;
         }
 
-        let status = engine.game_state().status;
-        if status == GameStatus::LevelComplete || status == GameStatus::AllComplete {
-            return Ok(path);
+        if is_level_complete(&engine, navigation_exit) {
+            stats.states_visited = visited.len();
+            return Ok((path, stats));
         }
-        if status == GameStatus::GameOver {
+        if engine.game_state().status == GameStatus::GameOver {
             continue;
         }
 
-        let key = state_key(&engine);
-        if !visited.insert(key) {
+        if !visited.insert(&engine) {
             continue;
         }
 
-        for direction in [
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ] {
-            let mut next = engine.clone();
-            let Ok(processed) = next.process_move(direction) else {
+        stats.nodes_expanded += 1;
+        for (direction, next) in successors(engine, &walls) {
+            let mut next_path = path.clone();
+            next_path.push(direction);
+            queue.push_back((next, next_path));
+        }
+        stats.max_queue_len = stats.max_queue_len.max(queue.len());
+    }
+
+    stats.states_visited = visited.len();
+    Err(NoSolution { stats }.into())
+}
+
+/// Best-effort beam search for levels too large for exact BFS.
+///
+/// Each depth layer is expanded as in `solve_level`, then only the `beam_width` distinct
+/// states closest to the goal survive: fewest remaining food first, then the
+/// shortest Manhattan distance from the head to the nearest food (or to the exit
+/// once all food is eaten). Memory and time stay bounded by `beam_width`, but the
+/// search is incomplete and the path it returns is not guaranteed to be shortest:
+/// a solvable level can fail when the route needs a detour the heuristic ranks
+/// poorly, so retry with a wider beam or fall back to `solve_level`.
+pub fn solve_level_beam(
+    level: LevelDefinition,
+    max_depth: usize,
+    beam_width: usize,
+) -> Result<Vec<Direction>> {
+    if beam_width == 0 {
+        bail!("Beam width must be at least 1");
+    }
+    let exit = (level.exit.x, level.exit.y);
+    let navigation_exit = navigation_exit(&level);
+    let walls = StaticObstacles::new(&level);
+    let engine = GameEngine::new(level).context("Invalid grid size in level definition")?;
+    let mut visited = VisitedStates::default();
+    let mut layer: Vec<(GameEngine, Vec<Direction>)> = vec![(engine, Vec::new())];
+
+    for _ in 0..=max_depth {
+        let mut next_layer = Vec::new();
+        for (engine, path) in layer {
+            if is_level_complete(&engine, navigation_exit) {
+                return Ok(path);
+            }
+            if engine.game_state().status == GameStatus::GameOver || !visited.insert(&engine) {
                 continue;
-            };
-            if !processed {
+            }
+            if path.len() == max_depth {
                 continue;
             }
+
+            for (direction, next) in successors(engine, &walls) {
+                let mut next_path = path.clone();
+                next_path.push(direction);
+                next_layer.push((next, next_path));
+            }
+        }
+
+        let next_layer = prune_layer(next_layer, &visited, beam_width, exit, navigation_exit);
+        if next_layer.is_empty() {
+            break;
+        }
+        layer = next_layer;
+    }
+
+    bail!("No solution found within beam width {beam_width}")
+}
+
+/// Keeps the `beam_width` best candidates of a beam layer.
+///
+/// Candidates that are game over, already visited, or repeat an earlier candidate's state are
+/// dropped before ranking, so duplicates reached along different paths can't crowd distinct
+/// states out of the beam.
+fn prune_layer(
+    candidates: Vec<(GameEngine, Vec<Direction>)>,
+    visited: &VisitedStates,
+    beam_width: usize,
+    exit: (i32, i32),
+    navigation_exit: Option<(i32, i32)>,
+) -> Vec<(GameEngine, Vec<Direction>)> {
+    let mut seen = VisitedStates::default();
+    let mut layer: Vec<_> = candidates
+        .into_iter()
+        .filter(|(engine, _)| {
+            engine.game_state().status != GameStatus::GameOver
+                && !visited.contains(engine)
+                && seen.insert(engine)
+        })
+        .collect();
+    layer.sort_by_cached_key(|(engine, _)| beam_score(engine, exit, navigation_exit));
+    layer.truncate(beam_width);
+    layer
+}
+
+/// Ranks a beam candidate; lower is better.
+///
+/// Finished states rank first so they are never cut, then states with fewer
+/// remaining food, then states whose head is nearer the closest target.
+fn beam_score(
+    engine: &GameEngine,
+    exit: (i32, i32),
+    navigation_exit: Option<(i32, i32)>,
+) -> (bool, usize, i32) {
+    if is_level_complete(engine, navigation_exit) {
+        return (false, 0, 0);
+    }
+
+    let level_state = engine.level_state();
+    let Some(head) = level_state.snake.segments.first() else {
+        return (true, usize::MAX, i32::MAX);
+    };
+    let remaining: Vec<(i32, i32)> = level_state
+        .food
+        .iter()
+        .chain(&level_state.floating_food)
+        .chain(&level_state.falling_food)
+        .map(|position| (position.x, position.y))
+        .collect();
+    let distance = remaining
+        .iter()
+        .copied()
+        .chain(remaining.is_empty().then_some(exit))
+        .map(|(x, y)| (head.x - x).abs() + (head.y - y).abs())
+        .min()
+        .unwrap_or(0);
+
+    (true, remaining.len(), distance)
+}
+
+/// A* search: expands the state with the lowest `moves so far + astar_heuristic`
+/// first, deduplicating states by `StateKey` like `solve_level`.
+///
+/// The heuristic never overestimates the remaining moves as long as every move
+/// shifts the head by one cell, so on such levels the result is as short as the
+/// BFS solution while far fewer states are expanded. A fall under gravity can
+/// move the head several cells in one move, so with gravity the path is usually
+/// but not provably optimal.
+pub fn solve_level_astar(level: LevelDefinition, max_depth: usize) -> Result<Vec<Direction>> {
+    let exit = (level.exit.x, level.exit.y);
+    let navigation_exit = navigation_exit(&level);
+    let total_food = total_food(&level);
+    let walls = StaticObstacles::new(&level);
+    let engine = GameEngine::new(level).context("Invalid grid size in level definition")?;
+    let mut open = BinaryHeap::new();
+    let mut visited = VisitedStates::default();
+    let mut sequence = 0usize;
+
+    let estimate = astar_heuristic(&engine, exit, total_food);
+    open.push(Reverse(AStarNode {
+        estimate,
+        sequence,
+        engine,
+        path: Vec::new(),
+    }));
+
+    while let Some(Reverse(AStarNode { engine, path, .. })) = open.pop() {
+        if path.len() > max_depth {
+            continue;
+        }
+
+        if is_level_complete(&engine, navigation_exit) {
+            return Ok(path);
+        }
+        if engine.game_state().status == GameStatus::GameOver {
+            continue;
+        }
+
+        if !visited.insert(&engine) {
+            continue;
+        }
+
+        for (direction, next) in successors(engine, &walls) {
             let mut next_path = path.clone();
             next_path.push(direction);
-            queue.push_back((next, next_path));
+            sequence += 1;
+            open.push(Reverse(AStarNode {
+                estimate: next_path.len() + astar_heuristic(&next, exit, total_food),
+                sequence,
+                engine: next,
+                path: next_path,
+            }));
         }
     }
 
     bail!("No solution found")
 }
 
+/// Open-list entry for `solve_level_astar`, ordered by estimate and then by
+/// insertion so equal estimates are expanded first-in, first-out.
+struct AStarNode {
+    estimate: usize,
+    sequence: usize,
+    engine: GameEngine,
+    path: Vec<Direction>,
+}
+
+impl PartialEq for AStarNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AStarNode {}
+
+impl PartialOrd for AStarNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AStarNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.estimate, self.sequence).cmp(&(other.estimate, other.sequence))
+    }
+}
+
+/// Lower bound on the moves left to finish the level from `engine`.
+///
+/// Every food item still needed must be reached and the exit entered after it,
+/// so the longest `head -> food -> exit` detour bounds the rest of the path.
+/// When the level needs fewer food than remain, only the cheapest such detour is
+/// certain. Summing the distances to every food item, as a greedy search would,
+/// can overestimate and lose optimality.
+fn astar_heuristic(engine: &GameEngine, exit: (i32, i32), total_food: usize) -> usize {
+    if engine.game_state().status != GameStatus::Playing {
+        return 0;
+    }
+    let level_state = engine.level_state();
+    let Some(head) = level_state.snake.segments.first() else {
+        return 0;
+    };
+    let distance =
+        |(ax, ay): (i32, i32), (bx, by): (i32, i32)| ((ax - bx).abs() + (ay - by).abs()) as usize;
+    let head = (head.x, head.y);
+
+    let needed = total_food.saturating_sub(engine.game_state().food_collected as usize);
+    let detours = level_state
+        .food
+        .iter()
+        .chain(&level_state.floating_food)
+        .chain(&level_state.falling_food)
+        .map(|food| distance(head, (food.x, food.y)) + distance((food.x, food.y), exit));
+    let remaining =
+        level_state.food.len() + level_state.floating_food.len() + level_state.falling_food.len();
+
+    if needed == 0 || remaining == 0 {
+        distance(head, exit)
+    } else if needed >= remaining {
+        detours.max().unwrap_or(0)
+    } else {
+        detours.min().unwrap_or(0)
+    }
+}
+
+/// Cheap deterministic solver for navigation-only levels (no food).
+///
+/// Follows the right-hand rule: from the current heading it tries turning right,
+/// going straight, turning left and finally turning back, taking the first move
+/// the engine accepts without ending the game. The path is not optimal and the
+/// walk gives up as soon as it revisits a state, since it would loop forever.
+pub fn solve_level_wallfollower(
+    level: LevelDefinition,
+    max_depth: usize,
+) -> Result<Vec<Direction>> {
+    let total_food = total_food(&level);
+    if total_food > 0 {
+        bail!("Wall follower only supports levels without food ({total_food} food present)");
+    }
+
+    let navigation_exit = navigation_exit(&level);
+    let mut heading = level.snake_direction;
+    let mut engine = GameEngine::new(level).context("Invalid grid size in level definition")?;
+    let mut visited: HashSet<(StateKey, i8)> = HashSet::new();
+    let mut path = Vec::new();
+
+    loop {
+        if is_level_complete(&engine, navigation_exit) {
+            return Ok(path);
+        }
+        if engine.game_state().status == GameStatus::GameOver {
+            bail!("Wall follower hit Game Over");
+        }
+        if path.len() >= max_depth {
+            bail!("Wall follower did not reach the exit within {max_depth} moves");
+        }
+        if !visited.insert((state_key(&engine), direction_code(Some(heading)))) {
+            bail!("Wall follower is looping without reaching the exit");
+        }
+
+        let (direction, next) = [
+            turn_right(heading),
+            heading,
+            turn_left(heading),
+            turn_right(turn_right(heading)),
+        ]
+        .into_iter()
+        .find_map(|direction| {
+            apply_move(&engine, direction)
+                .filter(|next| next.game_state().status != GameStatus::GameOver)
+                .map(|next| (direction, next))
+        })
+        .context("Wall follower is stuck with no safe move")?;
+
+        path.push(direction);
+        heading = direction;
+        engine = next;
+    }
+}
+
+/// Default cap on how many optimal solutions `solve_level_all_optimal` enumerates
+pub const MAX_OPTIMAL_SOLUTIONS: usize = 1000;
+
+/// Shortest solutions found by `solve_level_all_optimal_with_limit`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimalSolutions {
+    /// Distinct move sequences of the minimal length
+    pub solutions: Vec<Vec<Direction>>,
+    /// Whether enumeration stopped at the limit with more optimal solutions left
+    pub capped: bool,
+}
+
+/// Every shortest solution, up to [`MAX_OPTIMAL_SOLUTIONS`] of them
+#[allow(dead_code)]
+pub fn solve_level_all_optimal(
+    level: LevelDefinition,
+    max_depth: usize,
+) -> Result<Vec<Vec<Direction>>> {
+    solve_level_all_optimal_with_limit(level, max_depth, MAX_OPTIMAL_SOLUTIONS)
+        .map(|optimal| optimal.solutions)
+}
+
+/// Breadth-first search that keeps going through the depth where the level is first
+/// completed, returning every move sequence of that length instead of only the first.
+///
+/// A state is still expanded once, but every parent reaching it at its first depth is
+/// remembered, so the solutions can be enumerated by walking parents back to the start.
+pub fn solve_level_all_optimal_with_limit(
+    level: LevelDefinition,
+    max_depth: usize,
+    limit: usize,
+) -> Result<OptimalSolutions> {
+    let navigation_exit = navigation_exit(&level);
+    let walls = StaticObstacles::new(&level);
+    let engine = GameEngine::new(level).context("Invalid grid size in level definition")?;
+    if is_level_complete(&engine, navigation_exit) {
+        return Ok(OptimalSolutions {
+            solutions: vec![Vec::new()],
+            capped: false,
+        });
+    }
+
+    // `parents[node]` lists every (parent node, move) that reaches `node` at its depth.
+    let mut parents: Vec<Vec<(usize, Direction)>> = vec![Vec::new()];
+    let mut depths = vec![0];
+    let mut nodes: HashMap<StateKey, usize> = HashMap::from([(state_key(&engine), 0)]);
+    let mut layer = vec![(0, engine)];
+
+    for depth in 1..=max_depth {
+        let mut goals = Vec::new();
+        let mut next_layer = Vec::new();
+        for (node, engine) in layer {
+            for (direction, next) in successors(engine, &walls) {
+                if is_level_complete(&next, navigation_exit) {
+                    goals.push((node, direction));
+                    continue;
+                }
+                if next.game_state().status == GameStatus::GameOver {
+                    continue;
+                }
+                match nodes.get(&state_key(&next)) {
+                    Some(&existing) if depths[existing] == depth => {
+                        parents[existing].push((node, direction));
+                    },
+                    Some(_) => {},
+                    None => {
+                        let id = parents.len();
+                        nodes.insert(state_key(&next), id);
+                        parents.push(vec![(node, direction)]);
+                        depths.push(depth);
+                        next_layer.push((id, next));
+                    },
+                }
+            }
+        }
+
+        if !goals.is_empty() {
+            let mut solutions = Vec::new();
+            let mut capped = false;
+            for (node, direction) in goals {
+                capped = collect_paths(&parents, node, &mut vec![direction], &mut solutions, limit);
+                if capped {
+                    break;
+                }
+            }
+            return Ok(OptimalSolutions { solutions, capped });
+        }
+        if next_layer.is_empty() {
+            break;
+        }
+        layer = next_layer;
+    }
+
+    bail!("No solution found within depth {max_depth}")
+}
+
+/// Appends every path from the start to `node`, followed by the reversed `suffix`.
+/// Returns `true` once `limit` solutions are collected and another one was still pending.
+fn collect_paths(
+    parents: &[Vec<(usize, Direction)>],
+    node: usize,
+    suffix: &mut Vec<Direction>,
+    solutions: &mut Vec<Vec<Direction>>,
+    limit: usize,
+) -> bool {
+    if parents[node].is_empty() {
+        if solutions.len() == limit {
+            return true;
+        }
+        solutions.push(suffix.iter().rev().copied().collect());
+        return false;
+    }
+    for &(parent, direction) in &parents[node] {
+        suffix.push(direction);
+        let capped = collect_paths(parents, parent, suffix, solutions, limit);
+        suffix.pop();
+        if capped {
+            return true;
+        }
+    }
+    false
+}
+
+/// Food the level expects to be eaten: `totalFood` when set, else every food item
+pub(crate) fn total_food(level: &LevelDefinition) -> usize {
+    level
+        .total_food
+        .map(|total| total as usize)
+        .unwrap_or(level.food.len() + level.floating_food.len() + level.falling_food.len())
+}
+
+/// Exit cell of a navigation-only level (no food to collect), where standing on
+/// the exit is itself completion.
+pub(crate) fn navigation_exit(level: &LevelDefinition) -> Option<(i32, i32)> {
+    (total_food(level) == 0).then_some((level.exit.x, level.exit.y))
+}
+
+/// Completion rule shared by every solver strategy and `verify`.
+///
+/// Engine status is authoritative, except that a navigation-only level whose
+/// status is still `Playing` counts as complete once the head is on the exit,
+/// so solving and verifying agree even if the engine never flips the status.
+pub(crate) fn is_level_complete(engine: &GameEngine, navigation_exit: Option<(i32, i32)>) -> bool {
+    match engine.game_state().status {
+        GameStatus::LevelComplete | GameStatus::AllComplete => true,
+        GameStatus::GameOver => false,
+        GameStatus::Playing => navigation_exit.is_some_and(|(x, y)| {
+            engine
+                .level_state()
+                .snake
+                .segments
+                .first()
+                .is_some_and(|head| head.x == x && head.y == y)
+        }),
+    }
+}
+
+fn turn_right(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North,
+    }
+}
+
+fn turn_left(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::West,
+        Direction::West => Direction::South,
+        Direction::South => Direction::East,
+        Direction::East => Direction::North,
+    }
+}
+
+/// Grid offset of one step in `direction` (y grows downwards)
+pub(crate) fn direction_delta(direction: Direction) -> (i32, i32) {
+    match direction {
+        Direction::North => (0, -1),
+        Direction::South => (0, 1),
+        Direction::East => (1, 0),
+        Direction::West => (-1, 0),
+    }
+}
+
 pub fn load_level(level_path: &Path) -> Result<LevelDefinition> {
     let contents = fs::read_to_string(level_path)
         .with_context(|| format!("Failed to read level file: {}", level_path.display()))?;
@@ -84,18 +812,147 @@ pub fn load_level(level_path: &Path) -> Result<LevelDefinition> {
     Ok(level)
 }
 
+/// Loads the level at `level_path`, solves it with BFS and writes the solution to
+/// `output_path` as a bare-array playback with the default step delay.
+///
+/// The file is written with `playback::write_playback_directions`, so parent directories are
+/// created and the JSON ends with a newline.
 pub fn solve_level_to_playback(
     level_path: &Path,
     output_path: &Path,
     max_depth: usize,
-) -> Result<usize> {
+) -> Result<()> {
     let level = load_level(level_path)?;
     let solution = solve_level(level, max_depth)
         .with_context(|| format!("No solution found within depth {}", max_depth))?;
-    write_playback(output_path, &solution)?;
+    write_playback_directions(output_path, &solution, DEFAULT_STEP_DELAY_MS)
+}
+
+/// Like `solve_level_to_playback`, with a choice of strategy and step delays, and
+/// optionally wrapping the steps in a `PlaybackMeta` header.
+pub fn solve_level_to_playback_with_options(
+    level_path: &Path,
+    output_path: &Path,
+    max_depth: usize,
+    strategy: SolveStrategy,
+    delays: &DelayStrategy,
+    with_meta: bool,
+) -> Result<usize> {
+    let level = load_level(level_path)?;
+    let level_id = level.id;
+    let solution = strategy
+        .solve(level, max_depth)
+        .with_context(|| format!("No solution found within depth {}", max_depth))?;
+    let meta = with_meta.then(|| PlaybackMeta {
+        level_id,
+        generated_by: GENERATED_BY.to_string(),
+        optimal: strategy.is_optimal(),
+        moves: solution.len(),
+    });
+    let steps = playback_steps(&solution, delays);
+    write_playback(output_path, &steps, meta.as_ref())?;
     Ok(solution.len())
 }
 
+const GENERATED_BY: &str = concat!("gsnake-levels solver ", env!("CARGO_PKG_VERSION"));
+
+/// Applies a single move to a copy of `engine`, returning `None` when the move is
+/// rejected.
+///
+/// `process_move` settles the whole turn (movement, gravity, falling food, exit
+/// state) before returning, so `game_state`/`level_state` are already the
+/// resolved post-move state. `generate_frame` only renders that state into a grid
+/// and is not needed to keep `state_key` accurate.
+fn apply_move(engine: &GameEngine, direction: Direction) -> Option<GameEngine> {
+    #[cfg(test)]
+    tests::ENGINE_CLONES.with(|clones| clones.set(clones.get() + 1));
+    apply_move_in_place(engine.clone(), direction)
+}
+
+fn apply_move_in_place(mut engine: GameEngine, direction: Direction) -> Option<GameEngine> {
+    match engine.process_move(direction) {
+        Ok(true) => Some(engine),
+        _ => None,
+    }
+}
+
+/// Every accepted move out of `engine`, cloning it only when a move has to be tried.
+///
+/// Directions into a wall, off the grid or back into the neck are dropped before
+/// any clone, and the last remaining direction reuses `engine` itself, so a
+/// corridor step costs no clone at all.
+fn successors(engine: GameEngine, walls: &StaticObstacles) -> Vec<(Direction, GameEngine)> {
+    let candidates: Vec<Direction> = DIRECTIONS
+        .into_iter()
+        .filter(|&direction| !walls.blocks(&engine, direction) && !reverses(&engine, direction))
+        .collect();
+    let Some((&last, rest)) = candidates.split_last() else {
+        return Vec::new();
+    };
+
+    let mut next_states: Vec<_> = rest
+        .iter()
+        .filter_map(|&direction| Some((direction, apply_move(&engine, direction)?)))
+        .collect();
+    if let Some(next) = apply_move_in_place(engine, last) {
+        next_states.push((last, next));
+    }
+    next_states
+}
+
+/// Whether `direction` points the head back into the segment right behind it, which
+/// the engine always rejects, whatever the snake's length
+fn reverses(engine: &GameEngine, direction: Direction) -> bool {
+    let segments = &engine.level_state().snake.segments;
+    let [head, neck, ..] = segments.as_slice() else {
+        return false;
+    };
+    let (dx, dy) = direction_delta(direction);
+    head.x + dx == neck.x && head.y + dy == neck.y
+}
+
+/// The level's walls and grid bounds, which never change during play.
+///
+/// Moving the head into one of these cells always ends the game, so the search
+/// loops skip such directions before paying for an engine clone. In one-wide
+/// corridors this leaves a single candidate instead of four.
+struct StaticObstacles {
+    width: i32,
+    height: i32,
+    blocked: Vec<bool>,
+}
+
+impl StaticObstacles {
+    fn new(level: &LevelDefinition) -> Self {
+        let width = level.grid_size.width.max(0);
+        let height = level.grid_size.height.max(0);
+        let mut blocked = vec![false; (width * height) as usize];
+        for obstacle in &level.obstacles {
+            if (0..width).contains(&obstacle.x) && (0..height).contains(&obstacle.y) {
+                blocked[(obstacle.y * width + obstacle.x) as usize] = true;
+            }
+        }
+        Self {
+            width,
+            height,
+            blocked,
+        }
+    }
+
+    /// Whether moving the snake in `direction` puts its head on a wall or off the grid
+    fn blocks(&self, engine: &GameEngine, direction: Direction) -> bool {
+        let Some(head) = engine.level_state().snake.segments.first() else {
+            return false;
+        };
+        let (dx, dy) = direction_delta(direction);
+        let (x, y) = (head.x + dx, head.y + dy);
+        if !(0..self.width).contains(&x) || !(0..self.height).contains(&y) {
+            return true;
+        }
+        self.blocked[(y * self.width + x) as usize]
+    }
+}
+
 fn state_key(engine: &GameEngine) -> StateKey {
     let level_state = engine.level_state();
     let game_state = engine.game_state();
@@ -133,35 +990,502 @@ fn status_code(status: GameStatus) -> StatusCode {
     }
 }
 
-#[derive(Serialize)]
-struct PlaybackStep {
-    key: String,
-    delay_ms: u64,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::cell::Cell;
 
-fn write_playback(output_path: &Path, solution: &[Direction]) -> Result<()> {
-    let steps: Vec<PlaybackStep> = solution
-        .iter()
-        .copied()
-        .map(|direction| PlaybackStep {
-            key: direction_name(direction).to_string(),
-            delay_ms: 200,
-        })
-        .collect();
+    thread_local! {
+        /// Engine clones made by `apply_move` on this test thread
+        pub(super) static ENGINE_CLONES: Cell<usize> = const { Cell::new(0) };
+    }
 
-    if let Some(parent) = output_path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    fn count_engine_clones<T>(run: impl FnOnce() -> T) -> (T, usize) {
+        ENGINE_CLONES.with(|clones| clones.set(0));
+        let result = run();
+        (result, ENGINE_CLONES.with(Cell::get))
     }
-    fs::write(output_path, serde_json::to_string_pretty(&steps)? + "\n")
-        .with_context(|| format!("Failed to write {}", output_path.display()))
-}
 
-fn direction_name(direction: Direction) -> &'static str {
-    match direction {
-        Direction::North => "Up",
-        Direction::South => "Down",
-        Direction::East => "Right",
-        Direction::West => "Left",
+    fn falling_food_level() -> LevelDefinition {
+        let level = json!({
+            "id": 1,
+            "name": "Falling Food",
+            "difficulty": "easy",
+            "gridSize": { "width": 6, "height": 4 },
+            "snake": [{ "x": 1, "y": 2 }, { "x": 0, "y": 2 }],
+            "snakeDirection": "East",
+            "obstacles": [
+                { "x": 0, "y": 3 }, { "x": 1, "y": 3 }, { "x": 2, "y": 3 },
+                { "x": 3, "y": 3 }, { "x": 4, "y": 3 }, { "x": 5, "y": 3 }
+            ],
+            "food": [],
+            "exit": { "x": 5, "y": 2 },
+            "floatingFood": [],
+            "fallingFood": [{ "x": 3, "y": 0 }],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 1
+        });
+        serde_json::from_value(level).unwrap()
+    }
+
+    #[test]
+    fn test_apply_move_state_matches_frame_replay_with_falling_food() {
+        let level = falling_food_level();
+        let mut solver_engine = GameEngine::new(level.clone()).unwrap();
+        let mut replay_engine = GameEngine::new(level).unwrap();
+
+        for (step, direction) in [Direction::East, Direction::East, Direction::North]
+            .into_iter()
+            .enumerate()
+        {
+            solver_engine = apply_move(&solver_engine, direction)
+                .unwrap_or_else(|| panic!("move {step} ({direction:?}) should be legal"));
+
+            replay_engine.process_move(direction).unwrap();
+            let frame = replay_engine.generate_frame();
+
+            assert_eq!(state_key(&solver_engine), state_key(&replay_engine));
+            assert_eq!(solver_engine.game_state().status, frame.state.status);
+            if step == 0 {
+                // The food has dropped onto the floor, right in the snake's path.
+                assert_eq!(
+                    solver_engine.level_state().falling_food,
+                    vec![Position::new(3, 2)]
+                );
+            }
+        }
+        assert!(solver_engine.level_state().falling_food.is_empty());
+        assert_eq!(solver_engine.game_state().food_collected, 1);
+    }
+
+    #[test]
+    fn test_apply_move_returns_none_for_rejected_move() {
+        let engine = GameEngine::new(falling_food_level()).unwrap();
+        // Reversing into the neck is not a legal move.
+        assert!(apply_move(&engine, Direction::West).is_none());
+    }
+
+    #[test]
+    fn test_solve_level_beam_matches_bfs_on_small_level() {
+        let level = falling_food_level();
+        let exact = solve_level(level.clone(), 50).unwrap();
+        let beam = solve_level_beam(level, 50, 4).unwrap();
+        assert_eq!(beam.len(), exact.len());
+    }
+
+    #[test]
+    fn test_prune_layer_drops_duplicate_states_before_truncating() {
+        let level = falling_food_level();
+        let exit = (level.exit.x, level.exit.y);
+        let engine = GameEngine::new(level).unwrap();
+        let east = apply_move(&engine, Direction::East).unwrap();
+        let north = apply_move(&engine, Direction::North).unwrap();
+
+        // East ranks best; its copy would otherwise take the second slot.
+        let candidates = vec![
+            (east.clone(), vec![Direction::East]),
+            (east, vec![Direction::East]),
+            (north, vec![Direction::North]),
+        ];
+        let layer = prune_layer(candidates, &VisitedStates::default(), 2, exit, None);
+        let paths: Vec<_> = layer.into_iter().map(|(_, path)| path).collect();
+        assert_eq!(paths, vec![vec![Direction::East], vec![Direction::North]]);
+    }
+
+    #[test]
+    fn test_solve_level_beam_rejects_zero_width() {
+        let error = solve_level_beam(falling_food_level(), 50, 0).unwrap_err();
+        assert!(error.to_string().contains("Beam width must be at least 1"));
+    }
+
+    #[test]
+    fn test_solve_level_beam_reports_failure_within_depth() {
+        let error = solve_level_beam(falling_food_level(), 1, 4).unwrap_err();
+        assert!(error.to_string().contains("No solution found"));
+    }
+
+    fn corridor_level(total_food: u32) -> LevelDefinition {
+        let level = json!({
+            "id": 2,
+            "name": "Corridor",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 3 },
+            "snake": [{ "x": 1, "y": 1 }, { "x": 0, "y": 1 }],
+            "snakeDirection": "East",
+            "obstacles": [
+                { "x": 0, "y": 0 }, { "x": 1, "y": 0 }, { "x": 2, "y": 0 },
+                { "x": 3, "y": 0 }, { "x": 4, "y": 0 },
+                { "x": 0, "y": 2 }, { "x": 1, "y": 2 }, { "x": 2, "y": 2 },
+                { "x": 3, "y": 2 }, { "x": 4, "y": 2 }
+            ],
+            "food": [],
+            "exit": { "x": 4, "y": 1 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": total_food
+        });
+        serde_json::from_value(level).unwrap()
+    }
+
+    #[test]
+    fn test_static_obstacles_leave_one_direction_in_corridor() {
+        let level = corridor_level(0);
+        let walls = StaticObstacles::new(&level);
+        let engine = GameEngine::new(level).unwrap();
+
+        assert!(walls.blocks(&engine, Direction::North));
+        assert!(walls.blocks(&engine, Direction::South));
+        assert!(!walls.blocks(&engine, Direction::East));
+        // The body is not a static obstacle; the engine still rejects that move
+        assert!(!walls.blocks(&engine, Direction::West));
+    }
+
+    #[test]
+    fn test_static_obstacles_block_grid_edges() {
+        let mut level = corridor_level(0);
+        level.snake = vec![Position::new(4, 1), Position::new(3, 1)];
+        let walls = StaticObstacles::new(&level);
+        let engine = GameEngine::new(level).unwrap();
+
+        assert!(walls.blocks(&engine, Direction::East));
+        assert!(!walls.blocks(&engine, Direction::West));
+    }
+
+    #[test]
+    fn test_solve_level_corridor_unchanged_by_wall_prefilter() {
+        let path = solve_level(corridor_level(0), 20).unwrap();
+        assert_eq!(path, vec![Direction::East; 3]);
+    }
+
+    #[test]
+    fn test_prefilters_clone_no_engine_in_corridor() {
+        let width = 20;
+        let mut level = corridor_level(0);
+        level.grid_size.width = width;
+        level.obstacles = (0..width)
+            .flat_map(|x| [Position::new(x, 0), Position::new(x, 2)])
+            .collect();
+        level.exit = Position::new(width - 1, 1);
+        let mut three_segments = level.clone();
+        three_segments.snake = vec![
+            Position::new(2, 1),
+            Position::new(1, 1),
+            Position::new(0, 1),
+        ];
+
+        // Walls and the reversal into the neck are dropped before cloning, leaving only the
+        // in-place move; without the pre-filters each of the 18 steps cloned 3 engines.
+        assert_eq!(corridor_level(0).snake.len(), 2);
+        let ((path, stats), clones) =
+            count_engine_clones(|| solve_level_with_stats(level, 100).unwrap());
+        assert_eq!(path.len(), 18);
+        assert_eq!(stats.nodes_expanded, 18);
+        assert_eq!(clones, 0);
+
+        let ((path, _), clones) =
+            count_engine_clones(|| solve_level_with_stats(three_segments, 100).unwrap());
+        assert_eq!(path.len(), 17);
+        assert_eq!(clones, 0);
+    }
+
+    #[test]
+    fn test_reverses_flags_the_neck_of_two_segment_snake() {
+        let engine = GameEngine::new(corridor_level(0)).unwrap();
+        assert!(reverses(&engine, Direction::West));
+        assert!(!reverses(&engine, Direction::East));
+    }
+
+    #[test]
+    fn test_solve_level_astar_matches_bfs_length_on_easy_fixtures() {
+        let fixtures = crate::test_fixtures::easy_level_fixtures();
+        assert!(!fixtures.is_empty());
+
+        for path in fixtures {
+            let level = load_level(&path).unwrap();
+            let bfs = solve_level(level.clone(), 500).unwrap();
+            let astar = solve_level_astar(level, 500).unwrap();
+            assert_eq!(astar.len(), bfs.len(), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_solve_level_to_playback_writes_completing_playback() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let level_path = crate::test_fixtures::first_easy_level_fixture();
+        let playback_path = temp_dir.path().join("playbacks/easy/level.json");
+
+        solve_level_to_playback(&level_path, &playback_path, 500).unwrap();
+        assert!(fs::read_to_string(&playback_path).unwrap().ends_with("]\n"));
+
+        let directions = crate::playback::load_playback_directions(&playback_path).unwrap();
+        assert!(!directions.is_empty());
+        let outcome = crate::verify::verify_run(load_level(&level_path).unwrap(), &directions);
+        assert!(outcome.is_ok(), "{outcome:?}");
+    }
+
+    #[test]
+    fn test_solve_level_astar_navigation_only_corridor() {
+        let path = solve_level_astar(corridor_level(0), 20).unwrap();
+        assert_eq!(path, vec![Direction::East; 3]);
+    }
+
+    #[test]
+    fn test_solve_level_astar_respects_max_depth() {
+        assert!(solve_level_astar(corridor_level(0), 2).is_err());
+    }
+
+    fn no_solution_stats(error: anyhow::Error) -> SolveStats {
+        error
+            .downcast_ref::<NoSolution>()
+            .expect("Expected a NoSolution error")
+            .stats
+    }
+
+    #[test]
+    fn test_solve_level_with_stats_walled_off_exit_exhausts_search() {
+        let mut level = corridor_level(0);
+        level.obstacles.push(Position::new(3, 1));
+
+        let stats = no_solution_stats(solve_level_with_stats(level, 500).unwrap_err());
+        assert!(!stats.hit_depth_limit);
+        assert!(stats.nodes_expanded > 0);
+        assert_eq!(stats.states_visited, stats.nodes_expanded);
+    }
+
+    #[test]
+    fn test_solve_level_with_stats_reports_depth_limit() {
+        let error = solve_level_with_stats(corridor_level(0), 1).unwrap_err();
+        assert!(error.to_string().contains("depth limit"));
+        assert!(no_solution_stats(error).hit_depth_limit);
+    }
+
+    #[test]
+    fn test_solve_level_with_stats_ignores_dead_states_past_depth_limit() {
+        let mut level = corridor_level(0);
+        level.spikes.push(Position::new(2, 1));
+
+        // The only legal move lands on the spike, one step past the limit.
+        let stats = no_solution_stats(solve_level_with_stats(level, 0).unwrap_err());
+        assert!(!stats.hit_depth_limit);
+        assert_eq!(stats.nodes_expanded, 1);
+    }
+
+    #[test]
+    fn test_solve_level_with_stats_counts_solved_search() {
+        let (path, stats) = solve_level_with_stats(corridor_level(0), 20).unwrap();
+        assert_eq!(path.len(), 3);
+        assert!(!stats.hit_depth_limit);
+        assert_eq!(stats.nodes_expanded, 3);
+        assert!(stats.max_queue_len >= 1);
+    }
+
+    /// Plain BFS deduplicating on full `StateKey`s, as the solver did before fingerprints
+    fn reference_bfs(level: LevelDefinition, max_depth: usize) -> Option<Vec<Direction>> {
+        let navigation_exit = navigation_exit(&level);
+        let mut queue = VecDeque::from([(GameEngine::new(level).unwrap(), Vec::new())]);
+        let mut visited: HashSet<StateKey> = HashSet::new();
+        while let Some((engine, path)) = queue.pop_front() {
+            if path.len() > max_depth {
+                continue;
+            }
+            if is_level_complete(&engine, navigation_exit) {
+                return Some(path);
+            }
+            if engine.game_state().status == GameStatus::GameOver
+                || !visited.insert(state_key(&engine))
+            {
+                continue;
+            }
+            for direction in DIRECTIONS {
+                if let Some(next) = apply_move(&engine, direction) {
+                    let mut next_path = path.clone();
+                    next_path.push(direction);
+                    queue.push_back((next, next_path));
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_solve_level_matches_full_key_bfs_on_level_001() {
+        let level = load_level(&crate::test_fixtures::first_easy_level_fixture()).unwrap();
+
+        let expected = reference_bfs(level.clone(), 500).expect("Expected a solution");
+        assert_eq!(solve_level(level, 500).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_visited_states_separates_near_identical_states() {
+        // One engine per food cell of an otherwise identical room.
+        let engines: Vec<GameEngine> = (0..20)
+            .flat_map(|x| (1..20).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                let mut level = large_open_level(20);
+                level.food = vec![Position::new(x, y)];
+                GameEngine::new(level).unwrap()
+            })
+            .collect();
+
+        let mut visited = VisitedStates::default();
+        let mut fingerprints = HashSet::new();
+        for engine in &engines {
+            assert!(visited.insert(engine));
+            fingerprints.insert(state_fingerprint(engine));
+        }
+        assert!(engines.iter().all(|engine| !visited.insert(engine)));
+        assert_eq!(visited.len(), engines.len());
+        assert_eq!(fingerprints.len(), engines.len());
+
+        // Forcing every state into one bucket must still tell them apart.
+        let mut colliding = VisitedStates::default();
+        for engine in &engines {
+            assert!(colliding.insert_with_fingerprint(0, engine));
+        }
+        for engine in &engines {
+            assert!(!colliding.insert_with_fingerprint(0, engine));
+        }
+        assert_eq!(colliding.len(), engines.len());
+    }
+
+    #[test]
+    fn test_solve_level_clones_fewer_engines_than_eager_expansion() {
+        let mut level = large_open_level(6);
+        level.snake = vec![
+            Position::new(2, 0),
+            Position::new(1, 0),
+            Position::new(0, 0),
+        ];
+
+        let (eager, eager_clones) = count_engine_clones(|| reference_bfs(level.clone(), 50));
+        let (lazy, lazy_clones) = count_engine_clones(|| solve_level(level, 50).unwrap());
+        assert_eq!(Some(lazy), eager);
+        assert!(
+            lazy_clones * 2 < eager_clones,
+            "{lazy_clones} clones vs {eager_clones} eagerly"
+        );
+    }
+
+    #[test]
+    fn test_solve_level_all_optimal_finds_every_route_across_open_grid() {
+        // Exit two cells east and two south of the head: any ordering of the four moves.
+        let mut level = large_open_level(3);
+        level.snake = vec![Position::new(0, 0)];
+        level.food = Vec::new();
+        level.total_food = Some(0);
+        level.exit = Position::new(2, 2);
+
+        let shortest = solve_level(level.clone(), 10).unwrap();
+        let optimal = solve_level_all_optimal_with_limit(level.clone(), 10, 100).unwrap();
+        assert!(!optimal.capped);
+        assert_eq!(optimal.solutions.len(), 6);
+        assert!(optimal.solutions.contains(&shortest));
+        let distinct: HashSet<String> = optimal
+            .solutions
+            .iter()
+            .map(|solution| crate::playback::compact_directions(solution))
+            .collect();
+        assert_eq!(distinct.len(), 6);
+        assert!(optimal
+            .solutions
+            .iter()
+            .all(|solution| solution.len() == shortest.len()));
+
+        let capped = solve_level_all_optimal_with_limit(level, 10, 4).unwrap();
+        assert!(capped.capped);
+        assert_eq!(capped.solutions.len(), 4);
+    }
+
+    /// Open `size` x `size` room with food in the far corner, too large to search in 1ms
+    fn large_open_level(size: i32) -> LevelDefinition {
+        let level = json!({
+            "id": 3,
+            "name": "Large Open",
+            "difficulty": "hard",
+            "gridSize": { "width": size, "height": size },
+            "snake": [{ "x": 1, "y": 0 }, { "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [{ "x": size - 1, "y": size - 1 }, { "x": 0, "y": size - 1 }],
+            "exit": { "x": size - 1, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 2
+        });
+        serde_json::from_value(level).unwrap()
+    }
+
+    #[test]
+    fn test_solve_level_with_timeout_reports_timeout() {
+        let error =
+            solve_level_with_timeout(large_open_level(40), 500, Some(Duration::from_millis(1)))
+                .unwrap_err();
+
+        assert!(error.downcast_ref::<SolveTimeout>().is_some());
+        assert!(error.downcast_ref::<NoSolution>().is_none());
+        assert!(error.to_string().starts_with("Solver timed out after "));
+    }
+
+    #[test]
+    fn test_solve_level_with_timeout_solves_within_budget() {
+        let path =
+            solve_level_with_timeout(corridor_level(0), 20, Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(path, vec![Direction::East; 3]);
+    }
+
+    #[test]
+    fn test_solve_level_wallfollower_walks_corridor() {
+        let path = solve_level_wallfollower(corridor_level(0), 20).unwrap();
+        assert_eq!(path, vec![Direction::East; 3]);
+    }
+
+    #[test]
+    fn test_solve_level_wallfollower_rejects_levels_with_food() {
+        let error = solve_level_wallfollower(corridor_level(1), 20).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("only supports levels without food"));
+    }
+
+    #[test]
+    fn test_solve_level_wallfollower_respects_max_depth() {
+        let error = solve_level_wallfollower(corridor_level(0), 2).unwrap_err();
+        assert!(error.to_string().contains("within 2 moves"));
+    }
+
+    #[test]
+    fn test_solve_strategy_optimality() {
+        assert!(SolveStrategy::Bfs.is_optimal());
+        assert!(!SolveStrategy::Beam(8).is_optimal());
+        assert!(!SolveStrategy::WallFollower.is_optimal());
+    }
+
+    #[test]
+    fn test_navigation_exit_only_for_levels_without_food() {
+        assert_eq!(navigation_exit(&corridor_level(0)), Some((4, 1)));
+        assert_eq!(navigation_exit(&corridor_level(1)), None);
+    }
+
+    #[test]
+    fn test_is_level_complete_treats_head_on_navigation_exit_as_complete() {
+        // totalFood = 1 keeps the engine in Playing; the head sits at (1, 1).
+        let engine = GameEngine::new(corridor_level(1)).unwrap();
+        assert_eq!(engine.game_state().status, GameStatus::Playing);
+
+        assert!(is_level_complete(&engine, Some((1, 1))));
+        assert!(!is_level_complete(&engine, Some((4, 1))));
+        assert!(!is_level_complete(&engine, None));
+    }
+
+    #[test]
+    fn test_solve_level_completes_navigation_only_level() {
+        let path = solve_level(corridor_level(0), 20).unwrap();
+        assert_eq!(path, vec![Direction::East; 3]);
     }
 }
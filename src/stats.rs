@@ -0,0 +1,339 @@
+use crate::{
+    analysis::{analyze_level, LevelAnalysis, SymmetrySet},
+    levels, solver,
+};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// Serialization format for analysis output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+/// Restricts `stats` to levels with a given kind of symmetry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SymmetryFilter {
+    /// Any of the transforms below
+    Any,
+    /// Unchanged when flipped left to right
+    MirrorLeftRight,
+    /// Unchanged when flipped top to bottom
+    MirrorTopBottom,
+    /// Unchanged when rotated by 180 degrees
+    #[value(name = "rotation-180")]
+    Rotation180,
+    /// Levels with no symmetry at all
+    None,
+}
+
+impl SymmetryFilter {
+    fn matches(self, symmetry: &SymmetrySet) -> bool {
+        match self {
+            SymmetryFilter::Any => symmetry.any(),
+            SymmetryFilter::MirrorLeftRight => symmetry.mirror_left_right,
+            SymmetryFilter::MirrorTopBottom => symmetry.mirror_top_bottom,
+            SymmetryFilter::Rotation180 => symmetry.rotation_180,
+            SymmetryFilter::None => !symmetry.any(),
+        }
+    }
+}
+
+/// Analysis of one catalog level, keyed by where it lives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelStats {
+    pub difficulty: String,
+    pub file: String,
+    pub name: String,
+    pub analysis: LevelAnalysis,
+}
+
+/// Analysis of every level referenced by the levels.toml files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogStats {
+    pub level_count: usize,
+    pub levels_per_difficulty: BTreeMap<String, usize>,
+    pub levels: Vec<LevelStats>,
+}
+
+/// Prints the analysis of one level, as a human-readable report unless a format is given
+pub fn run_analyze(level_path: &Path, format: Option<OutputFormat>) -> Result<()> {
+    let level = solver::load_level(level_path)
+        .with_context(|| format!("Failed to load level: {}", level_path.display()))?;
+    let analysis = analyze_level(&level);
+    match format {
+        Some(format) => println!("{}", serialize(&analysis, format)?),
+        None => println!("{}", format_analysis_report(&analysis)),
+    }
+    Ok(())
+}
+
+fn format_analysis_report(analysis: &LevelAnalysis) -> String {
+    let mechanics = &analysis.mechanics;
+    let mechanics = [
+        (mechanics.has_floating_food, "floating food"),
+        (mechanics.has_falling_food, "falling food"),
+        (mechanics.has_stones, "stones"),
+        (mechanics.has_spikes, "spikes"),
+    ];
+    let symmetry = [
+        (analysis.symmetry.mirror_left_right, "mirror left-right"),
+        (analysis.symmetry.mirror_top_bottom, "mirror top-bottom"),
+        (analysis.symmetry.rotation_180, "rotation 180"),
+    ];
+    let list = |flags: &[(bool, &str)]| {
+        let names: Vec<&str> = flags
+            .iter()
+            .filter(|(present, _)| *present)
+            .map(|(_, name)| *name)
+            .collect();
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+
+    format!(
+        "Mechanics: {}\nObstacle pattern: {:?}\nObstacle density: {:.1}%\nFood: {}\nGrid area: {}\nDead ends: {} (corridor ratio {:.1}%)\nSymmetry: {}",
+        list(&mechanics),
+        analysis.pattern,
+        analysis.complexity.obstacle_density * 100.0,
+        analysis.complexity.food_count,
+        analysis.complexity.grid_area,
+        analysis.complexity.dead_ends,
+        analysis.complexity.corridor_ratio * 100.0,
+        list(&symmetry)
+    )
+}
+
+pub fn run_stats(
+    levels_root: &Path,
+    format: OutputFormat,
+    symmetry: Option<SymmetryFilter>,
+) -> Result<()> {
+    println!(
+        "{}",
+        serialize(&collect_stats(levels_root, symmetry)?, format)?
+    );
+    Ok(())
+}
+
+pub fn collect_stats(levels_root: &Path, symmetry: Option<SymmetryFilter>) -> Result<CatalogStats> {
+    let mut levels = Vec::new();
+    let mut levels_per_difficulty = BTreeMap::new();
+
+    for difficulty in &levels::catalog_difficulties(levels_root) {
+        let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
+        if !levels_toml_path.exists() {
+            continue;
+        }
+        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+
+        for entry in &levels_toml.level {
+            let Some(file) = entry.file.as_deref() else {
+                continue;
+            };
+            let level_path = levels_root.join(difficulty).join(file);
+            let level = solver::load_level(&level_path)
+                .with_context(|| format!("Failed to load level: {}", level_path.display()))?;
+            let analysis = analyze_level(&level);
+            if symmetry.is_some_and(|filter| !filter.matches(&analysis.symmetry)) {
+                continue;
+            }
+            *levels_per_difficulty
+                .entry(difficulty.to_string())
+                .or_insert(0) += 1;
+            levels.push(LevelStats {
+                difficulty: difficulty.to_string(),
+                file: file.to_string(),
+                name: level.name.clone(),
+                analysis,
+            });
+        }
+    }
+
+    Ok(CatalogStats {
+        level_count: levels.len(),
+        levels_per_difficulty,
+        levels,
+    })
+}
+
+fn serialize<T: Serialize>(value: &T, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(value).with_context(|| "Failed to serialize JSON")
+        },
+        OutputFormat::Toml => {
+            toml::to_string_pretty(value).with_context(|| "Failed to serialize TOML")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{ComplexityMetrics, LevelMechanics, ObstaclePattern};
+    use crate::levels::{write_levels_toml, LevelMeta, LevelsToml};
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn sample_analysis() -> LevelAnalysis {
+        LevelAnalysis {
+            mechanics: LevelMechanics {
+                has_floating_food: true,
+                has_falling_food: false,
+                has_stones: true,
+                has_spikes: false,
+            },
+            pattern: ObstaclePattern::VerticalWall,
+            complexity: ComplexityMetrics {
+                obstacle_density: 0.15,
+                food_count: 3,
+                grid_area: 100,
+                dead_ends: 2,
+                corridor_ratio: 0.25,
+            },
+            symmetry: SymmetrySet {
+                mirror_left_right: true,
+                mirror_top_bottom: false,
+                rotation_180: false,
+            },
+        }
+    }
+
+    fn sample_stats() -> CatalogStats {
+        CatalogStats {
+            level_count: 1,
+            levels_per_difficulty: BTreeMap::from([("easy".to_string(), 1)]),
+            levels: vec![LevelStats {
+                difficulty: "easy".to_string(),
+                file: "level_001.json".to_string(),
+                name: "Sample".to_string(),
+                analysis: sample_analysis(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_level_analysis_round_trips_through_toml_and_json() {
+        let analysis = sample_analysis();
+
+        let toml_output = serialize(&analysis, OutputFormat::Toml).unwrap();
+        assert!(toml_output.contains("pattern = \"VerticalWall\""));
+        assert_eq!(
+            toml::from_str::<LevelAnalysis>(&toml_output).unwrap(),
+            analysis
+        );
+
+        let json_output = serialize(&analysis, OutputFormat::Json).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LevelAnalysis>(&json_output).unwrap(),
+            analysis
+        );
+    }
+
+    #[test]
+    fn test_analyze_vertical_wall_fixture_round_trips_through_json() {
+        let level: gsnake_core::LevelDefinition = serde_json::from_value(json!({
+            "id": 1,
+            "name": "Wall",
+            "difficulty": "easy",
+            "gridSize": { "width": 6, "height": 4 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [{ "x": 3, "y": 1 }, { "x": 3, "y": 2 }, { "x": 3, "y": 3 }],
+            "food": [],
+            "exit": { "x": 5, "y": 3 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        }))
+        .unwrap();
+        let analysis = analyze_level(&level);
+
+        let json_output = serialize(&analysis, OutputFormat::Json).unwrap();
+        assert!(json_output.contains("\"pattern\": \"VerticalWall\""));
+        assert_eq!(
+            serde_json::from_str::<LevelAnalysis>(&json_output).unwrap(),
+            analysis
+        );
+    }
+
+    #[test]
+    fn test_format_analysis_report_lists_present_mechanics() {
+        let report = format_analysis_report(&sample_analysis());
+        assert_eq!(
+            report,
+            "Mechanics: floating food, stones\nObstacle pattern: VerticalWall\nObstacle density: 15.0%\nFood: 3\nGrid area: 100\nDead ends: 2 (corridor ratio 25.0%)\nSymmetry: mirror left-right"
+        );
+    }
+
+    #[test]
+    fn test_catalog_stats_round_trip_through_toml() {
+        let stats = sample_stats();
+        let toml_output = serialize(&stats, OutputFormat::Toml).unwrap();
+        assert!(toml_output.contains("[[levels]]"));
+        assert_eq!(toml::from_str::<CatalogStats>(&toml_output).unwrap(), stats);
+    }
+
+    #[test]
+    fn test_collect_stats_reads_levels_toml_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let easy_dir = temp_dir.path().join("easy");
+        fs::create_dir_all(&easy_dir).unwrap();
+        let level = json!({
+            "id": 1,
+            "name": "Stats Level",
+            "difficulty": "easy",
+            "gridSize": { "width": 4, "height": 4 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [{ "x": 2, "y": 2 }],
+            "food": [{ "x": 1, "y": 0 }],
+            "exit": { "x": 3, "y": 3 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 1
+        });
+        fs::write(easy_dir.join("a.json"), level.to_string()).unwrap();
+        write_levels_toml(
+            &easy_dir.join("levels.toml"),
+            &LevelsToml {
+                level: vec![LevelMeta {
+                    id: Some("a".to_string()),
+                    file: Some("a.json".to_string()),
+                    author: None,
+                    solved: None,
+                    difficulty: Some("easy".to_string()),
+                    tags: None,
+                    description: None,
+                }],
+            },
+        )
+        .unwrap();
+
+        let stats = collect_stats(temp_dir.path(), None).unwrap();
+        assert_eq!(stats.level_count, 1);
+        assert_eq!(stats.levels_per_difficulty.get("easy"), Some(&1));
+        assert_eq!(stats.levels[0].name, "Stats Level");
+        assert_eq!(stats.levels[0].analysis.complexity.food_count, 1);
+        assert_eq!(stats.levels[0].analysis.complexity.grid_area, 16);
+
+        let symmetric = collect_stats(temp_dir.path(), Some(SymmetryFilter::Any)).unwrap();
+        assert_eq!(symmetric.level_count, 0);
+        assert!(symmetric.levels_per_difficulty.is_empty());
+        let asymmetric = collect_stats(temp_dir.path(), Some(SymmetryFilter::None)).unwrap();
+        assert_eq!(asymmetric.level_count, 1);
+    }
+}
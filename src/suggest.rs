@@ -0,0 +1,166 @@
+use crate::{analysis, levels, solver};
+use anyhow::Result;
+use std::path::Path;
+
+/// Declared and suggested difficulty of one levels.toml-referenced level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifficultySuggestion {
+    pub label: String,
+    pub declared: String,
+    pub suggested: &'static str,
+    /// Solver solution length, when solving was requested and succeeded
+    pub moves: Option<usize>,
+}
+
+impl DifficultySuggestion {
+    /// Whether the declared difficulty differs from the suggestion, ignoring ASCII case
+    pub fn disagrees(&self) -> bool {
+        !self.declared.eq_ignore_ascii_case(self.suggested)
+    }
+}
+
+pub fn run_suggest_difficulty(levels_root: &Path, solve_depth: Option<usize>) -> Result<()> {
+    let suggestions = suggest_difficulties(levels_root, solve_depth)?;
+
+    for suggestion in &suggestions {
+        let moves = suggestion
+            .moves
+            .map(|moves| format!(", {moves} moves"))
+            .unwrap_or_default();
+        let marker = if suggestion.disagrees() {
+            "  <- disagrees"
+        } else {
+            ""
+        };
+        println!(
+            "{}: declared {}, suggested {}{moves}{marker}",
+            suggestion.label, suggestion.declared, suggestion.suggested
+        );
+    }
+    let disagreements = suggestions.iter().filter(|s| s.disagrees()).count();
+    println!(
+        "{disagreements} of {} level(s) disagree with the suggested difficulty",
+        suggestions.len()
+    );
+    Ok(())
+}
+
+/// Classifies every levels.toml-referenced level, solving each one first when
+/// `solve_depth` is given so solution length can weigh in
+pub fn suggest_difficulties(
+    levels_root: &Path,
+    solve_depth: Option<usize>,
+) -> Result<Vec<DifficultySuggestion>> {
+    let mut suggestions = Vec::new();
+
+    for difficulty in &levels::catalog_difficulties(levels_root) {
+        let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
+        if !levels_toml_path.exists() {
+            continue;
+        }
+        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+
+        for entry in &levels_toml.level {
+            let Some(file) = entry.file.as_deref() else {
+                continue;
+            };
+            let level = solver::load_level(&levels_root.join(difficulty).join(file))?;
+            let moves = solve_depth.and_then(|max_depth| {
+                solver::solve_level(level.clone(), max_depth)
+                    .ok()
+                    .map(|solution| solution.len())
+            });
+
+            suggestions.push(DifficultySuggestion {
+                label: format!("{difficulty}/{file}"),
+                declared: level
+                    .difficulty
+                    .clone()
+                    .unwrap_or_else(|| difficulty.to_string()),
+                suggested: analysis::classify_difficulty(&analysis::analyze_level(&level), moves),
+                moves,
+            });
+        }
+    }
+
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{write_levels_toml, LevelMeta, LevelsToml};
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_level(dir: &Path, file: &str, difficulty: &str, spikes: serde_json::Value) {
+        fs::create_dir_all(dir).unwrap();
+        let level = json!({
+            "id": 1,
+            "name": file,
+            "difficulty": difficulty,
+            "gridSize": { "width": 10, "height": 3 },
+            "snake": [{ "x": 0, "y": 1 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 9, "y": 1 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [{ "x": 5, "y": 0 }],
+            "spikes": spikes,
+            "totalFood": 0
+        });
+        fs::write(
+            dir.join(file),
+            serde_json::to_string_pretty(&level).unwrap(),
+        )
+        .unwrap();
+        write_levels_toml(
+            &dir.join("levels.toml"),
+            &LevelsToml {
+                level: vec![LevelMeta {
+                    id: Some(file.trim_end_matches(".json").to_string()),
+                    file: Some(file.to_string()),
+                    author: None,
+                    solved: None,
+                    difficulty: Some(difficulty.to_string()),
+                    tags: None,
+                    description: None,
+                }],
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_suggest_difficulties_flags_misfiled_levels() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        // Stones plus spikes score as hard; stones alone as medium.
+        write_level(
+            &levels_root.join("easy"),
+            "spiked.json",
+            "easy",
+            json!([{ "x": 3, "y": 2 }]),
+        );
+        // Declared difficulty casing doesn't count as a disagreement.
+        write_level(
+            &levels_root.join("medium"),
+            "stones.json",
+            "Medium",
+            json!([]),
+        );
+
+        let suggestions = suggest_difficulties(&levels_root, None).unwrap();
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].label, "easy/spiked.json");
+        assert_eq!(suggestions[0].suggested, "hard");
+        assert!(suggestions[0].disagrees());
+        assert_eq!(suggestions[1].declared, "Medium");
+        assert_eq!(suggestions[1].suggested, "medium");
+        assert!(!suggestions[1].disagrees());
+        assert_eq!(suggestions[1].moves, None);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    analysis, levels,
+    solver::{self, total_food},
+};
+use anyhow::Result;
+use gsnake_core::LevelDefinition;
+use std::path::Path;
+
+/// A solved level whose solution trips one or more quirk heuristics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspiciousLevel {
+    pub label: String,
+    pub moves: usize,
+    pub food: usize,
+    pub reasons: Vec<String>,
+}
+
+pub fn run_suspicious(levels_root: &Path, max_depth: usize) -> Result<()> {
+    let (ranked, solved) = audit_levels(levels_root, max_depth)?;
+
+    if ranked.is_empty() {
+        println!("No suspicious levels among {solved} solved level(s)");
+        return Ok(());
+    }
+
+    println!(
+        "Suspicious levels ({} of {solved} solved), most suspicious first:",
+        ranked.len()
+    );
+    for (rank, level) in ranked.iter().enumerate() {
+        println!(
+            "  {}. {} ({} moves, {} food)",
+            rank + 1,
+            level.label,
+            level.moves,
+            level.food
+        );
+        for reason in &level.reasons {
+            println!("      - {reason}");
+        }
+    }
+    Ok(())
+}
+
+/// Solves every levels.toml-referenced level and returns the flagged ones, ranked,
+/// along with the number of levels that were solved
+pub fn audit_levels(levels_root: &Path, max_depth: usize) -> Result<(Vec<SuspiciousLevel>, usize)> {
+    let mut flagged = Vec::new();
+    let mut solved = 0;
+
+    for difficulty in &levels::catalog_difficulties(levels_root) {
+        let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
+        if !levels_toml_path.exists() {
+            continue;
+        }
+        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+
+        for entry in &levels_toml.level {
+            let Some(file) = entry.file.as_deref() else {
+                continue;
+            };
+            let level = solver::load_level(&levels_root.join(difficulty).join(file))?;
+            let Ok(solution) = solver::solve_level(level.clone(), max_depth) else {
+                continue;
+            };
+            solved += 1;
+
+            let reasons = suspicion_reasons(&level, solution.len());
+            if !reasons.is_empty() {
+                flagged.push(SuspiciousLevel {
+                    label: format!("{difficulty}/{file}"),
+                    moves: solution.len(),
+                    food: total_food(&level),
+                    reasons,
+                });
+            }
+        }
+    }
+
+    rank(&mut flagged);
+    Ok((flagged, solved))
+}
+
+/// Heuristics for solutions that likely lean on an engine quirk rather than intended play.
+///
+/// Collecting `n` food and then stepping onto the exit needs at least `n + 1` moves, so
+/// a shorter optimal solution points at food being skipped. A snake that must grow to
+/// fill every reachable cell leaves no slack for intended routing.
+pub fn suspicion_reasons(level: &LevelDefinition, moves: usize) -> Vec<String> {
+    let mut reasons = Vec::new();
+    let food = total_food(level);
+
+    if food > 0 && moves <= food {
+        reasons.push(format!(
+            "optimal solution uses {moves} move(s) for {food} food (possible skip)"
+        ));
+    }
+
+    let final_length = level.snake.len() + food;
+    let reachable = analysis::reachable_cells(level).len();
+    if final_length >= reachable {
+        reasons.push(format!(
+            "snake grows to {final_length} segment(s) with only {reachable} reachable cell(s)"
+        ));
+    }
+
+    reasons
+}
+
+/// Most reasons first, then fewest moves per food, then by label for stable output
+fn rank(levels: &mut [SuspiciousLevel]) {
+    levels.sort_by(|a, b| {
+        b.reasons
+            .len()
+            .cmp(&a.reasons.len())
+            .then_with(|| moves_per_food(a).total_cmp(&moves_per_food(b)))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+}
+
+fn moves_per_food(level: &SuspiciousLevel) -> f64 {
+    if level.food == 0 {
+        f64::INFINITY
+    } else {
+        level.moves as f64 / level.food as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn corridor_level(width: i32, food: &[i32], total_food: u32) -> LevelDefinition {
+        let floor: Vec<_> = (0..width).map(|x| json!({ "x": x, "y": 1 })).collect();
+        let food: Vec<_> = food.iter().map(|x| json!({ "x": x, "y": 0 })).collect();
+        serde_json::from_value(json!({
+            "id": 1,
+            "name": "Corridor",
+            "difficulty": "easy",
+            "gridSize": { "width": width, "height": 2 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": floor,
+            "food": food,
+            "exit": { "x": width - 1, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": total_food
+        }))
+        .unwrap()
+    }
+
+    fn flagged(label: &str, moves: usize, food: usize, reasons: usize) -> SuspiciousLevel {
+        SuspiciousLevel {
+            label: label.to_string(),
+            moves,
+            food,
+            reasons: vec!["reason".to_string(); reasons],
+        }
+    }
+
+    #[test]
+    fn test_suspicion_reasons_accepts_ordinary_solution() {
+        let level = corridor_level(8, &[2], 1);
+        assert!(suspicion_reasons(&level, 7).is_empty());
+    }
+
+    #[test]
+    fn test_suspicion_reasons_flags_solution_shorter_than_food_count() {
+        let level = corridor_level(8, &[2, 3, 4], 3);
+        let reasons = suspicion_reasons(&level, 3);
+        assert_eq!(reasons.len(), 1);
+        assert!(reasons[0].contains("3 move(s) for 3 food"));
+    }
+
+    #[test]
+    fn test_suspicion_reasons_flags_snake_filling_reachable_cells() {
+        // One starting segment plus three food fills the four open cells.
+        let level = corridor_level(4, &[1, 2, 3], 3);
+        let reasons = suspicion_reasons(&level, 4);
+        assert_eq!(reasons.len(), 1);
+        assert!(reasons[0].contains("grows to 4 segment(s) with only 4 reachable cell(s)"));
+    }
+
+    #[test]
+    fn test_rank_orders_by_reasons_then_moves_per_food() {
+        let mut levels = vec![
+            flagged("easy/b.json", 6, 3, 1),
+            flagged("easy/a.json", 3, 3, 1),
+            flagged("hard/c.json", 20, 2, 2),
+        ];
+        rank(&mut levels);
+
+        let labels: Vec<_> = levels.iter().map(|level| level.label.as_str()).collect();
+        assert_eq!(labels, vec!["hard/c.json", "easy/a.json", "easy/b.json"]);
+    }
+}
@@ -1,12 +1,18 @@
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::num::NonZeroUsize;
 use std::path::Path;
 
-use crate::levels::DEFAULT_DIFFICULTIES;
-use crate::name_generator::generate_names_for_directory;
+use crate::levels::{catalog_difficulties, resolve_difficulty_filter};
+use crate::name_generator::{
+    existing_level_names, generate_names_for_directory_with_options, NamingOptions,
+    DEFAULT_GENERIC_NAME_THRESHOLD,
+};
 use crate::playback_generator::{
     generate_all_playbacks, generate_playbacks_for_difficulty, update_solved_status_from_results,
+    PlaybackOptions,
 };
+use crate::sync_progress::SyncProgress;
 use crate::toml_generator::{generate_all_levels_toml, generate_levels_toml};
 
 #[derive(Debug)]
@@ -14,40 +20,85 @@ pub struct SyncSummary {
     pub names_generated: usize,
     pub toml_files_updated: usize,
     pub playbacks_created: usize,
+    pub per_difficulty: BTreeMap<String, DifficultyStats>,
 }
 
-/// Sync metadata for all difficulties or a specific one
-pub fn sync_metadata(difficulty: Option<&str>) -> Result<SyncSummary> {
-    let levels_root = crate::levels::find_levels_root()?;
-    let playbacks_root = levels_root
-        .parent()
-        .map(|parent| parent.join("playbacks"))
-        .unwrap_or_else(|| Path::new("playbacks").to_path_buf());
-    sync_metadata_with_roots(&levels_root, &playbacks_root, difficulty)
+impl SyncSummary {
+    /// `difficulty/level_id` of every level left unsolved, in difficulty order
+    pub fn unsolved(&self) -> Vec<String> {
+        self.per_difficulty
+            .iter()
+            .flat_map(|(difficulty, stats)| {
+                stats
+                    .unsolved
+                    .iter()
+                    .map(move |level_id| format!("{difficulty}/{level_id}"))
+            })
+            .collect()
+    }
 }
 
-fn resolve_difficulties(difficulty: Option<&str>) -> Result<Vec<&'static str>> {
-    if let Some(raw) = difficulty {
-        let normalized = raw.trim().to_ascii_lowercase();
-        if normalized.is_empty() {
-            anyhow::bail!("Difficulty filter cannot be empty");
-        }
+/// What a sync did for one difficulty
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DifficultyStats {
+    pub names: usize,
+    pub toml_written: bool,
+    pub solved: usize,
+    /// Ids of levels without a verified playback after the sync
+    pub unsolved: Vec<String>,
+}
 
-        if let Some(selected) = DEFAULT_DIFFICULTIES
-            .iter()
-            .copied()
-            .find(|item| *item == normalized)
-        {
-            return Ok(vec![selected]);
-        }
+/// Options for a metadata sync run
+#[derive(Debug, Clone, Copy)]
+pub struct SyncOptions {
+    /// Skip levels recorded as done in the `.sync-progress.json` checkpoint of an interrupted run
+    pub resume: bool,
+    /// Warn when more than this fraction of a difficulty's names are generic
+    pub generic_name_threshold: f64,
+    /// Leave hand-written level names alone and only name unnamed or auto-named levels
+    pub keep_names: bool,
+    /// Seed for reproducible adjective picks when generated names collide
+    pub name_seed: Option<u64>,
+    /// Replay every freshly generated playback and mark it unsolved if it does not complete
+    pub self_check: bool,
+    /// Re-solve every level, even when its playback is newer than the level JSON
+    pub force: bool,
+    /// Print a line per level while playbacks are generated
+    pub show_progress: bool,
+    /// Cap on levels solved at once; defaults to the available parallelism
+    pub jobs: Option<NonZeroUsize>,
+}
 
-        anyhow::bail!(
-            "Unknown difficulty '{}'. Expected one of: easy, medium, hard",
-            raw
-        );
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            resume: true,
+            generic_name_threshold: DEFAULT_GENERIC_NAME_THRESHOLD,
+            keep_names: false,
+            name_seed: None,
+            self_check: false,
+            force: false,
+            show_progress: false,
+            jobs: None,
+        }
     }
+}
+
+/// Sync metadata for all difficulties or a specific one, writing playbacks next to `levels_root`
+pub fn sync_metadata(
+    levels_root: &Path,
+    difficulty: Option<&str>,
+    options: SyncOptions,
+) -> Result<SyncSummary> {
+    let playbacks_root = crate::levels::playbacks_root(levels_root);
+    sync_metadata_with_options(levels_root, &playbacks_root, difficulty, options)
+}
 
-    Ok(DEFAULT_DIFFICULTIES.to_vec())
+fn resolve_difficulties(levels_root: &Path, difficulty: Option<&str>) -> Result<Vec<String>> {
+    match difficulty {
+        Some(raw) => Ok(vec![resolve_difficulty_filter(levels_root, raw)?]),
+        None => Ok(catalog_difficulties(levels_root)),
+    }
 }
 
 /// Sync metadata using explicit levels/playbacks roots.
@@ -55,15 +106,49 @@ pub fn sync_metadata_with_roots(
     levels_root: &Path,
     playbacks_root: &Path,
     difficulty: Option<&str>,
+) -> Result<SyncSummary> {
+    sync_metadata_with_options(
+        levels_root,
+        playbacks_root,
+        difficulty,
+        SyncOptions::default(),
+    )
+}
+
+/// Sync metadata using explicit roots and options.
+///
+/// Progress is checkpointed after each playback so an interrupted run can pick up where it
+/// stopped; the checkpoint is removed once the sync completes.
+pub fn sync_metadata_with_options(
+    levels_root: &Path,
+    playbacks_root: &Path,
+    difficulty: Option<&str>,
+    options: SyncOptions,
 ) -> Result<SyncSummary> {
     if !levels_root.exists() {
         anyhow::bail!("Levels directory not found: {}", levels_root.display());
     }
 
-    let difficulties = resolve_difficulties(difficulty)?;
+    let difficulties = resolve_difficulties(levels_root, difficulty)?;
 
     let mut total_names = 0;
     let mut used_names = HashSet::new();
+    let mut per_difficulty: BTreeMap<String, DifficultyStats> = BTreeMap::new();
+
+    // Reserve the names of difficulties this run leaves alone, so syncing one difficulty
+    // never hands out a name a sibling difficulty already carries.
+    for sibling in catalog_difficulties(levels_root)
+        .into_iter()
+        .filter(|diff| !difficulties.contains(diff))
+    {
+        let sibling_path = levels_root.join(&sibling);
+        if sibling_path.is_dir() {
+            used_names.extend(
+                existing_level_names(&sibling_path, None)
+                    .with_context(|| format!("Failed to read level names in {}", sibling))?,
+            );
+        }
+    }
 
     // Step 1: Generate names for all levels
     println!("Generating level names...");
@@ -74,22 +159,31 @@ pub fn sync_metadata_with_roots(
             continue;
         }
 
-        let results = generate_names_for_directory(&diff_path, &mut used_names)
-            .with_context(|| format!("Failed to generate names for {}", diff))?;
+        let results = generate_names_for_directory_with_options(
+            &diff_path,
+            &mut used_names,
+            NamingOptions {
+                generic_threshold: options.generic_name_threshold,
+                overwrite: !options.keep_names,
+                seed: options.name_seed,
+            },
+        )
+        .with_context(|| format!("Failed to generate names for {}", diff))?;
 
         println!("  {}: {} names generated", diff, results.len());
         total_names += results.len();
+        per_difficulty.entry(diff.clone()).or_default().names = results.len();
     }
 
-    // Step 2: Generate levels.toml files
+    // Step 2: Generate levels.toml files, keeping curated author, tags and descriptions
     println!("Generating levels.toml files...");
     let toml_results = if difficulty.is_some() {
         // Single difficulty
-        let diff = difficulties[0];
+        let diff = &difficulties[0];
         let diff_path = levels_root.join(diff);
         generate_levels_toml(&diff_path, diff)
             .with_context(|| format!("Failed to generate levels.toml for {}", diff))?;
-        vec![format!("levels/{}/levels.toml", diff)]
+        vec![diff.clone()]
     } else {
         // All difficulties
         generate_all_levels_toml(levels_root)
@@ -97,34 +191,74 @@ pub fn sync_metadata_with_roots(
     };
 
     println!("  {} levels.toml files updated", toml_results.len());
+    for diff in &toml_results {
+        per_difficulty.entry(diff.clone()).or_default().toml_written = true;
+    }
 
     // Step 3: Generate playbacks
     println!("Generating playbacks...");
-    let max_depth = 500; // Default from US-006
+    let playback_options = PlaybackOptions {
+        max_depth: 500, // Default from US-006
+        verify_after: options.self_check,
+        force: options.force,
+        show_progress: options.show_progress,
+        jobs: options.jobs,
+    };
+    let mut progress = if options.resume {
+        SyncProgress::load(levels_root)?
+    } else {
+        SyncProgress::fresh(levels_root)
+    };
 
     let playback_results = if difficulty.is_some() {
-        let diff = difficulties[0];
+        let diff = &difficulties[0];
         let levels_dir = levels_root.join(diff);
         let playbacks_dir = playbacks_root.join(diff);
-        generate_playbacks_for_difficulty(&levels_dir, &playbacks_dir, max_depth)
-            .with_context(|| format!("Failed to generate playbacks for {}", diff))?
+        generate_playbacks_for_difficulty(
+            &levels_dir,
+            &playbacks_dir,
+            playback_options,
+            Some(&mut progress),
+        )
+        .with_context(|| format!("Failed to generate playbacks for {}", diff))?
     } else {
-        generate_all_playbacks(levels_root, playbacks_root, max_depth)
-            .with_context(|| "Failed to generate playbacks")?
+        generate_all_playbacks(
+            levels_root,
+            playbacks_root,
+            playback_options,
+            Some(&mut progress),
+        )
+        .with_context(|| "Failed to generate playbacks")?
     };
 
     let solved_count = playback_results.iter().filter(|r| r.solved).count();
     println!("  {} playbacks created", solved_count);
+    for result in &playback_results {
+        let diff = result
+            .level_path
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let stats = per_difficulty.entry(diff).or_default();
+        if result.solved {
+            stats.solved += 1;
+        } else {
+            stats.unsolved.push(result.level_id.clone());
+        }
+    }
 
     // Step 4: Update solved status in levels.toml
     println!("Updating solved status...");
     update_solved_status_from_results(&playback_results)
         .with_context(|| "Failed to update solved status")?;
+    progress.remove()?;
 
     Ok(SyncSummary {
         names_generated: total_names,
         toml_files_updated: toml_results.len(),
         playbacks_created: solved_count,
+        per_difficulty,
     })
 }
 
@@ -147,7 +281,7 @@ mod tests {
         let levels_root = temp_dir.path().join("levels");
         let playbacks_root = temp_dir.path().join("playbacks");
 
-        create_difficulty_dirs(&levels_root, &DEFAULT_DIFFICULTIES)?;
+        create_difficulty_dirs(&levels_root, &crate::levels::DEFAULT_DIFFICULTIES)?;
 
         let summary = sync_metadata_with_roots(&levels_root, &playbacks_root, None)?;
         assert_eq!(summary.names_generated, 0);
@@ -207,16 +341,15 @@ mod tests {
 
     #[test]
     fn test_sync_metadata_resolves_levels_root_from_package_directory() -> Result<()> {
-        let _lock = crate::test_cwd::cwd_mutex()
-            .lock()
-            .expect("Failed to lock cwd mutex");
-
         let temp_dir = TempDir::new()?;
         let levels_root = temp_dir.path().join("levels");
-        create_difficulty_dirs(&levels_root, &DEFAULT_DIFFICULTIES)?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
+        create_difficulty_dirs(&levels_root, &crate::levels::DEFAULT_DIFFICULTIES)?;
 
-        let summary = sync_metadata(None)?;
+        let summary = sync_metadata(
+            &crate::levels::find_levels_root_from(temp_dir.path())?,
+            None,
+            SyncOptions::default(),
+        )?;
         assert_eq!(summary.toml_files_updated, 3);
         assert!(levels_root.join("easy/levels.toml").exists());
         Ok(())
@@ -224,19 +357,95 @@ mod tests {
 
     #[test]
     fn test_sync_metadata_resolves_levels_root_from_repo_root() -> Result<()> {
-        let _lock = crate::test_cwd::cwd_mutex()
-            .lock()
-            .expect("Failed to lock cwd mutex");
-
         let temp_dir = TempDir::new()?;
         let package_root = temp_dir.path().join("gsnake-levels");
         let levels_root = package_root.join("levels");
-        create_difficulty_dirs(&levels_root, &DEFAULT_DIFFICULTIES)?;
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
+        create_difficulty_dirs(&levels_root, &crate::levels::DEFAULT_DIFFICULTIES)?;
 
-        let summary = sync_metadata(None)?;
+        let summary = sync_metadata(
+            &crate::levels::find_levels_root_from(temp_dir.path())?,
+            None,
+            SyncOptions::default(),
+        )?;
         assert_eq!(summary.toml_files_updated, 3);
         assert!(levels_root.join("easy/levels.toml").exists());
         Ok(())
     }
+
+    fn write_corridor_level(path: &Path, exit_x: i32, walled: bool) -> Result<()> {
+        let obstacles = if walled {
+            serde_json::json!([{ "x": 2, "y": 0 }])
+        } else {
+            serde_json::json!([])
+        };
+        let level = serde_json::json!({
+            "id": 1,
+            "name": "Corridor",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 1 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": obstacles,
+            "food": [],
+            "exit": { "x": exit_x, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        });
+        fs::write(path, serde_json::to_string_pretty(&level)?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_metadata_summary_lists_unsolved_levels_per_difficulty() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let playbacks_root = temp_dir.path().join("playbacks");
+        create_difficulty_dirs(&levels_root, &crate::levels::DEFAULT_DIFFICULTIES)?;
+        write_corridor_level(&levels_root.join("easy/open.json"), 4, false)?;
+        // A wall between the snake and the exit makes this one unsolvable.
+        write_corridor_level(&levels_root.join("easy/sealed.json"), 4, true)?;
+
+        let summary = sync_metadata_with_roots(&levels_root, &playbacks_root, None)?;
+        let easy = &summary.per_difficulty["easy"];
+        assert_eq!(easy.names, 2);
+        assert!(easy.toml_written);
+        assert_eq!(easy.solved, 1);
+        assert_eq!(easy.unsolved, vec!["sealed".to_string()]);
+        assert!(summary.per_difficulty["hard"].toml_written);
+        assert_eq!(summary.unsolved(), vec!["easy/sealed".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_difficulty_syncs_keep_names_unique_across_difficulties() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let levels_root = temp_dir.path().join("levels");
+        let playbacks_root = temp_dir.path().join("playbacks");
+        create_difficulty_dirs(&levels_root, &crate::levels::DEFAULT_DIFFICULTIES)?;
+        // Identical layouts produce the same base name in both difficulties.
+        write_corridor_level(&levels_root.join("easy/a.json"), 4, false)?;
+        write_corridor_level(&levels_root.join("medium/a.json"), 4, false)?;
+        let read_name = |difficulty: &str| -> Result<String> {
+            let path = levels_root.join(difficulty).join("a.json");
+            let level: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+            Ok(level["name"].as_str().unwrap_or_default().to_string())
+        };
+
+        let mut previous = None;
+        for _ in 0..2 {
+            for difficulty in ["easy", "medium"] {
+                sync_metadata_with_roots(&levels_root, &playbacks_root, Some(difficulty))?;
+            }
+            let names = (read_name("easy")?, read_name("medium")?);
+            assert_ne!(names.0, names.1);
+            if let Some(previous) = &previous {
+                assert_eq!(&names, previous);
+            }
+            previous = Some(names);
+        }
+        Ok(())
+    }
 }
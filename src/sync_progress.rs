@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Checkpoint file written under the levels root while sync-metadata runs
+pub const SYNC_PROGRESS_FILE: &str = ".sync-progress.json";
+
+/// A level whose playback was already generated by an interrupted sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletedLevel {
+    pub content_hash: String,
+    pub solved: bool,
+}
+
+/// Levels completed so far, keyed by `<difficulty>/<file>`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncProgress {
+    #[serde(skip)]
+    path: PathBuf,
+    levels: BTreeMap<String, CompletedLevel>,
+}
+
+impl SyncProgress {
+    /// Start an empty checkpoint under `levels_root`, ignoring any existing one
+    pub fn fresh(levels_root: &Path) -> Self {
+        Self {
+            path: levels_root.join(SYNC_PROGRESS_FILE),
+            levels: BTreeMap::new(),
+        }
+    }
+
+    /// Load the checkpoint under `levels_root`, or start empty if there is none
+    pub fn load(levels_root: &Path) -> Result<Self> {
+        let path = levels_root.join(SYNC_PROGRESS_FILE);
+        if !path.exists() {
+            return Ok(Self::fresh(levels_root));
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read sync checkpoint: {}", path.display()))?;
+        let mut progress: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse sync checkpoint: {}", path.display()))?;
+        progress.path = path;
+        Ok(progress)
+    }
+
+    /// Solved status recorded for `key`, if its level JSON is unchanged since
+    pub fn completed(&self, key: &str, content_hash: &str) -> Option<bool> {
+        self.levels
+            .get(key)
+            .filter(|entry| entry.content_hash == content_hash)
+            .map(|entry| entry.solved)
+    }
+
+    /// Record `key` as done and write the checkpoint to disk
+    pub fn record(&mut self, key: String, content_hash: String, solved: bool) -> Result<()> {
+        self.levels.insert(
+            key,
+            CompletedLevel {
+                content_hash,
+                solved,
+            },
+        );
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&self.path, json + "\n")
+            .with_context(|| format!("Failed to write sync checkpoint: {}", self.path.display()))
+    }
+
+    /// Delete the checkpoint file once the sync has finished
+    pub fn remove(self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path).with_context(|| {
+                format!("Failed to remove sync checkpoint: {}", self.path.display())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// SHA-256 of a level file's contents, used to notice edits between runs. Unlike
+/// `DefaultHasher`, the digest is stable across Rust releases, so checkpoints stay valid.
+pub fn content_hash(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+/// Checkpoint key for a level file: `<difficulty dir>/<file name>`
+pub fn progress_key(level_path: &Path) -> String {
+    let file_name = level_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    match level_path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|s| s.to_str())
+    {
+        Some(dir) => format!("{dir}/{file_name}"),
+        None => file_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_persists_and_load_restores() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut progress = SyncProgress::load(temp_dir.path())?;
+        assert_eq!(progress.completed("easy/a.json", &content_hash(b"a")), None);
+
+        progress.record("easy/a.json".to_string(), content_hash(b"a"), true)?;
+        assert!(temp_dir.path().join(SYNC_PROGRESS_FILE).exists());
+
+        let reloaded = SyncProgress::load(temp_dir.path())?;
+        assert_eq!(
+            reloaded.completed("easy/a.json", &content_hash(b"a")),
+            Some(true)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_hash_is_sha256_hex() {
+        assert_eq!(
+            content_hash(b"a"),
+            "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb"
+        );
+    }
+
+    #[test]
+    fn test_completed_ignores_changed_content() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut progress = SyncProgress::fresh(temp_dir.path());
+        progress.record("easy/a.json".to_string(), content_hash(b"a"), false)?;
+
+        assert_eq!(
+            progress.completed("easy/a.json", &content_hash(b"a")),
+            Some(false)
+        );
+        assert_eq!(progress.completed("easy/a.json", &content_hash(b"b")), None);
+        assert_eq!(progress.completed("easy/b.json", &content_hash(b"a")), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fresh_ignores_existing_checkpoint_and_remove_deletes_it() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut progress = SyncProgress::fresh(temp_dir.path());
+        progress.record("easy/a.json".to_string(), content_hash(b"a"), true)?;
+
+        let fresh = SyncProgress::fresh(temp_dir.path());
+        assert_eq!(fresh.completed("easy/a.json", &content_hash(b"a")), None);
+
+        progress.remove()?;
+        assert!(!temp_dir.path().join(SYNC_PROGRESS_FILE).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_key_uses_difficulty_dir() {
+        assert_eq!(
+            progress_key(Path::new("levels/medium/level_003.json")),
+            "medium/level_003.json"
+        );
+    }
+}
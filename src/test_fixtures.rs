@@ -0,0 +1,25 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The checked-in `levels/easy` level JSON files, sorted by path
+pub fn easy_level_fixtures() -> Vec<PathBuf> {
+    let easy_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("levels/easy");
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(easy_dir)
+        .expect("Failed to read levels/easy")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension().and_then(|ext| ext.to_str()) == Some("json")).then_some(path)
+        })
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+pub fn first_easy_level_fixture() -> PathBuf {
+    easy_level_fixtures()
+        .into_iter()
+        .next()
+        .expect("Expected easy fixture")
+}
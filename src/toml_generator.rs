@@ -1,6 +1,9 @@
-use crate::levels::{LevelMeta, LevelsToml};
+use crate::levels::{
+    catalog_difficulties, read_levels_toml, write_toml_pretty, LevelMeta, LevelsToml,
+};
 use anyhow::{bail, Context, Result};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -11,6 +14,9 @@ struct LevelNameOnly {
 }
 
 /// Scans a difficulty directory for JSON files and generates levels.toml
+///
+/// Entries already listed in an existing levels.toml keep their `author`, `tags` and
+/// `description`; only the derived fields are refreshed.
 #[allow(dead_code)]
 pub fn generate_levels_toml(difficulty_dir: &Path, difficulty: &str) -> Result<()> {
     // Verify directory exists
@@ -21,6 +27,9 @@ pub fn generate_levels_toml(difficulty_dir: &Path, difficulty: &str) -> Result<(
         );
     }
 
+    let toml_path = difficulty_dir.join("levels.toml");
+    let mut existing = read_existing_metas(&toml_path)?;
+
     // Scan for JSON files
     let entries = fs::read_dir(difficulty_dir)
         .with_context(|| format!("Failed to read directory: {}", difficulty_dir.display()))?;
@@ -67,15 +76,24 @@ pub fn generate_levels_toml(difficulty_dir: &Path, difficulty: &str) -> Result<(
         let level_data: LevelNameOnly = serde_json::from_str(&contents)
             .with_context(|| format!("Failed to parse level JSON: {}", path.display()))?;
 
-        // Create the metadata entry
+        // Create the metadata entry, carrying over curated fields
+        let curated = existing.remove(&filename);
         let meta = LevelMeta {
             id: Some(id),
-            file: Some(filename),
-            author: Some("gsnake".to_string()),
+            author: curated
+                .as_ref()
+                .and_then(|meta| meta.author.clone())
+                .or_else(|| Some("gsnake".to_string())),
             solved: Some(true),
             difficulty: Some(difficulty.to_string()),
-            tags: Some(vec![]),
-            description: Some(level_data.name),
+            tags: curated
+                .as_ref()
+                .and_then(|meta| meta.tags.clone())
+                .or_else(|| Some(vec![])),
+            description: curated
+                .and_then(|meta| meta.description)
+                .or(Some(level_data.name)),
+            file: Some(filename),
         };
 
         level_metas.push(meta);
@@ -92,27 +110,29 @@ pub fn generate_levels_toml(difficulty_dir: &Path, difficulty: &str) -> Result<(
     let levels_toml = LevelsToml { level: level_metas };
 
     // Write to levels.toml in the difficulty directory
-    let toml_path = difficulty_dir.join("levels.toml");
-    let output = toml::to_string_pretty(&levels_toml).with_context(|| {
-        format!(
-            "Failed to serialize levels.toml for {}",
-            difficulty_dir.display()
-        )
-    })?;
+    write_toml_pretty(&toml_path, &levels_toml)
+}
 
-    fs::write(&toml_path, output)
-        .with_context(|| format!("Failed to write {}", toml_path.display()))?;
+/// Existing levels.toml entries keyed by file name, or none if there is no levels.toml yet
+fn read_existing_metas(toml_path: &Path) -> Result<HashMap<String, LevelMeta>> {
+    if !toml_path.exists() {
+        return Ok(HashMap::new());
+    }
 
-    Ok(())
+    let levels_toml = read_levels_toml(toml_path)?;
+    Ok(levels_toml
+        .level
+        .into_iter()
+        .filter_map(|meta| meta.file.clone().map(|file| (file, meta)))
+        .collect())
 }
 
 /// Generates levels.toml for all difficulty directories
 #[allow(dead_code)]
 pub fn generate_all_levels_toml(levels_root: &Path) -> Result<Vec<String>> {
-    let difficulties = ["easy", "medium", "hard"];
     let mut results = Vec::new();
 
-    for difficulty in &difficulties {
+    for difficulty in &catalog_difficulties(levels_root) {
         let difficulty_dir = levels_root.join(difficulty);
 
         if !difficulty_dir.exists() {
@@ -198,6 +218,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_generate_levels_toml_ends_with_single_newline_and_is_idempotent() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let easy_dir = temp_dir.path().join("easy");
+        fs::create_dir(&easy_dir)?;
+        create_test_level_json(&easy_dir, "level_001.json", "Test Level One")?;
+
+        generate_levels_toml(&easy_dir, "easy")?;
+        let first = fs::read(easy_dir.join("levels.toml"))?;
+        assert!(first.ends_with(b"\n") && !first.ends_with(b"\n\n"));
+
+        generate_levels_toml(&easy_dir, "easy")?;
+        assert_eq!(fs::read(easy_dir.join("levels.toml"))?, first);
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_levels_toml_preserves_curated_metadata() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let easy_dir = temp_dir.path().join("easy");
+        fs::create_dir(&easy_dir)?;
+
+        create_test_level_json(&easy_dir, "level_001.json", "Test Level One")?;
+        crate::levels::write_levels_toml(
+            &easy_dir.join("levels.toml"),
+            &LevelsToml {
+                level: vec![LevelMeta {
+                    id: Some("stale".to_string()),
+                    file: Some("level_001.json".to_string()),
+                    author: Some("alice".to_string()),
+                    solved: Some(false),
+                    difficulty: Some("medium".to_string()),
+                    tags: Some(vec!["tutorial".to_string()]),
+                    description: Some("Hand-written blurb".to_string()),
+                }],
+            },
+        )?;
+        create_test_level_json(&easy_dir, "level_002.json", "Test Level Two")?;
+
+        generate_levels_toml(&easy_dir, "easy")?;
+
+        let levels_toml = read_levels_toml(&easy_dir.join("levels.toml"))?;
+        assert_eq!(levels_toml.level.len(), 2);
+
+        let curated = &levels_toml.level[0];
+        assert_eq!(curated.id.as_deref(), Some("level_001"));
+        assert_eq!(curated.difficulty.as_deref(), Some("easy"));
+        assert_eq!(curated.author.as_deref(), Some("alice"));
+        assert_eq!(curated.tags, Some(vec!["tutorial".to_string()]));
+        assert_eq!(curated.description.as_deref(), Some("Hand-written blurb"));
+
+        let added = &levels_toml.level[1];
+        assert_eq!(added.author.as_deref(), Some("gsnake"));
+        assert_eq!(added.tags, Some(vec![]));
+        assert_eq!(added.description.as_deref(), Some("Test Level Two"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_generate_levels_toml_nonexistent_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,19 +1,30 @@
 use anyhow::Result;
 use gsnake_core::models::LevelDefinition;
-use std::{fs, path::Path, process};
-
-use crate::levels::{find_levels_root, LevelsToml, DEFAULT_DIFFICULTIES};
-
-/// Exit codes for validation failures
-const EXIT_CODE_VALIDATION_ERROR: i32 = 1;
-const EXIT_CODE_IO_ERROR: i32 = 2;
-const EXIT_CODE_PARSE_ERROR: i32 = 3;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::analysis::{derive_total_food, geometry_issues, unreachable_targets};
+use crate::levels::{catalog_difficulties, infer_playback_path, LevelsToml};
+use crate::playback::load_playback_directions;
+use crate::solver::direction_delta;
+
+/// Exit code when every issue is a validation, geometry or reachability problem
+pub const EXIT_CODE_VALIDATION_ERROR: i32 = 1;
+/// Exit code when a file could not be read and nothing failed to parse
+pub const EXIT_CODE_IO_ERROR: i32 = 2;
+/// Exit code when any levels.toml or level JSON failed to parse
+pub const EXIT_CODE_PARSE_ERROR: i32 = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ValidationIssueKind {
     Io,
     Parse,
     Validation,
+    Geometry,
+    Reachability,
 }
 
 impl ValidationIssueKind {
@@ -22,6 +33,8 @@ impl ValidationIssueKind {
             Self::Io => "io",
             Self::Parse => "parse",
             Self::Validation => "validation",
+            Self::Geometry => "geometry",
+            Self::Reachability => "reachability",
         }
     }
 }
@@ -33,7 +46,7 @@ struct ValidationIssue {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
-struct ValidationReport {
+pub struct ValidationReport {
     issues: Vec<ValidationIssue>,
 }
 
@@ -49,11 +62,26 @@ impl ValidationReport {
         self.issues.append(&mut other.issues);
     }
 
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.issues.is_empty()
     }
 
-    fn exit_code(&self) -> i32 {
+    /// Drops geometry and reachability issues, for callers that run those analyses
+    /// on their own
+    #[allow(dead_code)]
+    pub(crate) fn without_level_analysis(mut self) -> Self {
+        self.issues.retain(|issue| {
+            !matches!(
+                issue.kind,
+                ValidationIssueKind::Geometry | ValidationIssueKind::Reachability
+            )
+        });
+        self
+    }
+
+    /// Process exit code for a failed validation: parse errors win over I/O errors, which
+    /// win over everything else
+    pub fn exit_code(&self) -> i32 {
         if self
             .issues
             .iter()
@@ -71,50 +99,189 @@ impl ValidationReport {
         }
     }
 
-    fn format_for_stderr(&self) -> String {
+    /// One `N. [kind] message` line per issue, without any multi-line detail
+    pub(crate) fn summary_lines(&self) -> Vec<String> {
+        self.issues
+            .iter()
+            .enumerate()
+            .map(|(index, issue)| {
+                let summary = issue.message.lines().next().unwrap_or_default();
+                format!("{}. [{}] {summary}", index + 1, issue.kind.label())
+            })
+            .collect()
+    }
+
+    /// The summary header and one line per issue; detail lines such as source snippets
+    /// are indented under their issue
+    pub fn format_for_stderr(&self) -> String {
         let mut output = format!("Validation failed with {} issue(s):", self.issues.len());
-        for (index, issue) in self.issues.iter().enumerate() {
-            output.push_str(&format!(
-                "\n  {}. [{}] {}",
-                index + 1,
-                issue.kind.label(),
-                issue.message
-            ));
+        for (issue, summary) in self.issues.iter().zip(self.summary_lines()) {
+            output.push_str(&format!("\n  {summary}"));
+            for detail in issue.message.lines().skip(1) {
+                output.push_str(&format!("\n     {detail}"));
+            }
         }
 
         output
     }
 }
 
-pub fn run_validate_levels_toml() -> Result<()> {
-    let report = validate_all_levels_toml()?;
+/// Validate every levels.toml; with `fix`, rewrite level JSON `difficulty` fields that
+/// disagree with their folder instead of reporting them. With `with_playbacks`, also require
+/// a non-empty playback for every level marked solved.
+///
+/// Failures are printed to stderr and returned; the caller exits with
+/// [`ValidationReport::exit_code`]. With `quiet`, nothing is printed on success.
+pub fn run_validate_levels_toml(
+    levels_root: &Path,
+    fix: bool,
+    with_playbacks: bool,
+    quiet: bool,
+) -> ValidationReport {
+    let mut report = validate_all_levels_toml_with_root(levels_root, fix);
+    if with_playbacks {
+        report.extend(validate_solved_playbacks(
+            levels_root,
+            &catalog_difficulties(levels_root),
+        ));
+    }
 
     if report.is_empty() {
-        println!("✓ All levels.toml files are valid");
-        return Ok(());
+        if !quiet {
+            println!("✓ All levels.toml files are valid");
+        }
+    } else {
+        eprintln!("{}", report.format_for_stderr());
     }
+    report
+}
+
+pub(crate) fn validate_all_levels_toml_with_root(
+    levels_root: &Path,
+    fix: bool,
+) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let difficulties = catalog_difficulties(levels_root);
 
-    eprintln!("{}", report.format_for_stderr());
-    process::exit(report.exit_code());
+    for difficulty in &difficulties {
+        let difficulty_dir = levels_root.join(difficulty);
+        report.extend(validate_difficulty_levels_toml(
+            &difficulty_dir,
+            difficulty,
+            fix,
+        ));
+    }
+    report.extend(validate_unique_level_ids(levels_root, &difficulties));
+
+    report
 }
 
-fn validate_all_levels_toml() -> Result<ValidationReport> {
-    let levels_root = find_levels_root()?;
-    Ok(validate_all_levels_toml_with_root(&levels_root))
+/// Flags numeric level ids shared by more than one referenced level JSON, across
+/// all difficulties, since they collide once aggregated into levels.json
+fn validate_unique_level_ids(levels_root: &Path, difficulties: &[String]) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut paths_by_id: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+
+    for difficulty in difficulties {
+        let difficulty_dir = levels_root.join(difficulty);
+        // Missing or unparsable files are already reported per difficulty.
+        let Ok(levels_toml) = parse_levels_toml(&difficulty_dir.join("levels.toml"), difficulty)
+        else {
+            continue;
+        };
+        for file_name in levels_toml
+            .level
+            .iter()
+            .filter_map(|entry| entry.file.as_ref())
+        {
+            let level_json_path = difficulty_dir.join(file_name);
+            if let Some(id) = read_level_id(&level_json_path) {
+                paths_by_id.entry(id).or_default().push(level_json_path);
+            }
+        }
+    }
+
+    for (id, paths) in paths_by_id {
+        if paths.len() > 1 {
+            let listed: Vec<String> = paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            report.push(
+                ValidationIssueKind::Validation,
+                format!(
+                    "Duplicate level id {} used by {} files: {}",
+                    id,
+                    paths.len(),
+                    listed.join(", ")
+                ),
+            );
+        }
+    }
+
+    report
 }
 
-fn validate_all_levels_toml_with_root(levels_root: &Path) -> ValidationReport {
+/// Flags `solved = true` entries whose playback is missing, unreadable or has no moves
+fn validate_solved_playbacks(levels_root: &Path, difficulties: &[String]) -> ValidationReport {
     let mut report = ValidationReport::default();
 
-    for difficulty in DEFAULT_DIFFICULTIES {
+    for difficulty in difficulties {
         let difficulty_dir = levels_root.join(difficulty);
-        report.extend(validate_difficulty_levels_toml(&difficulty_dir, difficulty));
+        // Missing or unparsable files are already reported per difficulty.
+        let Ok(levels_toml) = parse_levels_toml(&difficulty_dir.join("levels.toml"), difficulty)
+        else {
+            continue;
+        };
+        for file_name in levels_toml
+            .level
+            .iter()
+            .filter(|entry| entry.solved == Some(true))
+            .filter_map(|entry| entry.file.as_ref())
+        {
+            let level_json_path = difficulty_dir.join(file_name);
+            let Ok(playback_path) = infer_playback_path(levels_root, &level_json_path) else {
+                continue;
+            };
+            if !playback_path.exists() {
+                report.push(
+                    ValidationIssueKind::Validation,
+                    format!(
+                        "Level marked solved has no playback: {} (expected {})",
+                        level_json_path.display(),
+                        playback_path.display()
+                    ),
+                );
+                continue;
+            }
+            // Loading already rejects a playback without moves.
+            if let Err(error) = load_playback_directions(&playback_path) {
+                report.push(
+                    ValidationIssueKind::Validation,
+                    format!(
+                        "Level marked solved has an unreplayable playback: {} ({error:#})",
+                        playback_path.display()
+                    ),
+                );
+            }
+        }
     }
 
     report
 }
 
-fn validate_difficulty_levels_toml(difficulty_dir: &Path, difficulty: &str) -> ValidationReport {
+/// The numeric `id` of a level JSON file, if it can be read
+fn read_level_id(path: &Path) -> Option<u64> {
+    let content = fs::read_to_string(path).ok()?;
+    let level: serde_json::Value = serde_json::from_str(&content).ok()?;
+    level.get("id")?.as_u64()
+}
+
+fn validate_difficulty_levels_toml(
+    difficulty_dir: &Path,
+    difficulty: &str,
+    fix: bool,
+) -> ValidationReport {
     let mut report = ValidationReport::default();
     let levels_toml_path = difficulty_dir.join("levels.toml");
 
@@ -170,10 +337,62 @@ fn validate_difficulty_levels_toml(difficulty_dir: &Path, difficulty: &str) -> V
             continue;
         }
 
-        // Parse JSON file as LevelDefinition
-        if let Some(issue) = validate_level_json(&level_json_path) {
-            report.issues.push(issue);
-        }
+        // Parse JSON file as LevelDefinition and check its geometry and folder
+        report
+            .issues
+            .extend(validate_level_json(&level_json_path, difficulty, fix));
+    }
+
+    report.extend(validate_no_orphaned_levels(difficulty_dir, &levels_toml));
+
+    report
+}
+
+/// Flags `*.json` files in a difficulty directory that no levels.toml entry references,
+/// since they would never ship
+fn validate_no_orphaned_levels(
+    difficulty_dir: &Path,
+    levels_toml: &LevelsToml,
+) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let Ok(entries) = fs::read_dir(difficulty_dir) else {
+        return report;
+    };
+
+    // Compare case-insensitively: a reference that only differs in case is reported as a
+    // missing file on case-sensitive filesystems and resolves fine elsewhere.
+    let referenced: Vec<String> = levels_toml
+        .level
+        .iter()
+        .filter_map(|entry| entry.file.as_ref())
+        .map(|file| file.to_lowercase())
+        .collect();
+
+    let mut orphaned: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !referenced.contains(&name.to_lowercase()))
+        })
+        .collect();
+    orphaned.sort();
+
+    for path in orphaned {
+        report.push(
+            ValidationIssueKind::Validation,
+            format!(
+                "Orphaned level file not referenced in levels.toml: {}",
+                path.display()
+            ),
+        );
     }
 
     report
@@ -202,39 +421,228 @@ fn parse_levels_toml(
     })
 }
 
-fn validate_level_json(path: &Path) -> Option<ValidationIssue> {
+/// Flags a `snakeDirection` that points from the head straight back into the second segment,
+/// which the engine rejects as a reversal on the first move. Single-segment snakes have no
+/// second segment and can start facing any way.
+fn snake_direction_issue(level: &LevelDefinition) -> Option<String> {
+    let [head, neck, ..] = level.snake.as_slice() else {
+        return None;
+    };
+    let (dx, dy) = direction_delta(level.snake_direction);
+    (head.x + dx == neck.x && head.y + dy == neck.y).then(|| {
+        format!(
+            "snakeDirection {:?} points back into the snake's second segment at ({}, {})",
+            level.snake_direction, neck.x, neck.y
+        )
+    })
+}
+
+fn validate_level_json(path: &Path, difficulty: &str, fix: bool) -> Vec<ValidationIssue> {
     let content = match fs::read_to_string(path) {
         Ok(content) => content,
         Err(error) => {
-            return Some(ValidationIssue {
+            return vec![ValidationIssue {
                 kind: ValidationIssueKind::Io,
                 message: format!(
                     "Failed to read level JSON file: {} ({error})",
                     path.display()
                 ),
-            });
+            }];
         },
     };
 
-    match serde_json::from_str::<LevelDefinition>(&content) {
-        Ok(_) => None,
-        Err(error) => Some(ValidationIssue {
-            kind: ValidationIssueKind::Parse,
-            message: format!(
-                "Failed to parse level JSON as LevelDefinition: {} ({error})",
-                path.display()
-            ),
-        }),
+    let level = match serde_json::from_str::<LevelDefinition>(&content) {
+        Ok(level) => level,
+        Err(error) => {
+            return vec![ValidationIssue {
+                kind: ValidationIssueKind::Parse,
+                message: format!(
+                    "Failed to parse level JSON as LevelDefinition: {} ({error})\n{}",
+                    path.display(),
+                    source_snippet(&content, error.line(), error.column())
+                )
+                .trim_end()
+                .to_string(),
+            }];
+        },
+    };
+
+    let mut issues: Vec<ValidationIssue> = geometry_issues(&level)
+        .into_iter()
+        .map(|issue| ValidationIssue {
+            kind: ValidationIssueKind::Geometry,
+            message: format!("{}: {issue}", path.display()),
+        })
+        .collect();
+    // Flood fill is cheap next to the solver; skip it when positions are off the grid.
+    if issues.is_empty() {
+        issues.extend(
+            unreachable_targets(&level)
+                .into_iter()
+                .map(|target| ValidationIssue {
+                    kind: ValidationIssueKind::Reachability,
+                    message: format!(
+                        "{}: target ({}, {}) is unreachable from the snake",
+                        path.display(),
+                        target.x,
+                        target.y
+                    ),
+                }),
+        );
+    }
+    // The engine wins on `totalFood`, so a hand-edited count that disagrees with the
+    // arrays makes the level unwinnable or winnable too early.
+    if let Some(total_food) = level.total_food {
+        let food_items = derive_total_food(&level);
+        if total_food != food_items {
+            issues.push(ValidationIssue {
+                kind: ValidationIssueKind::Validation,
+                message: format!(
+                    "{}: totalFood {total_food} disagrees with {food_items} food items",
+                    path.display()
+                ),
+            });
+        }
+    }
+    if let Some(issue) = snake_direction_issue(&level) {
+        issues.push(ValidationIssue {
+            kind: ValidationIssueKind::Validation,
+            message: format!("{}: {issue}", path.display()),
+        });
+    }
+    issues.extend(validate_level_difficulty(
+        path, &content, &level, difficulty, fix,
+    ));
+    issues
+}
+
+/// Numbered lines around a 1-based `line`, with a caret under `column`; empty when the
+/// error has no position
+fn source_snippet(content: &str, line: usize, column: usize) -> String {
+    if line == 0 {
+        return String::new();
+    }
+    let first = line.saturating_sub(2).max(1);
+    let last = line + 1;
+    let width = last.to_string().len();
+
+    let mut snippet = String::new();
+    for (number, text) in content
+        .lines()
+        .enumerate()
+        .map(|(index, text)| (index + 1, text))
+        .skip(first - 1)
+        .take(last - first + 1)
+    {
+        snippet.push_str(&format!("{number:>width$} | {text}\n"));
+        if number == line {
+            snippet.push_str(&format!("{:>width$} | {:>column$}\n", "", "^"));
+        }
+    }
+    snippet
+}
+
+/// Checks the level's `difficulty` field against its folder, rewriting it with `fix`
+fn validate_level_difficulty(
+    path: &Path,
+    content: &str,
+    level: &LevelDefinition,
+    difficulty: &str,
+    fix: bool,
+) -> Option<ValidationIssue> {
+    let declared = level.difficulty.as_deref()?;
+    if declared.trim().eq_ignore_ascii_case(difficulty) {
+        return None;
+    }
+
+    if fix {
+        return match fix_level_difficulty(path, content, difficulty) {
+            Ok(()) => {
+                println!(
+                    "Fixed difficulty in {}: '{}' -> '{}'",
+                    path.display(),
+                    declared,
+                    difficulty
+                );
+                None
+            },
+            Err(error) => Some(ValidationIssue {
+                kind: ValidationIssueKind::Io,
+                message: format!(
+                    "Failed to fix difficulty in level JSON file: {} ({error})",
+                    path.display()
+                ),
+            }),
+        };
+    }
+
+    Some(ValidationIssue {
+        kind: ValidationIssueKind::Validation,
+        message: format!(
+            "Level JSON declares difficulty '{}' but lives in the '{}' folder: {} (run with --fix to rewrite it)",
+            declared,
+            difficulty,
+            path.display()
+        ),
+    })
+}
+
+/// Rewrite the `difficulty` field of a level JSON file, keeping every other field as is
+fn fix_level_difficulty(path: &Path, content: &str, difficulty: &str) -> Result<()> {
+    let mut level: serde_json::Value = serde_json::from_str(content)?;
+    if let Some(obj) = level.as_object_mut() {
+        obj.insert(
+            "difficulty".to_string(),
+            serde_json::Value::String(difficulty.to_string()),
+        );
     }
+    fs::write(path, serde_json::to_string_pretty(&level)?)?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::levels::{LevelMeta, LevelsToml};
+    use crate::levels::{LevelMeta, LevelsToml, DEFAULT_DIFFICULTIES};
     use std::fs;
     use tempfile::TempDir;
 
+    /// Writes a small easy level and a levels.toml that references it
+    fn write_geometry_level(
+        difficulty_dir: &Path,
+        exit: serde_json::Value,
+        obstacles: serde_json::Value,
+        food: serde_json::Value,
+    ) {
+        fs::create_dir_all(difficulty_dir).unwrap();
+        let level = serde_json::json!({
+            "id": 1,
+            "name": "Geometry Level",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 3 },
+            "snake": [{ "x": 1, "y": 1 }, { "x": 0, "y": 1 }],
+            "snakeDirection": "East",
+            "obstacles": obstacles,
+            "food": food,
+            "exit": exit,
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 1
+        });
+        fs::write(
+            difficulty_dir.join("level.json"),
+            serde_json::to_string_pretty(&level).unwrap(),
+        )
+        .unwrap();
+        let levels_toml = LevelsToml {
+            level: vec![create_level_meta(Some("level.json"))],
+        };
+        crate::levels::write_levels_toml(&difficulty_dir.join("levels.toml"), &levels_toml)
+            .unwrap();
+    }
+
     fn create_level_meta(file: Option<&str>) -> LevelMeta {
         LevelMeta {
             id: Some("test".to_string()),
@@ -253,7 +661,7 @@ mod tests {
         let difficulty_dir = temp_dir.path().join("easy");
         fs::create_dir(&difficulty_dir).unwrap();
 
-        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy");
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
         assert_eq!(report.issues.len(), 1);
         assert_eq!(report.issues[0].kind, ValidationIssueKind::Io);
         assert!(report.issues[0].message.contains("levels.toml not found"));
@@ -268,7 +676,7 @@ mod tests {
         let levels_toml_path = difficulty_dir.join("levels.toml");
         fs::write(&levels_toml_path, "invalid toml content [[[").unwrap();
 
-        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy");
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
         assert_eq!(report.issues.len(), 1);
         assert_eq!(report.issues[0].kind, ValidationIssueKind::Parse);
         assert!(report.issues[0]
@@ -289,7 +697,7 @@ mod tests {
         let levels_toml_path = difficulty_dir.join("levels.toml");
         crate::levels::write_levels_toml(&levels_toml_path, &levels_toml).unwrap();
 
-        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy");
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
         assert_eq!(report.issues.len(), 1);
         assert_eq!(report.issues[0].kind, ValidationIssueKind::Io);
         assert!(report.issues[0].message.contains("does not exist"));
@@ -311,7 +719,7 @@ mod tests {
         let levels_toml_path = difficulty_dir.join("levels.toml");
         crate::levels::write_levels_toml(&levels_toml_path, &levels_toml).unwrap();
 
-        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy");
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
         assert_eq!(report.issues.len(), 1);
         assert_eq!(report.issues[0].kind, ValidationIssueKind::Parse);
         assert!(report.issues[0]
@@ -339,7 +747,7 @@ mod tests {
         let levels_toml_path = difficulty_dir.join("levels.toml");
         crate::levels::write_levels_toml(&levels_toml_path, &levels_toml).unwrap();
 
-        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy");
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
         assert_eq!(report.issues.len(), 3);
         assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
         assert_eq!(report.issues[1].kind, ValidationIssueKind::Io);
@@ -386,12 +794,301 @@ mod tests {
         };
         crate::levels::write_levels_toml(&hard_dir.join("levels.toml"), &hard_toml).unwrap();
 
-        let report = validate_all_levels_toml_with_root(&levels_root);
+        let report = validate_all_levels_toml_with_root(&levels_root, false);
         assert_eq!(report.issues.len(), 2);
         assert_eq!(report.issues[0].kind, ValidationIssueKind::Io);
         assert_eq!(report.issues[1].kind, ValidationIssueKind::Parse);
     }
 
+    fn copy_difficulty_mismatch_fixture(difficulty_dir: &Path) -> std::path::PathBuf {
+        fs::create_dir_all(difficulty_dir).unwrap();
+        let level_path = difficulty_dir.join("misfiled.json");
+        fs::copy(
+            "tests/fixtures/difficulty_mismatch/easy/misfiled.json",
+            &level_path,
+        )
+        .unwrap();
+        let levels_toml = LevelsToml {
+            level: vec![create_level_meta(Some("misfiled.json"))],
+        };
+        crate::levels::write_levels_toml(&difficulty_dir.join("levels.toml"), &levels_toml)
+            .unwrap();
+        level_path
+    }
+
+    #[test]
+    fn test_validate_flags_difficulty_mismatch_with_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let difficulty_dir = temp_dir.path().join("easy");
+        copy_difficulty_mismatch_fixture(&difficulty_dir);
+
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
+        assert!(report.issues[0]
+            .message
+            .contains("declares difficulty 'hard' but lives in the 'easy' folder"));
+    }
+
+    #[test]
+    fn test_validate_fix_rewrites_difficulty_to_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let difficulty_dir = temp_dir.path().join("easy");
+        let level_path = copy_difficulty_mismatch_fixture(&difficulty_dir);
+
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", true);
+        assert!(report.is_empty());
+
+        let level: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&level_path).unwrap()).unwrap();
+        assert_eq!(level["difficulty"], "easy");
+        assert_eq!(level["name"], "Misfiled Corridor");
+        assert!(validate_difficulty_levels_toml(&difficulty_dir, "easy", false).is_empty());
+    }
+
+    #[test]
+    fn test_validate_flags_out_of_bounds_exit() {
+        let temp_dir = TempDir::new().unwrap();
+        let difficulty_dir = temp_dir.path().join("easy");
+        write_geometry_level(
+            &difficulty_dir,
+            serde_json::json!({ "x": 5, "y": 1 }),
+            serde_json::json!([]),
+            serde_json::json!([{ "x": 3, "y": 1 }]),
+        );
+
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Geometry);
+        assert!(report.issues[0]
+            .message
+            .ends_with("exit position (5, 1) is outside the 5x3 grid"));
+        assert_eq!(report.exit_code(), EXIT_CODE_VALIDATION_ERROR);
+    }
+
+    #[test]
+    fn test_validate_flags_obstacle_overlapping_food() {
+        let temp_dir = TempDir::new().unwrap();
+        let difficulty_dir = temp_dir.path().join("easy");
+        write_geometry_level(
+            &difficulty_dir,
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([{ "x": 3, "y": 1 }]),
+            serde_json::json!([{ "x": 3, "y": 1 }]),
+        );
+
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Geometry);
+        assert!(report.issues[0]
+            .message
+            .contains("level.json: obstacle at (3, 1) overlaps food"));
+    }
+
+    #[test]
+    fn test_validate_all_includes_custom_difficulty_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        for difficulty in DEFAULT_DIFFICULTIES {
+            fs::create_dir_all(levels_root.join(difficulty)).unwrap();
+            crate::levels::write_levels_toml(
+                &levels_root.join(difficulty).join("levels.toml"),
+                &LevelsToml { level: vec![] },
+            )
+            .unwrap();
+        }
+        // The fixture declares "easy", which disagrees with its "expert" folder
+        write_geometry_level(
+            &levels_root.join("expert"),
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([]),
+            serde_json::json!([{ "x": 3, "y": 1 }]),
+        );
+
+        let report = validate_all_levels_toml_with_root(&levels_root, false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert!(report.issues[0].message.contains("expert"));
+    }
+
+    #[test]
+    fn test_validate_all_flags_duplicate_ids_across_difficulties() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        for difficulty in DEFAULT_DIFFICULTIES {
+            write_geometry_level(
+                &levels_root.join(difficulty),
+                serde_json::json!({ "x": 4, "y": 1 }),
+                serde_json::json!([]),
+                serde_json::json!([{ "x": 3, "y": 1 }]),
+            );
+        }
+        // Only easy and medium share id 1; hard gets its own
+        for (difficulty, id) in [("medium", 1), ("hard", 2)] {
+            let path = levels_root.join(difficulty).join("level.json");
+            let mut level: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            level["id"] = serde_json::json!(id);
+            level["difficulty"] = serde_json::json!(difficulty);
+            fs::write(&path, level.to_string()).unwrap();
+        }
+
+        let report = validate_all_levels_toml_with_root(&levels_root, false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
+        let message = &report.issues[0].message;
+        assert!(message.starts_with("Duplicate level id 1 used by 2 files: "));
+        assert!(message.contains(&levels_root.join("easy/level.json").display().to_string()));
+        assert!(message.contains(&levels_root.join("medium/level.json").display().to_string()));
+    }
+
+    #[test]
+    fn test_validate_solved_playbacks_flags_missing_and_empty_playbacks() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        write_geometry_level(
+            &levels_root.join("easy"),
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([]),
+            serde_json::json!([{ "x": 3, "y": 1 }]),
+        );
+        let difficulties = vec!["easy".to_string()];
+        let playback_path = temp_dir.path().join("playbacks/easy/level.json");
+
+        let report = validate_solved_playbacks(&levels_root, &difficulties);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
+        assert!(report.issues[0]
+            .message
+            .starts_with("Level marked solved has no playback"));
+
+        fs::create_dir_all(playback_path.parent().unwrap()).unwrap();
+        fs::write(&playback_path, "[]").unwrap();
+        let report = validate_solved_playbacks(&levels_root, &difficulties);
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].message.contains("unreplayable playback"));
+        assert!(report.issues[0]
+            .message
+            .ends_with("(Playback input file is empty)"));
+
+        fs::write(&playback_path, r#""""#).unwrap();
+        let report = validate_solved_playbacks(&levels_root, &difficulties);
+        assert!(report.issues[0]
+            .message
+            .ends_with("(Playback input file is empty)"));
+
+        fs::write(&playback_path, r#"[{"key":"Right","delay_ms":1}]"#).unwrap();
+        assert!(validate_solved_playbacks(&levels_root, &difficulties).is_empty());
+    }
+
+    #[test]
+    fn test_validate_flags_total_food_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let difficulty_dir = temp_dir.path().join("easy");
+        write_geometry_level(
+            &difficulty_dir,
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([]),
+            serde_json::json!([{ "x": 3, "y": 1 }, { "x": 2, "y": 1 }]),
+        );
+        let path = difficulty_dir.join("level.json");
+        let mut level: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        level["totalFood"] = serde_json::json!(5);
+        fs::write(&path, level.to_string()).unwrap();
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
+        assert!(report.issues[0]
+            .message
+            .ends_with("totalFood 5 disagrees with 2 food items"));
+
+        level["totalFood"] = serde_json::json!(2);
+        fs::write(&path, level.to_string()).unwrap();
+        assert!(validate_difficulty_levels_toml(&difficulty_dir, "easy", false).is_empty());
+    }
+
+    #[test]
+    fn test_validate_flags_snake_direction_into_neck() {
+        let temp_dir = TempDir::new().unwrap();
+        let difficulty_dir = temp_dir.path().join("easy");
+        write_geometry_level(
+            &difficulty_dir,
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([]),
+            serde_json::json!([{ "x": 0, "y": 0 }]),
+        );
+        // The fixture's head sits east of its neck, matching "East".
+        assert!(validate_difficulty_levels_toml(&difficulty_dir, "easy", false).is_empty());
+
+        let path = difficulty_dir.join("level.json");
+        let mut level: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        level["snake"] =
+            serde_json::json!([{ "x": 2, "y": 1 }, { "x": 3, "y": 1 }, { "x": 3, "y": 2 }]);
+        fs::write(&path, level.to_string()).unwrap();
+
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
+        assert!(report.issues[0].message.ends_with(
+            "snakeDirection East points back into the snake's second segment at (3, 1)"
+        ));
+
+        // A two-segment body lying westward is rejected the same way.
+        level["snake"] = serde_json::json!([{ "x": 2, "y": 1 }, { "x": 3, "y": 1 }]);
+        fs::write(&path, level.to_string()).unwrap();
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert!(report.issues[0].message.ends_with(
+            "snakeDirection East points back into the snake's second segment at (3, 1)"
+        ));
+    }
+
+    #[test]
+    fn test_validate_difficulty_reports_exit_sealed_behind_wall() {
+        let temp_dir = TempDir::new().unwrap();
+        let easy_dir = temp_dir.path().join("easy");
+        write_geometry_level(
+            &easy_dir,
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([{ "x": 3, "y": 0 }, { "x": 3, "y": 1 }, { "x": 3, "y": 2 }]),
+            serde_json::json!([{ "x": 2, "y": 1 }]),
+        );
+
+        let report = validate_difficulty_levels_toml(&easy_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Reachability);
+        assert!(report.issues[0]
+            .message
+            .ends_with("target (4, 1) is unreachable from the snake"));
+        assert!(report.without_level_analysis().is_empty());
+    }
+
+    #[test]
+    fn test_validate_difficulty_flags_orphaned_level_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let easy_dir = temp_dir.path().join("easy");
+        write_geometry_level(
+            &easy_dir,
+            serde_json::json!({ "x": 4, "y": 1 }),
+            serde_json::json!([]),
+            serde_json::json!([{ "x": 3, "y": 1 }]),
+        );
+        fs::copy(easy_dir.join("level.json"), easy_dir.join("forgotten.json")).unwrap();
+
+        let report = validate_difficulty_levels_toml(&easy_dir, "easy", false);
+        assert_eq!(report.issues.len(), 1, "{}", report.format_for_stderr());
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::Validation);
+        assert_eq!(
+            report.issues[0].message,
+            format!(
+                "Orphaned level file not referenced in levels.toml: {}",
+                easy_dir.join("forgotten.json").display()
+            )
+        );
+    }
+
     #[test]
     fn test_validation_report_format_is_stable() {
         let mut report = ValidationReport::default();
@@ -411,6 +1108,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_parse_error_shows_source_snippet() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.json");
+        fs::write(
+            &path,
+            "{\n  \"id\": 1,\n  \"name\": oops,\n  \"difficulty\": \"easy\"\n}\n",
+        )
+        .unwrap();
+
+        let issues = validate_level_json(&path, "easy", false);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ValidationIssueKind::Parse);
+        let mut lines = issues[0].message.lines();
+        assert!(lines.next().unwrap().contains("line 3 column 11"));
+        assert_eq!(
+            lines.collect::<Vec<_>>(),
+            vec![
+                "1 | {",
+                "2 |   \"id\": 1,",
+                "3 |   \"name\": oops,",
+                "  |           ^",
+                "4 |   \"difficulty\": \"easy\"",
+            ]
+        );
+
+        let mut report = ValidationReport::default();
+        report.issues.extend(issues);
+        assert_eq!(report.summary_lines().len(), 1);
+        assert!(report
+            .format_for_stderr()
+            .contains("\n     3 |   \"name\": oops,\n"));
+    }
+
+    #[test]
+    fn test_run_validate_levels_toml_maps_issues_to_exit_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        for (id, difficulty) in DEFAULT_DIFFICULTIES.into_iter().enumerate() {
+            write_geometry_level(
+                &levels_root.join(difficulty),
+                serde_json::json!({ "x": 4, "y": 1 }),
+                serde_json::json!([]),
+                serde_json::json!([{ "x": 3, "y": 1 }]),
+            );
+            let path = levels_root.join(difficulty).join("level.json");
+            let mut level: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            level["id"] = serde_json::json!(id);
+            level["difficulty"] = serde_json::json!(difficulty);
+            fs::write(&path, level.to_string()).unwrap();
+        }
+        assert!(run_validate_levels_toml(&levels_root, false, false, true).is_empty());
+
+        fs::remove_file(levels_root.join("hard/level.json")).unwrap();
+        let report = run_validate_levels_toml(&levels_root, false, false, true);
+        assert_eq!(report.exit_code(), EXIT_CODE_IO_ERROR);
+
+        fs::write(levels_root.join("medium/level.json"), "{not-json}").unwrap();
+        let report = run_validate_levels_toml(&levels_root, false, false, true);
+        assert_eq!(report.exit_code(), EXIT_CODE_PARSE_ERROR);
+
+        // Every level is marked solved and none has a playback.
+        let with_playbacks = run_validate_levels_toml(&levels_root, false, true, true);
+        assert_eq!(
+            with_playbacks.summary_lines().len(),
+            report.summary_lines().len() + DEFAULT_DIFFICULTIES.len()
+        );
+        assert_eq!(with_playbacks.exit_code(), EXIT_CODE_PARSE_ERROR);
+    }
+
     #[test]
     fn test_validate_valid_levels() {
         let temp_dir = TempDir::new().unwrap();
@@ -445,7 +1213,7 @@ mod tests {
         let levels_toml_path = difficulty_dir.join("levels.toml");
         crate::levels::write_levels_toml(&levels_toml_path, &levels_toml).unwrap();
 
-        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy");
+        let report = validate_difficulty_levels_toml(&difficulty_dir, "easy", false);
         assert!(report.issues.is_empty());
     }
 }
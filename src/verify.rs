@@ -1,29 +1,49 @@
-use crate::playback::load_playback_directions;
+use crate::{
+    levels::catalog_difficulties,
+    playback::load_playback_directions,
+    solver::{is_level_complete, navigation_exit},
+};
 use anyhow::{bail, Context, Result};
-use gsnake_core::{engine::GameEngine, GameStatus, LevelDefinition};
+use gsnake_core::{engine::GameEngine, Direction, GameStatus, LevelDefinition, Position};
 use std::{
     fs,
     path::{Component, Path, PathBuf},
 };
 
+/// Infers the playback for `levels/<difficulty>/...` by swapping that `levels` component for
+/// `playbacks`. Only a `levels` directly followed by one of that catalog's difficulties
+/// counts, and the last such one wins, so parents that happen to be named `levels` are left
+/// alone.
 pub fn resolve_playback_path(level_path: &Path, override_path: Option<PathBuf>) -> Result<PathBuf> {
     if let Some(path) = override_path {
         return Ok(path);
     }
 
-    let mut replaced = PathBuf::new();
-    let mut replaced_any = false;
-    for component in level_path.components() {
-        match component {
-            Component::Normal(name) if name == "levels" && !replaced_any => {
+    let components: Vec<Component> = level_path.components().collect();
+    let anchor = (0..components.len().saturating_sub(1))
+        .rev()
+        .find(|&index| {
+            let [Component::Normal(name), Component::Normal(difficulty)] =
+                components[index..index + 2]
+            else {
+                return false;
+            };
+            let levels_root: PathBuf = components[..=index].iter().collect();
+            name == "levels"
+                && catalog_difficulties(&levels_root)
+                    .iter()
+                    .any(|known| difficulty == known.as_str())
+        });
+
+    if let Some(anchor) = anchor {
+        let mut replaced = PathBuf::new();
+        for (index, component) in components.iter().enumerate() {
+            if index == anchor {
                 replaced.push("playbacks");
-                replaced_any = true;
-            },
-            _ => replaced.push(component.as_os_str()),
+            } else {
+                replaced.push(component.as_os_str());
+            }
         }
-    }
-
-    if replaced_any {
         return Ok(replaced);
     }
 
@@ -33,34 +53,139 @@ pub fn resolve_playback_path(level_path: &Path, override_path: Option<PathBuf>)
     )
 }
 
+/// Extra per-move checks for high-assurance verification
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Fail as soon as any snake segment leaves the grid, even if the playback completes
+    pub strict_bounds: bool,
+}
+
+/// How a verified playback played out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyOutcome {
+    /// Moves applied before the level finished; trailing moves after completion are ignored
+    pub moves_consumed: usize,
+    pub food_collected: u32,
+    pub final_status: GameStatus,
+    /// The level was already complete before the first move, so no move was replayed
+    pub already_complete: bool,
+}
+
 pub fn verify_level(level_path: &Path, playback_path: &Path) -> Result<()> {
+    verify_level_with_options(level_path, playback_path, VerifyOptions::default())
+}
+
+pub fn verify_level_with_options(
+    level_path: &Path,
+    playback_path: &Path,
+    options: VerifyOptions,
+) -> Result<()> {
+    verify_level_detailed_with_options(level_path, playback_path, options).map(|_| ())
+}
+
+/// Like `verify_level`, but reports how many moves the playback needed and what it collected
+#[allow(dead_code)]
+pub fn verify_level_detailed(level_path: &Path, playback_path: &Path) -> Result<VerifyOutcome> {
+    verify_level_detailed_with_options(level_path, playback_path, VerifyOptions::default())
+}
+
+pub fn verify_level_detailed_with_options(
+    level_path: &Path,
+    playback_path: &Path,
+    options: VerifyOptions,
+) -> Result<VerifyOutcome> {
     let level = load_level(level_path)
         .with_context(|| format!("Failed to load level: {}", level_path.display()))?;
     let directions = load_playback_directions(playback_path)
         .with_context(|| format!("Failed to load playback: {}", playback_path.display()))?;
+    verify_run_with_options(level, &directions, options)
+}
+
+/// Replays `directions` against an in-memory level, without touching the filesystem
+pub fn verify_run(level: LevelDefinition, directions: &[Direction]) -> Result<VerifyOutcome> {
+    verify_run_with_options(level, directions, VerifyOptions::default())
+}
 
-    let mut engine = GameEngine::new(level)
-        .with_context(|| format!("Invalid grid size in level file: {}", level_path.display()))?;
+pub fn verify_run_with_options(
+    level: LevelDefinition,
+    directions: &[Direction],
+    options: VerifyOptions,
+) -> Result<VerifyOutcome> {
+    let (width, height) = (level.grid_size.width, level.grid_size.height);
+    let navigation_exit = navigation_exit(&level);
+    let mut engine = GameEngine::new(level).context("Invalid grid size in level definition")?;
     let mut frame = engine.generate_frame();
 
-    for direction in directions {
-        if frame.state.status != GameStatus::Playing {
+    // Degenerate levels can already be terminal before the first move.
+    if is_level_complete(&engine, navigation_exit) {
+        return Ok(VerifyOutcome {
+            already_complete: true,
+            ..outcome(&engine, 0)
+        });
+    }
+    if frame.state.status == GameStatus::GameOver {
+        bail!("Level starts in Game Over");
+    }
+
+    // The loop stops at the first move that leaves `Playing`, so trailing moves after a
+    // completion or a Game Over are never replayed and `moves_consumed` names that move.
+    let mut moves_consumed = 0;
+    for (index, &direction) in directions.iter().enumerate() {
+        if frame.state.status != GameStatus::Playing || is_level_complete(&engine, navigation_exit)
+        {
             break;
         }
+        moves_consumed += 1;
 
         engine
             .process_move(direction)
             .with_context(|| format!("Engine move failed for direction {direction:?}"))?;
         frame = engine.generate_frame();
+
+        if options.strict_bounds {
+            let segments = &engine.level_state().snake.segments;
+            if let Some((segment, position)) = out_of_bounds_segment(segments, width, height) {
+                bail!(
+                    "Move {} ({direction:?}) left snake segment {segment} at ({}, {}) outside the {width}x{height} grid",
+                    index + 1,
+                    position.x,
+                    position.y
+                );
+            }
+        }
     }
 
+    if is_level_complete(&engine, navigation_exit) {
+        return Ok(outcome(&engine, moves_consumed));
+    }
     match frame.state.status {
-        GameStatus::LevelComplete | GameStatus::AllComplete => Ok(()),
-        GameStatus::GameOver => bail!("Playback resulted in Game Over"),
-        GameStatus::Playing => bail!("Playback did not complete the level"),
+        GameStatus::GameOver => {
+            bail!("Playback resulted in Game Over at move {moves_consumed}")
+        },
+        _ => bail!("Playback did not complete the level"),
+    }
+}
+
+fn outcome(engine: &GameEngine, moves_consumed: usize) -> VerifyOutcome {
+    let game_state = engine.game_state();
+    VerifyOutcome {
+        moves_consumed,
+        food_collected: game_state.food_collected,
+        final_status: game_state.status,
+        already_complete: false,
     }
 }
 
+fn out_of_bounds_segment(
+    segments: &[Position],
+    width: i32,
+    height: i32,
+) -> Option<(usize, &Position)> {
+    segments.iter().enumerate().find(|(_, position)| {
+        position.x < 0 || position.y < 0 || position.x >= width || position.y >= height
+    })
+}
+
 fn load_level(level_path: &Path) -> Result<LevelDefinition> {
     let contents = fs::read_to_string(level_path)
         .with_context(|| format!("Failed to read level file: {}", level_path.display()))?;
@@ -204,6 +329,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_playback_path_doubled_levels() {
+        let level_path = Path::new("levels/levels/easy/level_001.json");
+        let playback_path = resolve_playback_path(level_path, None).unwrap();
+        assert_eq!(
+            playback_path,
+            PathBuf::from("levels/playbacks/easy/level_001.json")
+        );
+    }
+
+    #[test]
+    fn test_resolve_playback_path_ignores_non_difficulty_levels_parent() {
+        let level_path = Path::new("levels/archive/level_001.json");
+        let error = resolve_playback_path(level_path, None).unwrap_err();
+        assert!(error.to_string().contains("Unable to infer playback path"));
+
+        let level_path = Path::new("/srv/levels/repo/levels/hard/level_001.json");
+        assert_eq!(
+            resolve_playback_path(level_path, None).unwrap(),
+            PathBuf::from("/srv/levels/repo/playbacks/hard/level_001.json")
+        );
+    }
+
+    #[test]
+    fn test_resolve_playback_path_accepts_custom_difficulty_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let expert_dir = temp_dir.path().join("levels/expert");
+        let level_path = expert_dir.join("level_001.json");
+        assert!(resolve_playback_path(&level_path, None).is_err());
+
+        fs::create_dir_all(&expert_dir).unwrap();
+        fs::write(expert_dir.join("levels.toml"), "").unwrap();
+        assert_eq!(
+            resolve_playback_path(&level_path, None).unwrap(),
+            temp_dir.path().join("playbacks/expert/level_001.json")
+        );
+    }
+
     #[test]
     fn test_verify_level_missing_level_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -261,6 +424,67 @@ mod tests {
             .contains("Playback did not complete the level"));
     }
 
+    fn write_degenerate_level(path: &Path, snake: (i32, i32), spikes: &[(i32, i32)]) {
+        let spikes_json: Vec<_> = spikes
+            .iter()
+            .map(|(x, y)| json!({ "x": x, "y": y }))
+            .collect();
+        let level = json!({
+            "id": 1,
+            "name": "Degenerate Level",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 2 },
+            "snake": [{ "x": snake.0, "y": snake.1 }],
+            "snakeDirection": "East",
+            "obstacles": [
+                { "x": 0, "y": 1 }, { "x": 1, "y": 1 }, { "x": 2, "y": 1 },
+                { "x": 3, "y": 1 }, { "x": 4, "y": 1 }
+            ],
+            "food": [],
+            "exit": { "x": 2, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": spikes_json,
+            "totalFood": 0
+        });
+        fs::write(path, serde_json::to_string_pretty(&level).unwrap()).unwrap();
+    }
+
+    fn initial_status(level_path: &Path) -> GameStatus {
+        let engine = GameEngine::new(load_level(level_path).unwrap()).unwrap();
+        engine.generate_frame().state.status
+    }
+
+    #[test]
+    fn test_verify_level_accepts_level_complete_at_start() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("level.json");
+        let playback_path = temp_dir.path().join("playback.json");
+        // The head starts on the exit of a level without food.
+        write_degenerate_level(&level_path, (2, 0), &[]);
+        write_playback(&playback_path, &["Right", "Right"]);
+
+        let outcome = verify_level_detailed(&level_path, &playback_path).unwrap();
+        assert_eq!(outcome.moves_consumed, 0);
+        assert_eq!(outcome.food_collected, 0);
+        assert!(outcome.already_complete);
+    }
+
+    #[test]
+    fn test_verify_level_rejects_game_over_at_start() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("level.json");
+        let playback_path = temp_dir.path().join("playback.json");
+        // The head starts on a spike.
+        write_degenerate_level(&level_path, (0, 0), &[(0, 0)]);
+        write_playback(&playback_path, &["Right", "Right"]);
+        assert_eq!(initial_status(&level_path), GameStatus::GameOver);
+
+        let error = verify_level(&level_path, &playback_path).unwrap_err();
+        assert_eq!(error.to_string(), "Level starts in Game Over");
+    }
+
     #[test]
     fn test_verify_level_returns_game_over_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -272,4 +496,145 @@ mod tests {
         let error = verify_level(&level_path, &playback_path).unwrap_err();
         assert!(error.to_string().contains("Playback resulted in Game Over"));
     }
+
+    #[test]
+    fn test_verify_level_game_over_error_names_failing_move() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("level.json");
+        let playback_path = temp_dir.path().join("playback.json");
+        write_test_level(&level_path, 4, &[(2, 0)]);
+        write_playback(&playback_path, &["Right", "Right", "Right"]);
+
+        let error = verify_level(&level_path, &playback_path).unwrap_err();
+        assert!(error.to_string().contains("Game Over at move 2"), "{error}");
+    }
+
+    #[test]
+    fn test_verify_level_ignores_garbage_after_completion() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("level.json");
+        let playback_path = temp_dir.path().join("playback.json");
+        write_test_level(&level_path, 3, &[]);
+        // The fourth move would leave the grid if it were replayed.
+        write_playback(&playback_path, &["Right", "Right", "Right", "Up"]);
+
+        let outcome = verify_level_detailed(&level_path, &playback_path).unwrap();
+        assert_eq!(outcome.moves_consumed, 3);
+    }
+
+    #[test]
+    fn test_out_of_bounds_segment_reports_first_offender() {
+        let segments = vec![
+            Position::new(4, 0),
+            Position::new(5, 0),
+            Position::new(-1, 0),
+        ];
+        let (index, position) = out_of_bounds_segment(&segments, 5, 5).unwrap();
+        assert_eq!(index, 1);
+        assert_eq!((position.x, position.y), (5, 0));
+        assert!(out_of_bounds_segment(&segments[..1], 5, 5).is_none());
+    }
+
+    #[test]
+    fn test_verify_level_with_strict_bounds_accepts_in_bounds_playback() {
+        let temp_dir = TempDir::new().unwrap();
+        let level_path = temp_dir.path().join("level.json");
+        let playback_path = temp_dir.path().join("playback.json");
+        write_test_level(&level_path, 2, &[]);
+        write_playback(&playback_path, &["Right", "Right"]);
+
+        let options = VerifyOptions {
+            strict_bounds: true,
+        };
+        verify_level_with_options(&level_path, &playback_path, options).unwrap();
+    }
+
+    #[test]
+    fn test_verify_run_with_strict_bounds_names_offending_move() {
+        // The tail starts off the grid and is still outside after the first move.
+        let level: LevelDefinition = serde_json::from_value(json!({
+            "id": 1,
+            "name": "Off Grid Tail",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 1 },
+            "snake": [{ "x": 0, "y": 0 }, { "x": -1, "y": 0 }, { "x": -2, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": [],
+            "food": [],
+            "exit": { "x": 4, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        }))
+        .unwrap();
+        let directions = [Direction::East; 4];
+
+        let outcome = verify_run(level.clone(), &directions).unwrap();
+        assert_eq!(outcome.moves_consumed, 4);
+
+        let options = VerifyOptions {
+            strict_bounds: true,
+        };
+        let error = verify_run_with_options(level, &directions, options).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Move 1 (East) left snake segment 2 at (-1, 0) outside the 5x1 grid"
+        );
+    }
+
+    /// First easy fixture plus a fresh solver playback for it, which finishes exactly
+    fn solved_easy_fixture(temp_dir: &TempDir) -> (PathBuf, PathBuf) {
+        let level_path = crate::test_fixtures::first_easy_level_fixture();
+        let playback_path = temp_dir.path().join("playback.json");
+        crate::solver::solve_level_to_playback(&level_path, &playback_path, 500).unwrap();
+        (level_path, playback_path)
+    }
+
+    #[test]
+    fn test_verify_level_detailed_counts_every_move_of_exact_playback() {
+        let temp_dir = TempDir::new().unwrap();
+        let (level_path, playback_path) = solved_easy_fixture(&temp_dir);
+        let directions = load_playback_directions(&playback_path).unwrap();
+        let level = load_level(&level_path).unwrap();
+
+        let outcome = verify_level_detailed(&level_path, &playback_path).unwrap();
+        assert_eq!(outcome.moves_consumed, directions.len());
+        assert!(!outcome.already_complete);
+        assert_eq!(
+            outcome.food_collected as usize,
+            crate::solver::total_food(&level)
+        );
+    }
+
+    #[test]
+    fn test_verify_run_replays_solver_solution_in_memory() {
+        let temp_dir = TempDir::new().unwrap();
+        let (level_path, _) = solved_easy_fixture(&temp_dir);
+        let level = load_level(&level_path).unwrap();
+        let solution = crate::solver::solve_level(level.clone(), 500).unwrap();
+
+        let outcome = verify_run(level, &solution).unwrap();
+        assert_eq!(outcome.final_status, GameStatus::LevelComplete);
+        assert_eq!(outcome.moves_consumed, solution.len());
+    }
+
+    #[test]
+    fn test_verify_level_detailed_ignores_trailing_moves_after_completion() {
+        let temp_dir = TempDir::new().unwrap();
+        let (level_path, playback_path) = solved_easy_fixture(&temp_dir);
+        let exact_moves = load_playback_directions(&playback_path).unwrap().len();
+
+        let mut steps: Vec<serde_json::Value> =
+            serde_json::from_str(&fs::read_to_string(&playback_path).unwrap()).unwrap();
+        let padding = steps.last().unwrap().clone();
+        steps.extend(std::iter::repeat_n(padding, 3));
+        let padded_path = temp_dir.path().join("padded.json");
+        fs::write(&padded_path, serde_json::to_string(&steps).unwrap()).unwrap();
+
+        let outcome = verify_level_detailed(&level_path, &padded_path).unwrap();
+        assert_eq!(outcome.moves_consumed, exact_moves);
+        assert_ne!(outcome.final_status, GameStatus::GameOver);
+    }
 }
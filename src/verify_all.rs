@@ -1,77 +1,256 @@
-use crate::{levels, verify};
+use crate::{levels, playback, solver, verify};
 use anyhow::{bail, Context, Result};
-use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
 
-pub fn run_verify_all() -> Result<()> {
-    let levels_root = levels::find_levels_root()?;
-    let mut any_failed = false;
+/// Solver depth used by `--check-optimal` (default from US-006)
+const OPTIMAL_CHECK_MAX_DEPTH: usize = 500;
 
-    for difficulty in levels::DEFAULT_DIFFICULTIES {
-        let levels_toml_path = levels_root.join(difficulty).join("levels.toml");
+/// A level to verify, located by its difficulty and index in that levels.toml
+struct VerifyJob {
+    difficulty_index: usize,
+    entry_index: usize,
+    report_index: usize,
+    level_path: PathBuf,
+    playback_path: PathBuf,
+}
+
+/// What happened to one level in a verify-all run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyStatus {
+    Verified,
+    Failed,
+    MissingPlayback,
+}
+
+/// One entry of the `--report` JSON, in levels.toml order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyReportEntry {
+    pub difficulty: String,
+    pub file: String,
+    pub status: VerifyStatus,
+    pub solved: bool,
+    pub error: Option<String>,
+}
+
+/// Per-run counters from `run_verify_all`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyAllSummary {
+    pub verified: usize,
+    pub failed: usize,
+    pub skipped_missing_playback: usize,
+}
+
+impl std::fmt::Display for VerifyAllSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} verified, {} failed, {} skipped (missing playback)",
+            self.verified, self.failed, self.skipped_missing_playback
+        )
+    }
+}
+
+/// Error returned when at least one level fails verification.
+///
+/// Downcast an `anyhow::Error` to this type to read the run's counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyAllFailed {
+    pub summary: VerifyAllSummary,
+}
+
+impl std::fmt::Display for VerifyAllFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "One or more levels failed verification ({})",
+            self.summary
+        )
+    }
+}
+
+impl std::error::Error for VerifyAllFailed {}
+
+/// Verify every level with a playback and record the result as `solved` in levels.toml.
+///
+/// With `check_optimal`, verified playbacks longer than the solver's solution get a
+/// warning; they still count as solved. With `report`, an entry per level is written there
+/// as JSON before any failure is reported. With `difficulty`, only that difficulty is verified.
+pub fn run_verify_all(
+    levels_root: &Path,
+    difficulty: Option<&str>,
+    check_optimal: bool,
+    report: Option<&Path>,
+) -> Result<VerifyAllSummary> {
+    let difficulties = match difficulty {
+        Some(raw) => vec![levels::resolve_difficulty_filter(levels_root, raw)?],
+        None => levels::discover_difficulties(levels_root),
+    };
+
+    // Collect every level with a playback first, so the verification itself can
+    // run in parallel and each levels.toml is still written once, in order.
+    let mut catalogs = Vec::new();
+    let mut jobs = Vec::new();
+    let mut entries = Vec::new();
+    for difficulty in difficulties {
+        let levels_toml_path = levels_root.join(&difficulty).join("levels.toml");
         if !levels_toml_path.exists() {
             continue;
         }
 
-        let mut levels_toml = levels::read_levels_toml(&levels_toml_path)?;
-        let mut updated = false;
-
-        for entry in &mut levels_toml.level {
+        let levels_toml = levels::read_levels_toml(&levels_toml_path)?;
+        for (entry_index, entry) in levels_toml.level.iter().enumerate() {
             let file = match entry.file.as_deref() {
                 Some(file) => file,
                 None => continue,
             };
-            let level_path = levels_root.join(difficulty).join(file);
+            let level_path = levels_root.join(&difficulty).join(file);
             if !level_path.exists() {
                 bail!("Level file not found: {}", level_path.display());
             }
 
-            let playback_path = infer_playback_path(&levels_root, &level_path)?;
-            if !playback_path.exists() {
+            let playback_path = levels::infer_playback_path(levels_root, &level_path)?;
+            let has_playback = playback_path.exists();
+            entries.push(VerifyReportEntry {
+                difficulty: difficulty.clone(),
+                file: file.to_string(),
+                status: VerifyStatus::MissingPlayback,
+                solved: false,
+                error: None,
+            });
+            if !has_playback {
                 continue;
             }
 
-            match verify::verify_level(&level_path, &playback_path) {
-                Ok(()) => {
-                    entry.solved = Some(true);
-                },
-                Err(error) => {
-                    entry.solved = Some(false);
-                    any_failed = true;
-                    eprintln!("Verification failed for {}: {error}", level_path.display());
-                },
-            }
-            updated = true;
+            jobs.push(VerifyJob {
+                difficulty_index: catalogs.len(),
+                entry_index,
+                report_index: entries.len() - 1,
+                level_path,
+                playback_path,
+            });
+        }
+        catalogs.push((levels_toml_path, levels_toml, false));
+    }
+
+    let results = verify_in_parallel(&jobs, check_optimal);
+
+    let mut summary = VerifyAllSummary {
+        skipped_missing_playback: entries.len() - jobs.len(),
+        ..VerifyAllSummary::default()
+    };
+    for (job, result) in jobs.iter().zip(results) {
+        let (_, levels_toml, updated) = &mut catalogs[job.difficulty_index];
+        levels_toml.level[job.entry_index].solved = Some(result.is_ok());
+        if result.is_ok() {
+            summary.verified += 1;
+        } else {
+            summary.failed += 1;
         }
+        *updated = true;
 
-        if updated {
-            levels::write_levels_toml(&levels_toml_path, &levels_toml)
+        let entry = &mut entries[job.report_index];
+        entry.solved = result.is_ok();
+        entry.status = if result.is_ok() {
+            VerifyStatus::Verified
+        } else {
+            VerifyStatus::Failed
+        };
+        entry.error = result.err().map(|error| format!("{error:#}"));
+    }
+
+    for (levels_toml_path, levels_toml, updated) in &catalogs {
+        if *updated {
+            levels::write_levels_toml(levels_toml_path, levels_toml)
                 .with_context(|| format!("Failed to write {}", levels_toml_path.display()))?;
         }
     }
 
-    if any_failed {
-        bail!("One or more levels failed verification")
-    } else {
-        Ok(())
+    if let Some(report_path) = report {
+        levels::write_json_pretty(report_path, &entries)?;
+    }
+
+    if summary.failed > 0 {
+        return Err(VerifyAllFailed { summary }.into());
     }
+    Ok(summary)
 }
 
-fn infer_playback_path(levels_root: &PathBuf, level_path: &Path) -> Result<PathBuf> {
-    let relative = level_path.strip_prefix(levels_root).with_context(|| {
-        format!(
-            "Level path {} is not under levels root {}",
-            level_path.display(),
-            levels_root.display()
-        )
-    })?;
-    let mut playback = levels_root
-        .parent()
-        .unwrap_or(levels_root)
-        .join("playbacks");
-    for component in relative.components() {
-        playback.push(component);
-    }
-    Ok(playback)
+/// Verifies every job on a pool of scoped threads; results are in job order.
+///
+/// Failures are reported on stderr as they happen, so their order may vary.
+fn verify_in_parallel(jobs: &[VerifyJob], check_optimal: bool) -> Vec<Result<()>> {
+    let workers = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(jobs.len())
+        .max(1);
+    let next_job = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, Result<()>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next_job.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(index) else {
+                            break;
+                        };
+                        let result = verify::verify_level(&job.level_path, &job.playback_path);
+                        match &result {
+                            Ok(()) if check_optimal => {
+                                if let Some(warning) = optimality_warning(job) {
+                                    eprintln!("{warning}");
+                                }
+                            },
+                            Ok(()) => {},
+                            Err(error) => eprintln!(
+                                "Verification failed for {}: {error}",
+                                job.level_path.display()
+                            ),
+                        }
+                        done.push((index, result));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("verify worker panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Warning when the playback is longer than the solver's solution, or a note when
+/// the solver could not produce one to compare against
+fn optimality_warning(job: &VerifyJob) -> Option<String> {
+    let level = job.level_path.display();
+    let playback_moves = match playback::load_playback_directions(&job.playback_path) {
+        Ok(directions) => directions.len(),
+        Err(error) => return Some(format!("level {level}: skipped optimality check ({error})")),
+    };
+    let optimal = solver::load_level(&job.level_path)
+        .and_then(|definition| solver::solve_level(definition, OPTIMAL_CHECK_MAX_DEPTH));
+    match optimal {
+        Ok(solution) if playback_moves > solution.len() => Some(format!(
+            "level {level}: playback has {playback_moves} moves, optimal is {}",
+            solution.len()
+        )),
+        Ok(_) => None,
+        Err(error) => Some(format!(
+            "level {level}: skipped optimality check, solver found no solution within depth {OPTIMAL_CHECK_MAX_DEPTH} ({error})"
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -102,16 +281,46 @@ mod tests {
         fs::write(path, serde_json::to_string_pretty(&level).unwrap()).unwrap();
     }
 
+    /// One-row corridor on a floor, solved by walking east four times
+    fn write_floor_level(path: &Path) {
+        let floor: Vec<_> = (0..5).map(|x| json!({ "x": x, "y": 1 })).collect();
+        let level = json!({
+            "id": 2,
+            "name": "Verify-All Floor Level",
+            "difficulty": "easy",
+            "gridSize": { "width": 5, "height": 2 },
+            "snake": [{ "x": 0, "y": 0 }],
+            "snakeDirection": "East",
+            "obstacles": floor,
+            "food": [],
+            "exit": { "x": 4, "y": 0 },
+            "floatingFood": [],
+            "fallingFood": [],
+            "stones": [],
+            "spikes": [],
+            "totalFood": 0
+        });
+        fs::write(path, serde_json::to_string_pretty(&level).unwrap()).unwrap();
+    }
+
+    fn level_meta(id: &str) -> LevelMeta {
+        LevelMeta {
+            id: Some(id.to_string()),
+            file: None,
+            author: Some("gsnake".to_string()),
+            solved: None,
+            difficulty: Some("easy".to_string()),
+            tags: Some(vec![]),
+            description: Some("Verify-all test level".to_string()),
+        }
+    }
+
     fn write_levels_metadata(levels_toml_path: &Path, file: &str, solved: Option<bool>) {
         let levels_toml = LevelsToml {
             level: vec![LevelMeta {
-                id: Some("verify-all-level".to_string()),
                 file: Some(file.to_string()),
-                author: Some("gsnake".to_string()),
                 solved,
-                difficulty: Some("easy".to_string()),
-                tags: Some(vec![]),
-                description: Some("Verify-all test level".to_string()),
+                ..level_meta("verify-all-level")
             }],
         };
         write_levels_toml(levels_toml_path, &levels_toml).unwrap();
@@ -123,32 +332,37 @@ mod tests {
         let levels_root = temp_dir.path().join("levels");
         let external_level = temp_dir.path().join("outside/level.json");
 
-        let error = infer_playback_path(&levels_root, &external_level).unwrap_err();
+        let error = levels::infer_playback_path(&levels_root, &external_level).unwrap_err();
         assert!(error.to_string().contains("is not under levels root"));
     }
 
     #[test]
-    fn test_run_verify_all_fails_when_level_file_missing() {
-        let _lock = crate::test_cwd::cwd_mutex()
-            .lock()
-            .expect("Failed to lock cwd mutex");
+    fn test_infer_playback_path_for_bare_relative_levels_root() {
+        let playback =
+            levels::infer_playback_path(Path::new("levels"), Path::new("levels/easy/level.json"))
+                .unwrap();
+        assert_eq!(playback, PathBuf::from("playbacks/easy/level.json"));
+    }
 
+    #[test]
+    fn test_run_verify_all_fails_when_level_file_missing() {
         let temp_dir = TempDir::new().unwrap();
         let easy_dir = temp_dir.path().join("levels/easy");
         fs::create_dir_all(&easy_dir).unwrap();
         write_levels_metadata(&easy_dir.join("levels.toml"), "missing.json", Some(true));
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
 
-        let error = run_verify_all().unwrap_err();
+        let error = run_verify_all(
+            &crate::levels::find_levels_root_from(temp_dir.path()).unwrap(),
+            None,
+            false,
+            None,
+        )
+        .unwrap_err();
         assert!(error.to_string().contains("Level file not found"));
     }
 
     #[test]
     fn test_run_verify_all_skips_missing_playback_without_mutating_status() {
-        let _lock = crate::test_cwd::cwd_mutex()
-            .lock()
-            .expect("Failed to lock cwd mutex");
-
         let temp_dir = TempDir::new().unwrap();
         let easy_dir = temp_dir.path().join("levels/easy");
         fs::create_dir_all(&easy_dir).unwrap();
@@ -157,19 +371,117 @@ mod tests {
         write_test_level(&easy_dir.join(level_file));
         write_levels_metadata(&easy_dir.join("levels.toml"), level_file, Some(true));
 
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
-        run_verify_all().expect("verify-all should skip missing playback files");
+        run_verify_all(
+            &crate::levels::find_levels_root_from(temp_dir.path()).unwrap(),
+            None,
+            false,
+            None,
+        )
+        .expect("verify-all should skip missing playback files");
 
         let updated = read_levels_toml(&easy_dir.join("levels.toml")).unwrap();
         assert_eq!(updated.level[0].solved, Some(true));
     }
 
     #[test]
-    fn test_run_verify_all_marks_unsolved_when_playback_is_invalid() {
-        let _lock = crate::test_cwd::cwd_mutex()
-            .lock()
-            .expect("Failed to lock cwd mutex");
+    fn test_run_verify_all_verifies_many_levels_in_parallel() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut expected = Vec::new();
+        for difficulty in ["easy", "medium"] {
+            let levels_dir = temp_dir.path().join("levels").join(difficulty);
+            let playbacks_dir = temp_dir.path().join("playbacks").join(difficulty);
+            fs::create_dir_all(&levels_dir).unwrap();
+            fs::create_dir_all(&playbacks_dir).unwrap();
+
+            let mut entries = Vec::new();
+            for index in 0..6 {
+                let file = format!("level-{index}.json");
+                write_floor_level(&levels_dir.join(&file));
+                // One broken playback per difficulty; the rest walk east to the exit
+                let playback = if index == 3 {
+                    "{malformed-json}".to_string()
+                } else {
+                    serde_json::to_string(&vec![json!({"key": "Right", "delay_ms": 200}); 4])
+                        .unwrap()
+                };
+                fs::write(playbacks_dir.join(&file), playback).unwrap();
+                entries.push(LevelMeta {
+                    file: Some(file),
+                    solved: None,
+                    ..level_meta("verify-all-level")
+                });
+            }
+            write_levels_toml(
+                &levels_dir.join("levels.toml"),
+                &LevelsToml { level: entries },
+            )
+            .unwrap();
+            expected.push((levels_dir.join("levels.toml"), 3));
+        }
+
+        let error = run_verify_all(
+            &crate::levels::find_levels_root_from(temp_dir.path()).unwrap(),
+            None,
+            false,
+            None,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("One or more levels failed verification"));
+
+        for (levels_toml_path, failed_index) in expected {
+            let updated = read_levels_toml(&levels_toml_path).unwrap();
+            for (index, entry) in updated.level.iter().enumerate() {
+                assert_eq!(entry.file.as_deref(), Some(&*format!("level-{index}.json")));
+                assert_eq!(entry.solved, Some(index != failed_index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_optimal_warns_on_padded_playback_but_keeps_level_solved() {
+        let temp_dir = TempDir::new().unwrap();
+        let easy_dir = temp_dir.path().join("levels/easy");
+        let playbacks_dir = temp_dir.path().join("playbacks/easy");
+        fs::create_dir_all(&easy_dir).unwrap();
+        fs::create_dir_all(&playbacks_dir).unwrap();
+
+        let level_file = "level.json";
+        write_floor_level(&easy_dir.join(level_file));
+        write_levels_metadata(&easy_dir.join("levels.toml"), level_file, None);
+        // Four moves reach the exit; the two extra ones are padding
+        let padded = vec![json!({"key": "Right", "delay_ms": 200}); 6];
+        fs::write(
+            playbacks_dir.join(level_file),
+            serde_json::to_string(&padded).unwrap(),
+        )
+        .unwrap();
+
+        let job = VerifyJob {
+            difficulty_index: 0,
+            entry_index: 0,
+            report_index: 0,
+            level_path: easy_dir.join(level_file),
+            playback_path: playbacks_dir.join(level_file),
+        };
+        let warning = optimality_warning(&job).expect("Expected a suboptimal warning");
+        assert!(warning.ends_with("playback has 6 moves, optimal is 4"));
+
+        run_verify_all(
+            &crate::levels::find_levels_root_from(temp_dir.path()).unwrap(),
+            None,
+            true,
+            None,
+        )
+        .expect("padded playback should still verify");
+
+        let updated = read_levels_toml(&easy_dir.join("levels.toml")).unwrap();
+        assert_eq!(updated.level[0].solved, Some(true));
+    }
 
+    #[test]
+    fn test_run_verify_all_marks_unsolved_when_playback_is_invalid() {
         let temp_dir = TempDir::new().unwrap();
         let easy_dir = temp_dir.path().join("levels/easy");
         let playbacks_dir = temp_dir.path().join("playbacks/easy");
@@ -181,8 +493,13 @@ mod tests {
         write_levels_metadata(&easy_dir.join("levels.toml"), level_file, Some(true));
         fs::write(playbacks_dir.join(level_file), "{malformed-json}").unwrap();
 
-        let _cwd = crate::test_cwd::CwdGuard::set(temp_dir.path());
-        let error = run_verify_all().unwrap_err();
+        let error = run_verify_all(
+            &crate::levels::find_levels_root_from(temp_dir.path()).unwrap(),
+            None,
+            false,
+            None,
+        )
+        .unwrap_err();
         assert!(error
             .to_string()
             .contains("One or more levels failed verification"));
@@ -190,4 +507,134 @@ mod tests {
         let updated = read_levels_toml(&easy_dir.join("levels.toml")).unwrap();
         assert_eq!(updated.level[0].solved, Some(false));
     }
+
+    #[test]
+    fn test_run_verify_all_includes_custom_difficulty_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        let expert_dir = levels_root.join("expert");
+        let playbacks_dir = temp_dir.path().join("playbacks/expert");
+        fs::create_dir_all(&expert_dir).unwrap();
+        fs::create_dir_all(&playbacks_dir).unwrap();
+
+        let level_file = "level.json";
+        write_floor_level(&expert_dir.join(level_file));
+        write_levels_metadata(&expert_dir.join("levels.toml"), level_file, None);
+        let walk_east = vec![json!({"key": "Right", "delay_ms": 200}); 4];
+        fs::write(
+            playbacks_dir.join(level_file),
+            serde_json::to_string(&walk_east).unwrap(),
+        )
+        .unwrap();
+
+        run_verify_all(&levels_root, None, false, None).expect("expert level should verify");
+
+        let updated = read_levels_toml(&expert_dir.join("levels.toml")).unwrap();
+        assert_eq!(updated.level[0].solved, Some(true));
+    }
+
+    #[test]
+    fn test_run_verify_all_writes_report_entry_per_level() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        let easy_dir = levels_root.join("easy");
+        let playbacks_dir = temp_dir.path().join("playbacks/easy");
+        fs::create_dir_all(&easy_dir).unwrap();
+        fs::create_dir_all(&playbacks_dir).unwrap();
+
+        let mut entries = Vec::new();
+        for file in ["passing.json", "malformed.json", "unplayed.json"] {
+            write_floor_level(&easy_dir.join(file));
+            entries.push(LevelMeta {
+                file: Some(file.to_string()),
+                ..level_meta("verify-all-level")
+            });
+        }
+        write_levels_toml(
+            &easy_dir.join("levels.toml"),
+            &LevelsToml { level: entries },
+        )
+        .unwrap();
+        let walk_east = vec![json!({"key": "Right", "delay_ms": 200}); 4];
+        fs::write(
+            playbacks_dir.join("passing.json"),
+            serde_json::to_string(&walk_east).unwrap(),
+        )
+        .unwrap();
+        fs::write(playbacks_dir.join("malformed.json"), "{malformed-json}").unwrap();
+
+        let report_path = temp_dir.path().join("report.json");
+        let error = run_verify_all(&levels_root, None, false, Some(&report_path)).unwrap_err();
+        let failed = error
+            .downcast_ref::<VerifyAllFailed>()
+            .expect("Expected verify-all failure counters");
+        assert_eq!(
+            failed.summary,
+            VerifyAllSummary {
+                verified: 1,
+                failed: 1,
+                skipped_missing_playback: 1,
+            }
+        );
+
+        let report: Vec<VerifyReportEntry> =
+            serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        let summary: Vec<_> = report
+            .iter()
+            .map(|entry| (entry.file.as_str(), entry.status, entry.solved))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("passing.json", VerifyStatus::Verified, true),
+                ("malformed.json", VerifyStatus::Failed, false),
+                ("unplayed.json", VerifyStatus::MissingPlayback, false),
+            ]
+        );
+        assert!(report.iter().all(|entry| entry.difficulty == "easy"));
+        assert_eq!(report[0].error, None);
+        assert!(report[1].error.is_some());
+    }
+
+    #[test]
+    fn test_run_verify_all_difficulty_filter_leaves_other_difficulties_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let levels_root = temp_dir.path().join("levels");
+        let walk_east = vec![json!({"key": "Right", "delay_ms": 200}); 4];
+        for difficulty in crate::levels::DEFAULT_DIFFICULTIES {
+            let levels_dir = levels_root.join(difficulty);
+            let playbacks_dir = temp_dir.path().join("playbacks").join(difficulty);
+            fs::create_dir_all(&levels_dir).unwrap();
+            fs::create_dir_all(&playbacks_dir).unwrap();
+            write_floor_level(&levels_dir.join("level.json"));
+            write_levels_metadata(&levels_dir.join("levels.toml"), "level.json", None);
+            // Only the easy playback is valid; the others would fail if verified.
+            let playback = if difficulty == "easy" {
+                serde_json::to_string(&walk_east).unwrap()
+            } else {
+                "{malformed-json}".to_string()
+            };
+            fs::write(playbacks_dir.join("level.json"), playback).unwrap();
+        }
+        let untouched: Vec<_> = ["medium", "hard"]
+            .map(|difficulty| {
+                fs::read_to_string(levels_root.join(difficulty).join("levels.toml")).unwrap()
+            })
+            .to_vec();
+
+        let summary = run_verify_all(&levels_root, Some(" Easy "), false, None).unwrap();
+        assert_eq!(summary.verified, 1);
+        assert_eq!(summary.failed, 0);
+
+        let easy = read_levels_toml(&levels_root.join("easy/levels.toml")).unwrap();
+        assert_eq!(easy.level[0].solved, Some(true));
+        for (difficulty, before) in ["medium", "hard"].into_iter().zip(untouched) {
+            let after =
+                fs::read_to_string(levels_root.join(difficulty).join("levels.toml")).unwrap();
+            assert_eq!(after, before);
+        }
+
+        let error = run_verify_all(&levels_root, Some("legendary"), false, None).unwrap_err();
+        assert!(error.to_string().contains("Unknown difficulty"));
+    }
 }
@@ -1,13 +1,14 @@
-use gsnake_levels::levels::{write_levels_toml, LevelMeta, LevelsToml};
+use gsnake_levels::levels::{write_levels_toml, LevelMeta, LevelsToml, LEVELS_ROOT_ENV};
+use gsnake_levels::validate_levels_toml::{EXIT_CODE_IO_ERROR, EXIT_CODE_PARSE_ERROR};
 use serde_json::json;
 use std::{fs, path::Path, process::Command};
 use tempfile::TempDir;
 
-fn write_test_level(path: &Path) {
+fn write_test_level(path: &Path, id: u32, difficulty: &str) {
     let level = json!({
-        "id": 1,
+        "id": id,
         "name": "CLI Test Level",
-        "difficulty": "easy",
+        "difficulty": difficulty,
         "gridSize": { "width": 5, "height": 5 },
         "snake": [{ "x": 0, "y": 0 }],
         "snakeDirection": "East",
@@ -48,19 +49,46 @@ fn write_levels_metadata_entries(levels_toml_path: &Path, entries: Vec<LevelMeta
 }
 
 fn run_levels_command(current_dir: &Path, args: &[&str]) -> std::process::Output {
-    Command::new(env!("CARGO_BIN_EXE_gsnake-levels"))
+    run_levels_command_with_root_env(current_dir, None, args)
+}
+
+fn run_levels_command_with_root_env(
+    current_dir: &Path,
+    levels_root_env: Option<&Path>,
+    args: &[&str],
+) -> std::process::Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_gsnake-levels"));
+    command
         .args(args)
         .current_dir(current_dir)
+        .env_remove(LEVELS_ROOT_ENV);
+    if let Some(levels_root) = levels_root_env {
+        command.env(LEVELS_ROOT_ENV, levels_root);
+    }
+    command
         .output()
         .expect("failed to run gsnake-levels binary")
 }
 
+/// Writes a catalog with one easy level; when `broken`, the referenced level file is left out
+fn write_catalog(levels_root: &Path, broken: bool) {
+    for difficulty in ["easy", "medium", "hard"] {
+        fs::create_dir_all(levels_root.join(difficulty)).unwrap();
+        write_levels_metadata_entries(&levels_root.join(difficulty).join("levels.toml"), vec![]);
+    }
+    let easy_dir = levels_root.join("easy");
+    if !broken {
+        write_test_level(&easy_dir.join("level.json"), 1, "easy");
+    }
+    write_levels_metadata(&easy_dir.join("levels.toml"), "level.json", Some(true));
+}
+
 #[test]
 fn test_verify_command_returns_error_for_uninferrable_playback_path() {
     let temp_dir = TempDir::new().unwrap();
     let level_path = temp_dir.path().join("custom/easy/level.json");
     fs::create_dir_all(level_path.parent().unwrap()).unwrap();
-    write_test_level(&level_path);
+    write_test_level(&level_path, 1, "easy");
 
     let output = run_levels_command(temp_dir.path(), &["verify", "custom/easy/level.json"]);
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -77,7 +105,7 @@ fn test_verify_command_returns_error_for_malformed_playback_file() {
     let playback_path = temp_dir.path().join("playbacks/easy/level.json");
     fs::create_dir_all(level_path.parent().unwrap()).unwrap();
     fs::create_dir_all(playback_path.parent().unwrap()).unwrap();
-    write_test_level(&level_path);
+    write_test_level(&level_path, 1, "easy");
     fs::write(&playback_path, "{malformed-json}").unwrap();
 
     let output = run_levels_command(temp_dir.path(), &["verify", "levels/easy/level.json"]);
@@ -95,7 +123,7 @@ fn test_verify_command_returns_error_for_invalid_playback_key() {
     let playback_path = temp_dir.path().join("playbacks/easy/level.json");
     fs::create_dir_all(level_path.parent().unwrap()).unwrap();
     fs::create_dir_all(playback_path.parent().unwrap()).unwrap();
-    write_test_level(&level_path);
+    write_test_level(&level_path, 1, "easy");
     fs::write(
         &playback_path,
         r#"[
@@ -147,7 +175,7 @@ fn test_validate_levels_toml_reports_aggregated_errors() {
         ],
     );
 
-    write_test_level(&medium_dir.join("level-medium.json"));
+    write_test_level(&medium_dir.join("level-medium.json"), 2, "medium");
     write_levels_metadata_entries(
         &medium_dir.join("levels.toml"),
         vec![create_level_meta(
@@ -157,7 +185,7 @@ fn test_validate_levels_toml_reports_aggregated_errors() {
         )],
     );
 
-    write_test_level(&hard_dir.join("level-hard.json"));
+    write_test_level(&hard_dir.join("level-hard.json"), 3, "hard");
     write_levels_metadata_entries(
         &hard_dir.join("levels.toml"),
         vec![create_level_meta(
@@ -170,8 +198,48 @@ fn test_validate_levels_toml_reports_aggregated_errors() {
     let output = run_levels_command(temp_dir.path(), &["validate-levels-toml"]);
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.status.code(), Some(EXIT_CODE_PARSE_ERROR));
     assert!(stderr.contains("Validation failed with 2 issue(s):"));
     assert!(stderr.contains("1. [io] Referenced level JSON file does not exist"));
     assert!(stderr.contains("2. [parse] Failed to parse level JSON as LevelDefinition"));
 }
+
+#[test]
+fn test_levels_root_env_var_selects_catalog_without_cwd_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let levels_root = temp_dir.path().join("catalog/levels");
+    write_catalog(&levels_root, true);
+    let elsewhere = temp_dir.path().join("elsewhere");
+    fs::create_dir_all(&elsewhere).unwrap();
+
+    let output =
+        run_levels_command_with_root_env(&elsewhere, Some(&levels_root), &["validate-levels-toml"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(EXIT_CODE_IO_ERROR));
+    assert!(stderr.contains("Referenced level JSON file does not exist"));
+    assert!(stderr.contains("catalog/levels/easy/level.json"));
+}
+
+#[test]
+fn test_levels_root_flag_overrides_env_var() {
+    let temp_dir = TempDir::new().unwrap();
+    let broken_root = temp_dir.path().join("broken/levels");
+    let valid_root = temp_dir.path().join("valid/levels");
+    write_catalog(&broken_root, true);
+    write_catalog(&valid_root, false);
+
+    let output = run_levels_command_with_root_env(
+        temp_dir.path(),
+        Some(&broken_root),
+        &[
+            "validate-levels-toml",
+            "--levels-root",
+            valid_root.to_str().unwrap(),
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout.contains("All levels.toml files are valid"));
+}
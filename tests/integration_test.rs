@@ -1,5 +1,9 @@
-use gsnake_levels::verify::verify_level;
+use gsnake_levels::{
+    playback::load_playback_directions, solver::solve_level_to_playback, verify::verify_level,
+};
+use serde_json::json;
 use std::path::Path;
+use tempfile::TempDir;
 
 #[test]
 fn test_verify_level_easy_001_succeeds() {
@@ -74,3 +78,37 @@ fn test_verify_level_with_multiple_levels() {
         }
     }
 }
+
+#[test]
+fn test_solve_and_verify_agree_on_navigation_only_level() {
+    let temp_dir = TempDir::new().unwrap();
+    let level_path = temp_dir.path().join("levels/easy/navigation.json");
+    let playback_path = temp_dir.path().join("playbacks/easy/navigation.json");
+    std::fs::create_dir_all(level_path.parent().unwrap()).unwrap();
+
+    // No food at all: reaching the exit must complete the level on its own.
+    let level = json!({
+        "id": 1,
+        "name": "Navigation Only",
+        "difficulty": "easy",
+        "gridSize": { "width": 5, "height": 2 },
+        "snake": [{ "x": 0, "y": 0 }],
+        "snakeDirection": "East",
+        "obstacles": [
+            { "x": 0, "y": 1 }, { "x": 1, "y": 1 }, { "x": 2, "y": 1 },
+            { "x": 3, "y": 1 }, { "x": 4, "y": 1 }
+        ],
+        "food": [],
+        "exit": { "x": 4, "y": 0 },
+        "floatingFood": [],
+        "fallingFood": [],
+        "stones": [],
+        "spikes": [],
+        "totalFood": 0
+    });
+    std::fs::write(&level_path, serde_json::to_string_pretty(&level).unwrap()).unwrap();
+
+    solve_level_to_playback(&level_path, &playback_path, 20).unwrap();
+    assert_eq!(load_playback_directions(&playback_path).unwrap().len(), 4);
+    verify_level(&level_path, &playback_path).unwrap();
+}
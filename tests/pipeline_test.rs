@@ -0,0 +1,103 @@
+//! End-to-end regression run over the committed fixture tree in `tests/fixtures/pipeline`.
+//!
+//! The pipeline is driven through the binary exactly as a maintainer would run it:
+//! sync metadata (names, levels.toml, solved playbacks), verify, validate, and
+//! aggregate. Outputs are compared against the goldens in
+//! `tests/fixtures/pipeline/expected`; set `UPDATE_GOLDENS=1` to rewrite them
+//! after an intentional change.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+use tempfile::TempDir;
+
+const DIFFICULTIES: [&str; 3] = ["easy", "medium", "hard"];
+
+fn fixture_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pipeline")
+}
+
+fn copy_dir(source: &Path, destination: &Path) {
+    fs::create_dir_all(destination).unwrap();
+    for entry in fs::read_dir(source).unwrap() {
+        let path = entry.unwrap().path();
+        let target = destination.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+fn run_step(current_dir: &Path, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_gsnake-levels"))
+        .args(args)
+        .current_dir(current_dir)
+        .output()
+        .expect("failed to run gsnake-levels binary");
+    assert!(
+        output.status.success(),
+        "`gsnake-levels {}` failed\nstdout:\n{}\nstderr:\n{}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn assert_golden(relative: &str, actual: &str) {
+    let golden_path = fixture_root().join("expected").join(relative);
+    if std::env::var_os("UPDATE_GOLDENS").is_some() {
+        fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+        fs::write(&golden_path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&golden_path)
+        .unwrap_or_else(|_| panic!("Missing golden {}", golden_path.display()));
+    assert_eq!(
+        actual.replace("\r\n", "\n"),
+        expected.replace("\r\n", "\n"),
+        "{relative} does not match its golden"
+    );
+}
+
+#[test]
+fn test_pipeline_matches_goldens() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    copy_dir(&fixture_root().join("levels"), &root.join("levels"));
+
+    run_step(root, &["sync-metadata"]);
+    for difficulty in DIFFICULTIES {
+        let levels_toml =
+            fs::read_to_string(root.join("levels").join(difficulty).join("levels.toml")).unwrap();
+        assert_golden(&format!("levels/{difficulty}/levels.toml"), &levels_toml);
+
+        for entry in fs::read_dir(root.join("playbacks").join(difficulty)).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            assert_golden(
+                &format!("playbacks/{difficulty}/{name}"),
+                &fs::read_to_string(&path).unwrap(),
+            );
+        }
+    }
+
+    // Playbacks written by the solver must be found again by path inference.
+    run_step(root, &["verify-all"]);
+    run_step(root, &["validate-levels-toml"]);
+
+    let aggregate = run_step(root, &["generate-levels-json", "--no-sync"]);
+    let aggregate_json: serde_json::Value = serde_json::from_slice(&aggregate.stdout).unwrap();
+    assert_golden(
+        "levels.json",
+        &(serde_json::to_string_pretty(&aggregate_json).unwrap() + "\n"),
+    );
+
+    fs::write(root.join("levels.json"), &aggregate.stdout).unwrap();
+    run_step(root, &["verify-aggregate", "levels.json"]);
+}